@@ -68,6 +68,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 			ChatStreamEvent::Chunk(chunk) => {
 				print!("{}", chunk.content);
 			}
+			ChatStreamEvent::BlockStart(block) => {
+				println!("  BlockStart: {} ({:?})", block.index, block.kind);
+			}
+			ChatStreamEvent::BlockStop(block) => {
+				println!("  BlockStop: {}", block.index);
+			}
+			ChatStreamEvent::ToolCallStart(start) => {
+				println!("  ToolCallStart: {} ({})", start.fn_name, start.call_id);
+			}
+			ChatStreamEvent::ToolCallArgsDelta(delta) => {
+				println!("  ToolCallArgsDelta: {}", delta.delta);
+			}
+			ChatStreamEvent::ToolCallEnd(end) => {
+				println!("  ToolCallEnd: {}", end.call_id);
+			}
+			ChatStreamEvent::Usage(usage) => {
+				println!("  Usage: {:?} output tokens", usage.completion_tokens);
+			}
 			ChatStreamEvent::ToolCallChunk(chunk) => {
 				println!("  ToolCallChunk: {:?}", chunk.tool_call);
 			}
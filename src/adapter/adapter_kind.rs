@@ -1,4 +1,5 @@
 use crate::adapter::adapters::ollama::OllamaAdapter;
+use crate::adapter::adapters::openai_compat::OpenAICompatAdapter;
 use crate::adapter::adapters::openai_resp::OpenAIRespAdapter;
 use crate::adapter::adapters::together::TogetherAdapter;
 use crate::adapter::adapters::zai::ZaiAdapter;
@@ -10,9 +11,12 @@ use crate::adapter::deepseek::DeepSeekAdapter;
 use crate::adapter::fireworks::FireworksAdapter;
 use crate::adapter::gemini::GeminiAdapter;
 use crate::adapter::groq::GroqAdapter;
+use crate::adapter::huggingface::HuggingFaceAdapter;
 use crate::adapter::mimo::MimoAdapter;
+use crate::adapter::mock::MockAdapter;
 use crate::adapter::nebius::NebiusAdapter;
 use crate::adapter::openai::OpenAIAdapter;
+use crate::adapter::perplexity::PerplexityAdapter;
 use crate::adapter::xai::XaiAdapter;
 use crate::adapter::{Adapter as _, zai};
 use crate::{ModelName, Result};
@@ -54,8 +58,17 @@ pub enum AdapterKind {
 	Aliyun,
 	/// Cohere today use it's own native protocol but might move to OpenAI Adapter
 	Cohere,
+	/// For Hugging Face Inference Providers and TGI (Mostly use OpenAI, only accessible via namespace `hf::`)
+	HuggingFace,
+	/// For Perplexity Sonar (Mostly use OpenAI, plus search options and citations)
+	Perplexity,
 	/// OpenAI shared behavior + some custom. (currently, localhost only, can be customize with ServerTargetResolver).
 	Ollama,
+	/// Generic OpenAI compatible backends (vLLM, SGLang, LM Studio, LiteLLM, ...) driven by a `CompatProfile`
+	/// (only accessible via namespace `openai_compat::` or the namespace of a client `CompatProfile`)
+	OpenAICompat,
+	/// In-process scripted replies for tests, without HTTP (only accessible via namespace `mock::`, see `MockScript`)
+	Mock,
 }
 
 /// Serialization/Parse implementations
@@ -78,7 +91,11 @@ impl AdapterKind {
 			AdapterKind::BigModel => "BigModel",
 			AdapterKind::Aliyun => "Aliyun",
 			AdapterKind::Cohere => "Cohere",
+			AdapterKind::HuggingFace => "HuggingFace",
+			AdapterKind::Perplexity => "Perplexity",
 			AdapterKind::Ollama => "Ollama",
+			AdapterKind::OpenAICompat => "OpenAICompat",
+			AdapterKind::Mock => "Mock",
 		}
 	}
 
@@ -100,7 +117,11 @@ impl AdapterKind {
 			AdapterKind::BigModel => "bigmodel",
 			AdapterKind::Aliyun => "aliyun",
 			AdapterKind::Cohere => "cohere",
+			AdapterKind::HuggingFace => "hf",
+			AdapterKind::Perplexity => "perplexity",
 			AdapterKind::Ollama => "ollama",
+			AdapterKind::OpenAICompat => "openai_compat",
+			AdapterKind::Mock => "mock",
 		}
 	}
 
@@ -121,7 +142,11 @@ impl AdapterKind {
 			"bigmodel" => Some(AdapterKind::BigModel),
 			"aliyun" => Some(AdapterKind::Aliyun),
 			"cohere" => Some(AdapterKind::Cohere),
+			"hf" => Some(AdapterKind::HuggingFace),
+			"perplexity" => Some(AdapterKind::Perplexity),
 			"ollama" => Some(AdapterKind::Ollama),
+			"openai_compat" => Some(AdapterKind::OpenAICompat),
+			"mock" => Some(AdapterKind::Mock),
 			_ => None,
 		}
 	}
@@ -147,7 +172,11 @@ impl AdapterKind {
 			AdapterKind::BigModel => BigModelAdapter::DEFAULT_API_KEY_ENV_NAME,
 			AdapterKind::Aliyun => AliyunAdapter::DEFAULT_API_KEY_ENV_NAME,
 			AdapterKind::Cohere => CohereAdapter::DEFAULT_API_KEY_ENV_NAME,
+			AdapterKind::HuggingFace => HuggingFaceAdapter::DEFAULT_API_KEY_ENV_NAME,
+			AdapterKind::Perplexity => PerplexityAdapter::DEFAULT_API_KEY_ENV_NAME,
 			AdapterKind::Ollama => OllamaAdapter::DEFAULT_API_KEY_ENV_NAME,
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::DEFAULT_API_KEY_ENV_NAME,
+			AdapterKind::Mock => MockAdapter::DEFAULT_API_KEY_ENV_NAME,
 		}
 	}
 }
@@ -167,12 +196,15 @@ impl AdapterKind {
 	///  - DeepSeek   - model in DeepSeek models (deepseek.com)
 	///  - Zhipu      - starts_with "glm"
 	///  - Cohere     - starts_with "command"
+	///  - Perplexity - starts_with "sonar"
 	///  - Ollama     - For anything else
 	///
 	/// Other Some adapters have to have model name namespaced to be used,
 	/// - e.g., for together.ai `together::meta-llama/Llama-3-8b-chat-hf`
 	/// - e.g., for nebius with `nebius::Qwen/Qwen3-235B-A22B`
+	/// - e.g., for Hugging Face with `hf::meta-llama/Llama-3.1-8B-Instruct`
 	/// - e.g., for ZAI coding plan with `coding::glm-4.6`
+	/// - e.g., for a `CompatProfile` of the client with `vllm::Qwen/Qwen3-8B` (resolved by the `Client`, not here)
 	///
 	/// And all adapters can be force namspaced as well.
 	///
@@ -212,6 +244,8 @@ impl AdapterKind {
 			Ok(Self::Mimo)
 		} else if model.starts_with("command") || model.starts_with("embed-") {
 			Ok(Self::Cohere)
+		} else if model.starts_with("sonar") {
+			Ok(Self::Perplexity)
 		} else if model.starts_with("grok") {
 			Ok(Self::Xai)
 		} else if model.starts_with("glm") {
@@ -230,7 +264,7 @@ impl AdapterKind {
 
 /// Inner api to return
 impl AdapterKind {
-	fn from_model_namespace(model: &str) -> Option<Self> {
+	pub(crate) fn from_model_namespace(model: &str) -> Option<Self> {
 		let (namespace, _) = ModelName::split_as_namespace_and_name(model);
 		let namespace = namespace?;
 
@@ -1,11 +1,10 @@
 use crate::adapter::AdapterKind;
-use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
+use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse, TokenCount};
 use crate::embed::{EmbedOptionsSet, EmbedRequest, EmbedResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
-use crate::{Headers, ModelIden};
-use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{Error, Result, ServiceTarget};
+use crate::{Headers, ModelIden, ModelInfo};
 use serde_json::Value;
 
 pub trait Adapter {
@@ -18,6 +17,10 @@ pub trait Adapter {
 	// NOTE: Adapter is a crate trait, so it is acceptable to use async fn here.
 	async fn all_model_names(kind: AdapterKind) -> Result<Vec<String>>;
 
+	/// List the models of the provider models endpoint, with the resolved service target (endpoint & auth).
+	/// NOTE: The model name of the service target can be empty (when listed by `AdapterKind`).
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>>;
+
 	/// The base service URL for this AdapterKind for the given service type.
 	/// NOTE: For some services, the URL will be further updated in the to_web_request_data method.
 	fn get_service_url(model_iden: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String>;
@@ -40,10 +43,28 @@ pub trait Adapter {
 	/// To be implemented by Adapters.
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse>;
 
+	/// The token counting request of the provider API, for `Client::count_tokens(...)`.
+	/// NOTE: Returns `None` by default (no provider API), and the client then estimates the tokens locally.
+	fn to_count_tokens_request_data(
+		_service_target: ServiceTarget,
+		_chat_req: ChatRequest,
+		_options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<Option<WebRequestData>> {
+		Ok(None)
+	}
+
+	/// To be implemented by the Adapters implementing `to_count_tokens_request_data`.
+	fn to_token_count(model_iden: ModelIden, _web_response: WebResponse) -> Result<TokenCount> {
+		Err(Error::AdapterNotSupported {
+			adapter_kind: model_iden.adapter_kind,
+			feature: "count_tokens".to_string(),
+		})
+	}
+
 	/// To be implemented by Adapters.
 	fn to_embed_request_data(
 		service_target: ServiceTarget,
@@ -73,6 +94,9 @@ pub enum ServiceType {
 // region:    --- WebRequestData
 
 // NOTE: This cannot really move to `webc` because it must be public with the adapter, and `webc` is private for now.
+/// The HTTP request of a provider call (URL, headers, and JSON payload).
+///
+/// Returned by `Client::build_chat_request(...)` and `Client::build_embed_request(...)` (with redacted headers).
 #[derive(Debug, Clone)]
 pub struct WebRequestData {
 	pub url: String,
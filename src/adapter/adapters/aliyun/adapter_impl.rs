@@ -4,9 +4,8 @@ use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
-use crate::{ModelIden, Result, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{ModelIden, ModelInfo, Result, ServiceTarget};
 
 /// Aliyun Adapter - Uses OpenAI-compatible API for Dashscope (Aliyun)
 ///
@@ -74,6 +73,11 @@ impl Adapter for AliyunAdapter {
 		Ok(MODELS.iter().map(|s| s.to_string()).collect())
 	}
 
+	/// Note: Returns the static list above (model ids only)
+	async fn list_models(_service_target: ServiceTarget, _web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		Ok(MODELS.iter().map(|id| ModelInfo::new(AdapterKind::Aliyun, *id)).collect())
+	}
+
 	/// Returns the service URL for the given model
 	///
 	/// Since Aliyun Dashscope API is OpenAI-compatible, we use the OpenAI URL pattern.
@@ -116,10 +120,10 @@ impl Adapter for AliyunAdapter {
 	/// Delegates to OpenAIAdapter due to API compatibility.
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		OpenAIAdapter::to_chat_stream(model_iden, reqwest_builder, options_set)
+		OpenAIAdapter::to_chat_stream(model_iden, stream_source, options_set)
 	}
 
 	/// Converts embedding request data to web request format
@@ -1,16 +1,16 @@
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_no_regex_response_format, get_api_key};
 use crate::adapter::anthropic::AnthropicStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	Binary, BinarySource, CacheControl, CacheCreationDetails, ChatOptionsSet, ChatRequest, ChatResponse, ChatRole,
-	ChatStream, ChatStreamResponse, ContentPart, MessageContent, PromptTokensDetails, ReasoningEffort, Tool, ToolCall,
-	ToolConfig, ToolName, Usage,
+	ChatStream, ChatStreamResponse, ContentPart, MessageContent, PromptTokensDetails, ReasoningEffort, TokenCount,
+	Tool, ToolCall, ToolConfig, ToolName, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{EventSourceStream, WebResponse};
-use crate::{Headers, ModelIden};
+use crate::support::rfc3339_to_unix_secs;
+use crate::webc::{EventSourceStream, WebClient, WebResponse, WebStreamSource};
+use crate::{Headers, ModelIden, ModelInfo};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
 use serde_json::{Value, json};
 use tracing::warn;
 use value_ext::JsonValueExt;
@@ -128,6 +128,49 @@ impl Adapter for AnthropicAdapter {
 		Self::list_model_names_for_end_target(kind, Self::default_endpoint(), Self::default_auth()).await
 	}
 
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		let ServiceTarget { model, auth, endpoint } = service_target;
+
+		// -- url
+		let base_url = endpoint.base_url();
+		let url = format!("{base_url}models?limit=1000");
+
+		// -- auth / headers
+		let api_key = get_api_key(auth, &model)?;
+		let headers = Headers::from(vec![
+			("x-api-key".to_string(), api_key),
+			("anthropic-version".to_string(), ANTHROPIC_VERSION.to_string()),
+		]);
+
+		// -- Exec request
+		let mut res = web_client
+			.do_get(&url, &headers)
+			.await
+			.map_err(|webc_error| crate::Error::WebAdapterCall {
+				adapter_kind: model.adapter_kind,
+				webc_error,
+			})?;
+
+		let models: Vec<Value> = res.body.x_take("data").unwrap_or_default();
+		let infos = models
+			.into_iter()
+			.filter_map(|mut model_value| {
+				let id: String = model_value.x_take("id").ok()?;
+				let mut info = ModelInfo::new(model.adapter_kind, id);
+				info.display_name = model_value.x_take("display_name").ok();
+				info.created = model_value
+					.x_get::<String>("created_at")
+					.ok()
+					.and_then(|created_at| rfc3339_to_unix_secs(&created_at));
+				info.context_window = model_value.x_get("max_input_tokens").ok();
+				info.max_output_tokens = model_value.x_get("max_tokens").ok();
+				Some(info)
+			})
+			.collect();
+
+		Ok(infos)
+	}
+
 	fn get_service_url(_model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
 		let base_url = endpoint.base_url();
 		let url = match service_type {
@@ -145,6 +188,7 @@ impl Adapter for AnthropicAdapter {
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<WebRequestData> {
 		let ServiceTarget { endpoint, auth, model } = target;
+		check_no_regex_response_format(&model, &options_set)?;
 
 		// -- api_key
 		let api_key = get_api_key(auth, &model)?;
@@ -290,6 +334,39 @@ impl Adapter for AnthropicAdapter {
 		Ok(WebRequestData { url, headers, payload })
 	}
 
+	/// Anthropic `messages/count_tokens`, with the chat request payload (without the generation properties).
+	fn to_count_tokens_request_data(
+		target: ServiceTarget,
+		chat_req: ChatRequest,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<Option<WebRequestData>> {
+		let WebRequestData {
+			url,
+			headers,
+			payload: mut chat_payload,
+		} = Self::to_web_request_data(target, ServiceType::Chat, chat_req, options_set)?;
+
+		let url = format!("{url}/count_tokens");
+
+		// Only the properties accepted by the count_tokens API
+		let mut payload = json!({});
+		for name in ["model", "messages", "system", "tools", "tool_choice", "thinking"] {
+			if let Ok(value) = chat_payload.x_take::<Value>(name)
+				&& !value.is_null()
+			{
+				payload.x_insert(name, value)?;
+			}
+		}
+
+		Ok(Some(WebRequestData { url, headers, payload }))
+	}
+
+	fn to_token_count(model_iden: ModelIden, web_response: WebResponse) -> Result<TokenCount> {
+		let WebResponse { mut body, .. } = web_response;
+		let input_tokens: u32 = body.x_take("input_tokens")?;
+		Ok(TokenCount::from_provider(model_iden, input_tokens))
+	}
+
 	fn to_chat_response(
 		model_iden: ModelIden,
 		web_response: WebResponse,
@@ -360,18 +437,19 @@ impl Adapter for AnthropicAdapter {
 			model_iden,
 			provider_model_iden,
 			usage,
+			citations: None,
 			captured_raw_body: None, // Set by the client exec_chat
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let event_source = EventSourceStream::new(reqwest_builder);
+		let event_source = EventSourceStream::new(stream_source);
 		let anthropic_stream = AnthropicStreamer::new(event_source, model_iden.clone(), options_set);
-		let chat_stream = ChatStream::from_inter_stream(anthropic_stream);
+		let chat_stream = ChatStream::from_inter_stream(model_iden.clone(), anthropic_stream);
 		Ok(ChatStreamResponse {
 			model_iden,
 			stream: chat_stream,
@@ -1,10 +1,13 @@
 use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions};
 use crate::adapter::anthropic::parse_cache_creation_details;
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
-use crate::chat::{ChatOptionsSet, PromptTokensDetails, ToolCall, Usage};
+use crate::chat::{
+	ChatOptionsSet, PromptTokensDetails, ToolCall, ToolCallArgsDelta, ToolCallEnd, ToolCallStart, Usage,
+};
 use crate::webc::{Event, EventSourceStream};
 use crate::{Error, ModelIden, Result};
 use serde_json::{Map, Value};
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use value_ext::JsonValueExt;
@@ -19,6 +22,9 @@ pub struct AnthropicStreamer {
 
 	captured_data: StreamerCapturedData,
 	in_progress_block: InProgressBlock,
+	/// The number of tool calls started (the index of the next `ToolCallStart`)
+	tool_call_count: usize,
+	pending_events: VecDeque<InterStreamEvent>,
 }
 
 enum InProgressBlock {
@@ -35,6 +41,8 @@ impl AnthropicStreamer {
 			options: StreamerOptions::new(model_iden, options_set),
 			captured_data: Default::default(),
 			in_progress_block: InProgressBlock::Text,
+			tool_call_count: 0,
+			pending_events: VecDeque::new(),
 		}
 	}
 }
@@ -43,6 +51,9 @@ impl futures::Stream for AnthropicStreamer {
 	type Item = Result<InterStreamEvent>;
 
 	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if let Some(event) = self.pending_events.pop_front() {
+			return Poll::Ready(Some(Ok(event)));
+		}
 		if self.done {
 			return Poll::Ready(None);
 		}
@@ -55,13 +66,13 @@ impl futures::Stream for AnthropicStreamer {
 					let message_type = message.event.as_str();
 
 					match message_type {
-						"message_start" => {
-							self.capture_usage(message_type, &message.data)?;
-							continue;
-						}
-						"message_delta" => {
+						// -- Interim usage (input tokens on start, output tokens on delta)
+						"message_start" | "message_delta" => {
 							self.capture_usage(message_type, &message.data)?;
-							continue;
+							match self.usage_so_far() {
+								Some(usage) => return Poll::Ready(Some(Ok(InterStreamEvent::Usage(usage)))),
+								None => continue,
+							}
 						}
 						"content_block_start" => {
 							let mut data: Value =
@@ -74,11 +85,21 @@ impl futures::Stream for AnthropicStreamer {
 								Ok("text") => self.in_progress_block = InProgressBlock::Text,
 								Ok("thinking") => self.in_progress_block = InProgressBlock::Thinking,
 								Ok("tool_use") => {
+									let id: String = data.x_take("/content_block/id")?;
+									let name: String = data.x_take("/content_block/name")?;
+									let tool_call_start = ToolCallStart {
+										call_id: id.clone(),
+										fn_name: name.clone(),
+										index: self.tool_call_count,
+									};
+									self.tool_call_count += 1;
 									self.in_progress_block = InProgressBlock::ToolUse {
-										id: data.x_take("/content_block/id")?,
-										name: data.x_take("/content_block/name")?,
+										id,
+										name,
 										input: String::new(),
 									};
+
+									return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallStart(tool_call_start))));
 								}
 								Ok(txt) => {
 									tracing::warn!("unhandled content type: {txt}");
@@ -111,9 +132,20 @@ impl futures::Stream for AnthropicStreamer {
 
 									return Poll::Ready(Some(Ok(InterStreamEvent::Chunk(content))));
 								}
-								InProgressBlock::ToolUse { input, .. } => {
-									input.push_str(data.x_get_str("/delta/partial_json")?);
-									continue;
+								InProgressBlock::ToolUse { id, input, .. } => {
+									let partial_json = data.x_get_str("/delta/partial_json")?;
+									// NOTE: The first delta is often empty, so no event for it
+									if partial_json.is_empty() {
+										continue;
+									}
+									input.push_str(partial_json);
+
+									return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallArgsDelta(
+										ToolCallArgsDelta {
+											call_id: id.clone(),
+											delta: partial_json.to_string(),
+										},
+									))));
 								}
 								InProgressBlock::Thinking => {
 									if let Ok(thinking) = data.x_take::<String>("/delta/thinking") {
@@ -149,6 +181,7 @@ impl futures::Stream for AnthropicStreamer {
 										serde_json::from_str(&input)?
 									};
 
+									let end_call_id = id.clone();
 									let tc = ToolCall {
 										call_id: id,
 										fn_name: name,
@@ -164,7 +197,11 @@ impl futures::Stream for AnthropicStreamer {
 										}
 									}
 
-									return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallChunk(tc))));
+									// Return the ToolCallEnd, then the complete ToolCallChunk
+									self.pending_events.push_back(InterStreamEvent::ToolCallChunk(tc));
+									return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallEnd(ToolCallEnd {
+										call_id: end_call_id,
+									}))));
 								}
 								_ => {
 									// no-op for remaining block types
@@ -182,15 +219,7 @@ impl futures::Stream for AnthropicStreamer {
 
 							// Capture the usage
 							let captured_usage = if self.options.capture_usage {
-								self.captured_data.usage.take().map(|mut usage| {
-									// Compute the total if any of input/output are not null
-									if usage.prompt_tokens.is_some() || usage.completion_tokens.is_some() {
-										usage.total_tokens = Some(
-											usage.prompt_tokens.unwrap_or(0) + usage.completion_tokens.unwrap_or(0),
-										);
-									}
-									usage
-								})
+								self.usage_so_far()
 							} else {
 								None
 							};
@@ -201,6 +230,7 @@ impl futures::Stream for AnthropicStreamer {
 								captured_reasoning_content: self.captured_data.reasoning_content.take(),
 								captured_tool_calls: self.captured_data.tool_calls.take(),
 								captured_thought_signatures: None,
+								captured_citations: None,
 							};
 
 							// TODO: Need to capture the data as needed
@@ -228,82 +258,90 @@ impl futures::Stream for AnthropicStreamer {
 
 // Support
 impl AnthropicStreamer {
+	/// Accumulates the usage of the message (for the interim `Usage` events, and the captured usage).
 	fn capture_usage(&mut self, message_type: &str, message_data: &str) -> Result<()> {
-		if self.options.capture_usage {
-			let data = self.parse_message_data(message_data)?;
-			// TODO: Might want to exit early if usage is not found
-
-			let (input_path, output_path) = if message_type == "message_start" {
-				("/message/usage/input_tokens", "/message/usage/output_tokens")
-			} else if message_type == "message_delta" {
-				("/usage/input_tokens", "/usage/output_tokens")
-			} else {
-				// TODO: Use tracing
-				tracing::debug!(
-					"TRACING DEBUG - Anthropic message type not supported for input/output tokens: {message_type}"
-				);
-				return Ok(()); // For now permissive
-			};
-
-			// -- Capture/Add the eventual input_tokens
-			// NOTE: Permissive on this one; if an error occurs, treat it as nonexistent (for now)
-			if let Ok(input_tokens) = data.x_get::<i32>(input_path) {
-				let val = self
-					.captured_data
-					.usage
-					.get_or_insert(Usage::default())
-					.prompt_tokens
-					.get_or_insert(0);
-				*val += input_tokens;
-			}
-
-			if let Ok(output_tokens) = data.x_get::<i32>(output_path) {
-				let val = self
-					.captured_data
-					.usage
-					.get_or_insert(Usage::default())
-					.completion_tokens
-					.get_or_insert(0);
-				*val += output_tokens;
-			}
+		let data = self.parse_message_data(message_data)?;
+		// TODO: Might want to exit early if usage is not found
+
+		let (input_path, output_path) = if message_type == "message_start" {
+			("/message/usage/input_tokens", "/message/usage/output_tokens")
+		} else if message_type == "message_delta" {
+			("/usage/input_tokens", "/usage/output_tokens")
+		} else {
+			// TODO: Use tracing
+			tracing::debug!(
+				"TRACING DEBUG - Anthropic message type not supported for input/output tokens: {message_type}"
+			);
+			return Ok(()); // For now permissive
+		};
+
+		// -- Capture/Add the eventual input_tokens
+		// NOTE: Permissive on this one; if an error occurs, treat it as nonexistent (for now)
+		if let Ok(input_tokens) = data.x_get::<i32>(input_path) {
+			let val = self
+				.captured_data
+				.usage
+				.get_or_insert(Usage::default())
+				.prompt_tokens
+				.get_or_insert(0);
+			*val += input_tokens;
+		}
 
-			// -- Capture cache tokens (only present in message_start)
-			// NOTE: Anthropic's input_tokens does NOT include cached tokens, so we must add them.
-			// See also: AnthropicAdapter::into_usage() for non-streaming equivalent.
-			if message_type == "message_start" {
-				let cache_creation: i32 = data.x_get("/message/usage/cache_creation_input_tokens").unwrap_or(0);
-				let cache_read: i32 = data.x_get("/message/usage/cache_read_input_tokens").unwrap_or(0);
-
-				// Parse cache_creation breakdown if present (TTL-specific breakdown)
-				// Use x_get with JSON pointer to navigate to /message/usage/cache_creation
-				let cache_creation_details = data
-					.x_get::<Value>("/message/usage/cache_creation")
-					.ok()
-					.as_ref()
-					.and_then(parse_cache_creation_details);
-
-				if cache_creation > 0 || cache_read > 0 || cache_creation_details.is_some() {
-					let usage = self.captured_data.usage.get_or_insert(Usage::default());
-
-					// Add cache tokens to prompt_tokens (same as into_usage does)
-					if let Some(ref mut pt) = usage.prompt_tokens {
-						*pt += cache_creation + cache_read;
-					}
+		if let Ok(output_tokens) = data.x_get::<i32>(output_path) {
+			let val = self
+				.captured_data
+				.usage
+				.get_or_insert(Usage::default())
+				.completion_tokens
+				.get_or_insert(0);
+			*val += output_tokens;
+		}
 
-					// Set prompt_tokens_details (match into_usage behavior: always Some(value))
-					usage.prompt_tokens_details = Some(PromptTokensDetails {
-						cache_creation_tokens: Some(cache_creation),
-						cache_creation_details,
-						cached_tokens: Some(cache_read),
-						audio_tokens: None,
-					});
+		// -- Capture cache tokens (only present in message_start)
+		// NOTE: Anthropic's input_tokens does NOT include cached tokens, so we must add them.
+		// See also: AnthropicAdapter::into_usage() for non-streaming equivalent.
+		if message_type == "message_start" {
+			let cache_creation: i32 = data.x_get("/message/usage/cache_creation_input_tokens").unwrap_or(0);
+			let cache_read: i32 = data.x_get("/message/usage/cache_read_input_tokens").unwrap_or(0);
+
+			// Parse cache_creation breakdown if present (TTL-specific breakdown)
+			// Use x_get with JSON pointer to navigate to /message/usage/cache_creation
+			let cache_creation_details = data
+				.x_get::<Value>("/message/usage/cache_creation")
+				.ok()
+				.as_ref()
+				.and_then(parse_cache_creation_details);
+
+			if cache_creation > 0 || cache_read > 0 || cache_creation_details.is_some() {
+				let usage = self.captured_data.usage.get_or_insert(Usage::default());
+
+				// Add cache tokens to prompt_tokens (same as into_usage does)
+				if let Some(ref mut pt) = usage.prompt_tokens {
+					*pt += cache_creation + cache_read;
 				}
+
+				// Set prompt_tokens_details (match into_usage behavior: always Some(value))
+				usage.prompt_tokens_details = Some(PromptTokensDetails {
+					cache_creation_tokens: Some(cache_creation),
+					cache_creation_details,
+					cached_tokens: Some(cache_read),
+					audio_tokens: None,
+				});
 			}
 		}
 
 		Ok(())
 	}
 
+	/// The usage accumulated so far, with the computed total (if any of input/output are not null).
+	fn usage_so_far(&self) -> Option<Usage> {
+		let mut usage = self.captured_data.usage.clone()?;
+		if usage.prompt_tokens.is_some() || usage.completion_tokens.is_some() {
+			usage.total_tokens = Some(usage.prompt_tokens.unwrap_or(0) + usage.completion_tokens.unwrap_or(0));
+		}
+		Some(usage)
+	}
+
 	/// Simple wrapper for now, with the corresponding map_err.
 	/// Might have more logic later.
 	fn parse_message_data(&self, payload: &str) -> Result<Value> {
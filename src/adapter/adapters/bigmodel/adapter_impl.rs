@@ -1,11 +1,10 @@
-use crate::ModelIden;
 use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{ModelIden, ModelInfo};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
 
 /// The BigModel adapter. Only available via namespace.
 ///
@@ -35,6 +34,10 @@ impl Adapter for BigModelAdapter {
 		OpenAIAdapter::list_model_names_for_end_target(kind, Self::default_endpoint(), Self::default_auth()).await
 	}
 
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		OpenAIAdapter::util_list_models(service_target, web_client).await
+	}
+
 	fn get_service_url(_model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
 		// For ZAI, we need to handle model-specific routing at this level
 		// because get_service_url is called with the modified endpoint from to_web_request_data
@@ -67,10 +70,10 @@ impl Adapter for BigModelAdapter {
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		OpenAIAdapter::to_chat_stream(model_iden, reqwest_builder, options_set)
+		OpenAIAdapter::to_chat_stream(model_iden, stream_source, options_set)
 	}
 
 	fn to_embed_request_data(
@@ -1,26 +1,29 @@
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_no_regex_response_format, get_api_key};
 use crate::adapter::cohere::CohereStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
-	ChatOptionsSet, ChatRequest, ChatResponse, ChatRole, ChatStream, ChatStreamResponse, MessageContent, Usage,
+	ChatOptionsSet, ChatRequest, ChatResponse, ChatResponseFormat, ChatRole, ChatStream, ChatStreamResponse, Citation,
+	CitationSource, ContentPart, MessageContent, ToolCall, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{WebResponse, WebStream};
+use crate::webc::{EventSourceStream, WebClient, WebResponse, WebStreamSource};
 use crate::{Error, Headers, Result};
-use crate::{ModelIden, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::{Modality, ModelIden, ModelInfo, ServiceTarget};
 use serde_json::{Value, json};
+use tracing::warn;
 use value_ext::JsonValueExt;
 
 pub struct CohereAdapter;
 
 const MODELS: &[&str] = &[
+	"command-a-03-2025",
+	"command-a-reasoning-08-2025",
+	"command-a-vision-07-2025",
+	"command-r7b-12-2024",
+	"command-r-plus-08-2024",
+	"command-r-08-2024",
 	"command-r-plus",
 	"command-r",
-	"command",
-	"command-nightly",
-	"command-light",
-	"command-light-nightly",
 ];
 
 impl CohereAdapter {
@@ -31,7 +34,7 @@ impl Adapter for CohereAdapter {
 	const DEFAULT_API_KEY_ENV_NAME: Option<&'static str> = Some(Self::API_KEY_DEFAULT_ENV_NAME);
 
 	fn default_endpoint() -> Endpoint {
-		const BASE_URL: &str = "https://api.cohere.com/v1/";
+		const BASE_URL: &str = "https://api.cohere.com/v2/";
 		Endpoint::from_static(BASE_URL)
 	}
 
@@ -47,15 +50,42 @@ impl Adapter for CohereAdapter {
 		Ok(MODELS.iter().map(|s| s.to_string()).collect())
 	}
 
+	/// Note: The models endpoint is only in the v1 API (`{base_url_without_v2}v1/models`)
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		let ServiceTarget { model, auth, endpoint } = service_target;
+
+		// -- url
+		let base_url = endpoint.base_url();
+		let base_without_version = base_url.trim_end_matches("v2/");
+		let url = format!("{base_without_version}v1/models?page_size=1000");
+
+		// -- auth / headers
+		let api_key = get_api_key(auth, &model)?;
+		let headers = Headers::from(("Authorization".to_string(), format!("Bearer {api_key}")));
+
+		// -- Exec request
+		let mut res = web_client
+			.do_get(&url, &headers)
+			.await
+			.map_err(|webc_error| Error::WebAdapterCall {
+				adapter_kind: model.adapter_kind,
+				webc_error,
+			})?;
+
+		let models: Vec<Value> = res.body.x_take("models").unwrap_or_default();
+		let infos = models
+			.into_iter()
+			.filter_map(|model_value| Self::into_model_info(model.adapter_kind, model_value))
+			.collect();
+
+		Ok(infos)
+	}
+
 	fn get_service_url(_model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
 		let base_url = endpoint.base_url();
 		let url = match service_type {
 			ServiceType::Chat | ServiceType::ChatStream => format!("{base_url}chat"),
-			ServiceType::Embed => {
-				//HACK: Cohere embeddings use v2 API, but base_url is v1, so we need to replace it
-				let base_without_version = base_url.trim_end_matches("v1/");
-				format!("{base_without_version}v2/embed")
-			}
+			ServiceType::Embed => format!("{base_url}embed"),
 		};
 		Ok(url)
 	}
@@ -67,6 +97,7 @@ impl Adapter for CohereAdapter {
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<WebRequestData> {
 		let ServiceTarget { endpoint, auth, model } = target;
+		check_no_regex_response_format(&model, &options_set)?;
 
 		// -- api_key (this Adapter requires it)
 		let api_key = get_api_key(auth, &model)?;
@@ -79,9 +110,9 @@ impl Adapter for CohereAdapter {
 
 		// -- parts
 		let CohereChatRequestParts {
-			preamble,
-			message,
-			chat_history,
+			messages,
+			tools,
+			documents,
 		} = Self::into_cohere_request_parts(model.clone(), chat_req)?;
 
 		// -- Build the basic payload
@@ -89,15 +120,30 @@ impl Adapter for CohereAdapter {
 		let stream = matches!(service_type, ServiceType::ChatStream);
 		let mut payload = json!({
 			"model": model_name.to_string(),
-			"message": message,
+			"messages": messages,
 			"stream": stream
 		});
 
-		if !chat_history.is_empty() {
-			payload.x_insert("chat_history", chat_history)?;
+		if let Some(tools) = tools {
+			payload.x_insert("tools", tools)?;
 		}
-		if let Some(preamble) = preamble {
-			payload.x_insert("preamble", preamble)?;
+		if let Some(documents) = documents {
+			payload.x_insert("documents", documents)?;
+		}
+
+		// -- Add the response format
+		// see: https://docs.cohere.com/docs/structured-outputs
+		let response_format = match options_set.response_format() {
+			Some(ChatResponseFormat::JsonMode) => Some(json!({"type": "json_object"})),
+			Some(ChatResponseFormat::JsonSpec(st_json)) => Some(json!({
+				"type": "json_object",
+				"json_schema": st_json.schema.clone(),
+			})),
+			// Not supported by Cohere (rejected above)
+			Some(ChatResponseFormat::Regex(_)) | None => None,
+		};
+		if let Some(response_format) = response_format {
+			payload.x_insert("response_format", response_format)?;
 		}
 
 		// -- Add supported ChatOptions
@@ -117,6 +163,10 @@ impl Adapter for CohereAdapter {
 			payload.x_insert("p", top_p)?;
 		}
 
+		if let Some(seed) = options_set.seed() {
+			payload.x_insert("seed", seed)?;
+		}
+
 		Ok(WebRequestData { url, headers, payload })
 	}
 
@@ -128,42 +178,78 @@ impl Adapter for CohereAdapter {
 		let WebResponse { mut body, .. } = web_response;
 
 		// -- Capture the provider_model_iden
-		// TODO: Need to be implemented (if available), for now, just clone model_iden
-		// let provider_model_name: Option<String> = body.x_remove("model").ok();
-		let provider_model_name = None;
-		let provider_model_iden = model_iden.from_optional_name(provider_model_name);
+		// NOTE: The v2 chat response does not return the model name, so, just clone model_iden
+		let provider_model_iden = model_iden.from_optional_name(None);
 
 		// -- Get usage
-		let usage = body.x_take("/meta/tokens").map(Self::into_usage).unwrap_or_default();
+		let usage = body.x_take("/usage/tokens").map(Self::into_usage).unwrap_or_default();
 
 		// -- Get response
-		let Some(mut last_chat_history_item) = body.x_take::<Vec<Value>>("chat_history")?.pop() else {
+		let Ok(mut message) = body.x_take::<Value>("message") else {
 			return Err(Error::NoChatResponse { model_iden });
 		};
 
-		let content: MessageContent = last_chat_history_item
-			.x_take::<Option<String>>("message")?
-			.map(MessageContent::from)
-			.unwrap_or_default();
+		// -- Capture the text and thinking content
+		let mut content = MessageContent::default();
+		let mut reasoning_content: Option<String> = None;
+		if let Ok(Value::Array(items)) = message.x_take::<Value>("content") {
+			for mut item in items {
+				let item_type: String = item.x_get("type").unwrap_or_default();
+				match item_type.as_str() {
+					"text" => {
+						if let Ok(text) = item.x_take::<String>("text") {
+							content.push(text);
+						}
+					}
+					"thinking" => {
+						if let Ok(thinking) = item.x_take::<String>("thinking") {
+							reasoning_content.get_or_insert_with(String::new).push_str(&thinking);
+						}
+					}
+					_ => (),
+				}
+			}
+		}
+
+		// -- The tool plan is the reasoning before the tool calls
+		if reasoning_content.is_none() {
+			reasoning_content = message.x_take::<Option<String>>("tool_plan").ok().flatten();
+		}
+
+		// -- Capture the tool calls
+		if let Ok(Value::Array(tool_calls)) = message.x_take::<Value>("tool_calls") {
+			let tool_calls = tool_calls
+				.into_iter()
+				.map(Self::into_tool_call)
+				.collect::<Result<Vec<ToolCall>>>()?;
+			content.extend(MessageContent::from_tool_calls(tool_calls));
+		}
+
+		// -- Capture the citations
+		let citations = match message.x_take::<Value>("citations") {
+			Ok(Value::Array(citations)) => Some(citations.into_iter().map(Self::into_citation).collect()),
+			_ => None,
+		};
 
 		Ok(ChatResponse {
 			content,
-			reasoning_content: None,
+			reasoning_content,
 			model_iden,
 			provider_model_iden,
 			usage,
+			citations,
 			captured_raw_body: None, // Set by the client exec_chat
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let web_stream = WebStream::new_with_delimiter(reqwest_builder, "\n");
-		let cohere_stream = CohereStreamer::new(web_stream, model_iden.clone(), options_set);
-		let chat_stream = ChatStream::from_inter_stream(cohere_stream);
+		let event_source = EventSourceStream::new(stream_source);
+		let cohere_stream = CohereStreamer::new(event_source, model_iden.clone(), options_set);
+		let chat_stream = ChatStream::from_inter_stream(model_iden.clone(), cohere_stream);
 
 		Ok(ChatStreamResponse {
 			model_iden,
@@ -192,7 +278,32 @@ impl Adapter for CohereAdapter {
 
 /// Support function
 impl CohereAdapter {
-	/// Convert usage from '/meta/tokens'
+	/// The Cohere v1 model (`name`, `endpoints`, `features`, `context_length`) to a `ModelInfo`.
+	fn into_model_info(adapter_kind: AdapterKind, mut model_value: Value) -> Option<ModelInfo> {
+		let name: String = model_value.x_take("name").ok()?;
+		let endpoints: Vec<String> = model_value.x_take("endpoints").unwrap_or_default();
+		let features: Vec<String> = model_value.x_take("features").unwrap_or_default();
+		let has_feature = |name: &str| features.iter().any(|f| f == name);
+
+		let mut info = ModelInfo::new(adapter_kind, name);
+		info.context_window = model_value.x_get("context_length").ok();
+
+		let is_chat = endpoints.iter().any(|e| e == "chat");
+		if is_chat {
+			info.input_modalities.push(Modality::Text);
+			if has_feature("vision") {
+				info.input_modalities.push(Modality::Image);
+			}
+			info.output_modalities.push(Modality::Text);
+			info.supports_tools = Some(has_feature("tools"));
+			info.supports_reasoning = Some(has_feature("reasoning"));
+		}
+		info.supports_embeddings = Some(endpoints.iter().any(|e| e == "embed"));
+
+		Some(info)
+	}
+
+	/// Convert usage from '/usage/tokens'
 	/// ```json
 	///  "tokens": {
 	///    "input_tokens": 20,
@@ -224,89 +335,288 @@ impl CohereAdapter {
 		}
 	}
 
-	/// Takes the GenAI ChatMessages and builds the system string and JSON messages for Cohere.
-	/// - Pops the last chat user message and sets it as the message
-	/// - Sets any eventual `system` as the first `preamble`
-	/// - Adds all of the system messages into the 'preamble' (this might change when ChatReq has a `.system`)
-	/// - Builds the chat history with the remaining messages
+	/// Convert a v2 tool call
+	/// ```json
+	/// {"id": "get_weather_1byjy32y4hvq", "type": "function", "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}}
+	/// ```
+	pub(super) fn into_tool_call(mut tool_call_value: Value) -> Result<ToolCall> {
+		let call_id: String = tool_call_value.x_take("id")?;
+		let fn_name: String = tool_call_value.x_take("/function/name")?;
+		// NOTE: The arguments are a JSON string, we are resilient, if we cannot parse, we keep the string
+		let fn_arguments = match tool_call_value.x_take::<Value>("/function/arguments")? {
+			Value::String(arguments) => serde_json::from_str(&arguments).unwrap_or(Value::String(arguments)),
+			arguments => arguments,
+		};
+
+		Ok(ToolCall {
+			call_id,
+			fn_name,
+			fn_arguments,
+			thought_signatures: None,
+		})
+	}
+
+	/// Convert a v2 citation
+	/// ```json
+	/// {"start": 8, "end": 14, "text": "sunny.", "sources": [{"type": "document", "id": "doc_0", "document": {"id": "doc_0", "title": "...", "snippet": "..."}}]}
+	/// ```
+	/// NOTE: Tool sources have a `tool_output` object instead of `document`.
+	pub(super) fn into_citation(mut citation_value: Value) -> Citation {
+		let sources = match citation_value.x_take::<Value>("sources") {
+			Ok(Value::Array(sources)) => sources
+				.into_iter()
+				.map(|mut source| {
+					let mut data = source
+						.x_take::<Value>("document")
+						.or_else(|_| source.x_take::<Value>("tool_output"))
+						.unwrap_or_default();
+					let snippet = data.x_take::<String>("snippet").or_else(|_| data.x_take::<String>("text")).ok();
+					CitationSource {
+						id: source.x_take("id").ok(),
+						url: data.x_take("url").ok(),
+						title: data.x_take("title").ok(),
+						snippet,
+					}
+				})
+				.collect(),
+			_ => Vec::new(),
+		};
+
+		Citation {
+			start: citation_value.x_take("start").ok(),
+			end: citation_value.x_take("end").ok(),
+			text: citation_value.x_take("text").ok(),
+			sources,
+		}
+	}
+
+	/// Takes the GenAI ChatRequest and builds the v2 `messages`, `tools`, and `documents` for Cohere.
+	/// - The eventual `system` and the system messages become `system` messages
+	/// - The user messages support text and images
+	/// - The assistant messages support text and tool calls (text becomes the `tool_plan` when tool calls are present)
+	/// - The tool responses become `tool` messages
 	fn into_cohere_request_parts(
 		model_iden: ModelIden, // for error only
-		mut chat_req: ChatRequest,
+		chat_req: ChatRequest,
 	) -> Result<CohereChatRequestParts> {
-		let mut chat_history: Vec<Value> = Vec::new();
-		let mut systems: Vec<String> = Vec::new();
+		if chat_req.messages.is_empty() {
+			return Err(Error::ChatReqHasNoMessages { model_iden });
+		}
+
+		let mut messages: Vec<Value> = Vec::new();
 
-		// -- Add the eventual system as preamble
+		// -- Add the eventual system
 		if let Some(system) = chat_req.system {
-			systems.push(system);
+			messages.push(json!({"role": "system", "content": system}));
 		}
 
-		// -- Build and extract the last user message
-		let last_chat_msg = chat_req.messages.pop().ok_or_else(|| Error::ChatReqHasNoMessages {
-			model_iden: model_iden.clone(),
-		})?;
-		if !matches!(last_chat_msg.role, ChatRole::User) {
-			return Err(Error::LastChatMessageIsNotUser {
-				model_iden,
-				actual_role: last_chat_msg.role,
-			});
-		}
+		// -- Build the messages
+		for msg in chat_req.messages {
+			match msg.role {
+				ChatRole::System => {
+					if let Some(content) = msg.content.into_joined_texts() {
+						messages.push(json!({"role": "system", "content": content}));
+					}
+				}
 
-		// TODO: Needs to implement tool_calls
-		let Some(message) = last_chat_msg.content.into_joined_texts() else {
-			return Err(Error::MessageContentTypeNotSupported {
-				model_iden,
-				cause: "Only MessageContent::Text supported for this model (for now)",
-			});
-		};
+				// User - text and images
+				ChatRole::User => {
+					if msg.content.is_text_only() {
+						let content = msg.content.into_joined_texts().unwrap_or_default();
+						messages.push(json!({"role": "user", "content": content}));
+					} else {
+						let mut values: Vec<Value> = Vec::new();
+						for part in msg.content {
+							match part {
+								ContentPart::Text(text) => values.push(json!({"type": "text", "text": text})),
+								ContentPart::Binary(binary) => {
+									if !binary.is_image() {
+										return Err(Error::MessageContentTypeNotSupported {
+											model_iden,
+											cause: "Only image binary content is supported for Cohere",
+										});
+									}
+									let image_url = binary.into_url();
+									values.push(json!({"type": "image_url", "image_url": {"url": image_url}}));
+								}
+								// Tool responses in the user message are sent as tool messages
+								ContentPart::ToolResponse(tool_response) => messages.push(json!({
+									"role": "tool",
+									"tool_call_id": tool_response.call_id,
+									"content": tool_response.content,
+								})),
+								ContentPart::ToolCall(_) => warn!("Cohere ignores tool calls in user messages"),
+								ContentPart::ThoughtSignature(_) => (),
+								// Custom are ignored for this logic
+								ContentPart::Custom(_) => (),
+							}
+						}
+						if !values.is_empty() {
+							messages.push(json!({"role": "user", "content": values}));
+						}
+					}
+				}
 
-		// -- Build
-		for msg in chat_req.messages {
-			let Some(content) = msg.content.into_joined_texts() else {
-				return Err(Error::MessageContentTypeNotSupported {
-					model_iden,
-					cause: "Only MessageContent::Text supported for this model (for now)",
-				});
-			};
+				// Assistant - text and tool calls
+				ChatRole::Assistant => {
+					let mut texts: Vec<String> = Vec::new();
+					let mut tool_calls: Vec<Value> = Vec::new();
+					for part in msg.content {
+						match part {
+							ContentPart::Text(text) => texts.push(text),
+							ContentPart::ToolCall(tool_call) => tool_calls.push(json!({
+								"id": tool_call.call_id,
+								"type": "function",
+								"function": {
+									"name": tool_call.fn_name,
+									"arguments": tool_call.fn_arguments.to_string(),
+								}
+							})),
+							ContentPart::Binary(_) => warn!("Cohere ignores binary content in assistant messages"),
+							ContentPart::ToolResponse(_) => (),
+							ContentPart::ThoughtSignature(_) => (),
+							// Custom are ignored for this logic
+							ContentPart::Custom(_) => (),
+						}
+					}
+
+					let text = texts.join("\n\n");
+					let message = if tool_calls.is_empty() {
+						json!({"role": "assistant", "content": text})
+					} else if text.is_empty() {
+						json!({"role": "assistant", "tool_calls": tool_calls})
+					} else {
+						json!({"role": "assistant", "tool_plan": text, "tool_calls": tool_calls})
+					};
+					messages.push(message);
+				}
 
-			match msg.role {
-				// For now, system and tool messages go to the system
-				ChatRole::System => systems.push(content),
-				ChatRole::User => chat_history.push(json! ({"role": "USER", "content": content})),
-				ChatRole::Assistant => chat_history.push(json! ({"role": "CHATBOT", "content": content})),
+				// Tool - tool responses
 				ChatRole::Tool => {
-					return Err(Error::MessageRoleNotSupported {
-						model_iden,
-						role: ChatRole::Tool,
-					});
+					for part in msg.content {
+						if let ContentPart::ToolResponse(tool_response) = part {
+							messages.push(json!({
+								"role": "tool",
+								"tool_call_id": tool_response.call_id,
+								"content": tool_response.content,
+							}));
+						}
+					}
 				}
 			}
 		}
 
-		// -- Build the preamble
-		// Note: For now, we just concatenate the system messages into the preamble as recommended by Cohere
-		//       Later, the ChatRequest should have a `.system` property
-		let preamble = if !systems.is_empty() {
-			Some(systems.join("\n"))
-		} else {
-			None
-		};
+		// -- Build the tools
+		let tools = chat_req.tools.map(|tools| {
+			tools
+				.into_iter()
+				.map(|tool| {
+					json!({
+						"type": "function",
+						"function": {
+							"name": tool.name,
+							"description": tool.description,
+							"parameters": tool.schema,
+						}
+					})
+				})
+				.collect::<Vec<Value>>()
+		});
+
+		// -- Build the documents
+		let documents = chat_req.documents.map(|documents| {
+			documents
+				.into_iter()
+				.map(|document| {
+					let mut data = json!({"text": document.text});
+					if let Some(title) = document.title {
+						data["title"] = title.into();
+					}
+					match document.id {
+						Some(id) => json!({"id": id, "data": data}),
+						None => json!({"data": data}),
+					}
+				})
+				.collect::<Vec<Value>>()
+		});
 
 		Ok(CohereChatRequestParts {
-			preamble,
-			message,
-			chat_history,
+			messages,
+			tools,
+			documents,
 		})
 	}
 }
 
 struct CohereChatRequestParts {
-	/// The "system" in the Cohere context
-	preamble: Option<String>,
-	/// The last user message
-	message: String,
-	/// The chat history (user and assistant, except the last user message which is the message)
-	chat_history: Vec<Value>,
+	/// The v2 messages (system, user, assistant, tool)
+	messages: Vec<Value>,
+	/// The eventual function tools
+	tools: Option<Vec<Value>>,
+	/// The eventual grounding documents
+	documents: Option<Vec<Value>>,
 }
 
 // endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chat::{ChatDocument, ChatMessage, ToolResponse};
+
+	#[test]
+	fn test_cohere_request_parts_tool_flow_and_documents() {
+		let model_iden = ModelIden::new(AdapterKind::Cohere, "command-a-03-2025");
+		let tool_call = ToolCall {
+			call_id: "call_1".to_string(),
+			fn_name: "get_weather".to_string(),
+			fn_arguments: json!({"city": "Paris"}),
+			thought_signatures: None,
+		};
+		let chat_req = ChatRequest::from_system("Be concise")
+			.append_message(ChatMessage::user("Weather in Paris?"))
+			.append_message(vec![tool_call])
+			.append_message(ToolResponse::new("call_1", "Sunny"))
+			.append_document(ChatDocument::new("Paris is in France").with_id("doc_0"));
+
+		let CohereChatRequestParts {
+			messages, documents, ..
+		} = CohereAdapter::into_cohere_request_parts(model_iden, chat_req).unwrap();
+
+		assert_eq!(messages.len(), 4);
+		assert_eq!(messages[0], json!({"role": "system", "content": "Be concise"}));
+		assert_eq!(
+			messages[2].pointer("/tool_calls/0/function/arguments"),
+			Some(&json!(r#"{"city":"Paris"}"#))
+		);
+		assert_eq!(
+			messages[3],
+			json!({"role": "tool", "tool_call_id": "call_1", "content": "Sunny"})
+		);
+		assert_eq!(
+			documents,
+			Some(vec![json!({"id": "doc_0", "data": {"text": "Paris is in France"}})])
+		);
+	}
+
+	#[test]
+	fn test_cohere_into_citation() {
+		let citation = CohereAdapter::into_citation(json!({
+			"start": 8,
+			"end": 14,
+			"text": "France",
+			"type": "TEXT_CONTENT",
+			"sources": [{"type": "document", "id": "doc_0", "document": {"id": "doc_0", "text": "Paris is in France"}}]
+		}));
+
+		assert_eq!(citation.start, Some(8));
+		assert_eq!(citation.end, Some(14));
+		assert_eq!(citation.text.as_deref(), Some("France"));
+		assert_eq!(citation.sources[0].id.as_deref(), Some("doc_0"));
+		assert_eq!(citation.sources[0].snippet.as_deref(), Some("Paris is in France"));
+	}
+}
+
+// endregion: --- Tests
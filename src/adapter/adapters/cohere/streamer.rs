@@ -1,128 +1,211 @@
 use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions};
 use crate::adapter::cohere::CohereAdapter;
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
-use crate::chat::ChatOptionsSet;
-use crate::webc::WebStream;
+use crate::chat::{ChatOptionsSet, Citation, ToolCall, ToolCallArgsDelta, ToolCallEnd, ToolCallStart};
+use crate::webc::{Event, EventSourceStream};
 use crate::{Error, ModelIden, Result};
-use serde::Deserialize;
 use serde_json::Value;
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use value_ext::JsonValueExt;
 
+/// Streamer for the Cohere v2 chat SSE events
+/// (`message-start`, `content-delta`, `tool-plan-delta`, `tool-call-*`, `citation-start`, `message-end`, ...)
 pub struct CohereStreamer {
-	inner: WebStream,
+	inner: EventSourceStream,
 	options: StreamerOptions,
 
 	// -- Set by the poll_next
-	/// Flag to prevent polling the EventSource after a MessageStop event
+	/// Flag to prevent polling the EventSource after a message-end event
 	done: bool,
 	captured_data: StreamerCapturedData,
+
+	/// The tool call in progress (between `tool-call-start` and `tool-call-end`), with the arguments as string
+	in_progress_tool_call: Option<ToolCall>,
+	/// The number of tool calls started (the index of the next `ToolCallStart`)
+	tool_call_count: usize,
+	/// The citations captured with the `citation-start` events
+	captured_citations: Option<Vec<Citation>>,
+	pending_events: VecDeque<InterStreamEvent>,
 }
 
 impl CohereStreamer {
-	pub fn new(inner: WebStream, model_iden: ModelIden, options_set: ChatOptionsSet<'_, '_>) -> Self {
+	pub fn new(inner: EventSourceStream, model_iden: ModelIden, options_set: ChatOptionsSet<'_, '_>) -> Self {
 		Self {
 			inner,
 			done: false,
 			options: StreamerOptions::new(model_iden, options_set),
 			captured_data: Default::default(),
+			in_progress_tool_call: None,
+			tool_call_count: 0,
+			captured_citations: None,
+			pending_events: VecDeque::new(),
 		}
 	}
 }
 
-/// Required properties that need to be parsed
-#[derive(Deserialize, Debug)]
-struct CohereStreamMessage {
-	#[allow(unused)]
-	is_finished: bool,
-	event_type: String,
-	text: Option<String>,
-	response: Option<CohereStreamMessageResponse>,
-}
-#[derive(Deserialize, Debug)]
-struct CohereStreamMessageResponse {
-	meta: Option<Value>,
-}
-
 // Implement futures::Stream for InterStream<CohereStream>
 impl futures::Stream for CohereStreamer {
 	type Item = Result<InterStreamEvent>;
 
 	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if let Some(event) = self.pending_events.pop_front() {
+			return Poll::Ready(Some(Ok(event)));
+		}
 		if self.done {
 			return Poll::Ready(None);
 		}
 
-		while let Poll::Ready(item) = Pin::new(&mut self.inner).poll_next(cx) {
-			match item {
-				Some(Ok(raw_string)) => {
-					let cohere_message =
-						serde_json::from_str::<CohereStreamMessage>(&raw_string).map_err(|serde_error| {
-							Error::StreamParse {
-								model_iden: self.options.model_iden.clone(),
-								serde_error,
-							}
-						});
-
-					match cohere_message {
-						Ok(cohere_message) => {
-							let inter_event = match cohere_message.event_type.as_str() {
-								"stream-start" => InterStreamEvent::Start,
-								"text-generation" => {
-									if let Some(content) = cohere_message.text {
-										// Add to the captured content if chat options allow it
-										if self.options.capture_content {
-											match self.captured_data.content {
-												Some(ref mut c) => c.push_str(&content),
-												None => self.captured_data.content = Some(content.clone()),
-											}
-										}
-										InterStreamEvent::Chunk(content)
-									} else {
-										continue;
+		while let Poll::Ready(event) = Pin::new(&mut self.inner).poll_next(cx) {
+			match event {
+				Some(Ok(Event::Open)) => return Poll::Ready(Some(Ok(InterStreamEvent::Start))),
+				Some(Ok(Event::Message(message))) => {
+					let mut message_data: Value =
+						serde_json::from_str(&message.data).map_err(|serde_error| Error::StreamParse {
+							model_iden: self.options.model_iden.clone(),
+							serde_error,
+						})?;
+
+					let event_type: String = message_data.x_take("type").unwrap_or_default();
+
+					let inter_event = match event_type.as_str() {
+						// -- Text or thinking content
+						"content-delta" => {
+							if let Ok(content) = message_data.x_take::<String>("/delta/message/content/text") {
+								// Add to the captured content if chat options allow it
+								if self.options.capture_content {
+									match self.captured_data.content {
+										Some(ref mut c) => c.push_str(&content),
+										None => self.captured_data.content = Some(content.clone()),
 									}
 								}
-								"stream-end" => {
-									// -- Capture usage
-									let meta = cohere_message.response.and_then(|r| r.meta);
-									let captured_usage = if self.options.capture_usage {
-										meta.and_then(|mut v| v.x_take("tokens").ok())
-											.map(CohereAdapter::into_usage)
-											.map(|mut usage| {
-												// Compute the total if any of input/output are not null
-												if usage.prompt_tokens.is_some() || usage.completion_tokens.is_some() {
-													usage.total_tokens = Some(
-														usage.prompt_tokens.unwrap_or(0)
-															+ usage.completion_tokens.unwrap_or(0),
-													);
-												}
-												usage
-											})
-									} else {
-										None
-									};
-
-									let inter_stream_end = InterStreamEnd {
-										captured_usage,
-										captured_text_content: self.captured_data.content.take(),
-										captured_reasoning_content: self.captured_data.reasoning_content.take(),
-										captured_tool_calls: self.captured_data.tool_calls.take(),
-										captured_thought_signatures: None,
-									};
-
-									InterStreamEvent::End(inter_stream_end)
-								}
-								_ => continue, // Skip the "other" event
+								InterStreamEvent::Chunk(content)
+							} else if let Ok(reasoning) =
+								message_data.x_take::<String>("/delta/message/content/thinking")
+							{
+								self.capture_reasoning(&reasoning);
+								InterStreamEvent::ReasoningChunk(reasoning)
+							} else {
+								continue;
+							}
+						}
+
+						// -- The tool plan is the reasoning before the tool calls
+						"tool-plan-delta" => {
+							let Ok(tool_plan) = message_data.x_take::<String>("/delta/message/tool_plan") else {
+								continue;
 							};
+							self.capture_reasoning(&tool_plan);
+							InterStreamEvent::ReasoningChunk(tool_plan)
+						}
 
-							return Poll::Ready(Some(Ok(inter_event)));
+						// -- Tool calls
+						"tool-call-start" => {
+							let mut tool_call_value: Value =
+								message_data.x_take("/delta/message/tool_calls").unwrap_or_default();
+							let tool_call = ToolCall {
+								call_id: tool_call_value.x_take("id").unwrap_or_default(),
+								fn_name: tool_call_value.x_take("/function/name").unwrap_or_default(),
+								fn_arguments: Value::String(
+									tool_call_value.x_take("/function/arguments").unwrap_or_default(),
+								),
+								thought_signatures: None,
+							};
+							let tool_call_start = ToolCallStart {
+								call_id: tool_call.call_id.clone(),
+								fn_name: tool_call.fn_name.clone(),
+								index: self.tool_call_count,
+							};
+							self.tool_call_count += 1;
+							// NOTE: The start event might already have some arguments
+							if let Some(arguments) = tool_call.fn_arguments.as_str()
+								&& !arguments.is_empty()
+							{
+								self.pending_events
+									.push_back(InterStreamEvent::ToolCallArgsDelta(ToolCallArgsDelta {
+										call_id: tool_call.call_id.clone(),
+										delta: arguments.to_string(),
+									}));
+							}
+							self.in_progress_tool_call = Some(tool_call);
+							InterStreamEvent::ToolCallStart(tool_call_start)
 						}
-						Err(err) => {
-							tracing::error!("Cohere Adapter Stream Error: {}", err);
-							return Poll::Ready(Some(Err(err)));
+						"tool-call-delta" => {
+							if let Ok(arguments) =
+								message_data.x_take::<String>("/delta/message/tool_calls/function/arguments")
+								&& let Some(tool_call) = self.in_progress_tool_call.as_mut()
+								&& let Some(existing_arguments) = tool_call.fn_arguments.as_str()
+							{
+								tool_call.fn_arguments = Value::String(format!("{existing_arguments}{arguments}"));
+								InterStreamEvent::ToolCallArgsDelta(ToolCallArgsDelta {
+									call_id: tool_call.call_id.clone(),
+									delta: arguments,
+								})
+							} else {
+								continue;
+							}
+						}
+						"tool-call-end" => {
+							let Some(mut tool_call) = self.in_progress_tool_call.take() else {
+								continue;
+							};
+							// NOTE: We are resilient, if we cannot parse the arguments, we keep the string
+							if let Value::String(arguments) = tool_call.fn_arguments {
+								tool_call.fn_arguments =
+									serde_json::from_str(&arguments).unwrap_or(Value::String(arguments));
+							}
+							if self.options.capture_tool_calls {
+								self.captured_data
+									.tool_calls
+									.get_or_insert_with(Vec::new)
+									.push(tool_call.clone());
+							}
+							// The ToolCallEnd, then the complete ToolCallChunk
+							let call_id = tool_call.call_id.clone();
+							self.pending_events.push_back(InterStreamEvent::ToolCallChunk(tool_call));
+							InterStreamEvent::ToolCallEnd(ToolCallEnd { call_id })
 						}
-					}
+
+						// -- Citations (captured for the end event)
+						"citation-start" => {
+							if self.options.capture_content
+								&& let Ok(citation_value) = message_data.x_take::<Value>("/delta/message/citations")
+							{
+								self.captured_citations
+									.get_or_insert_with(Vec::new)
+									.push(CohereAdapter::into_citation(citation_value));
+							}
+							continue;
+						}
+
+						// -- End
+						"message-end" => {
+							self.done = true;
+
+							let captured_usage = if self.options.capture_usage {
+								message_data.x_take("/delta/usage/tokens").ok().map(CohereAdapter::into_usage)
+							} else {
+								None
+							};
+
+							let inter_stream_end = InterStreamEnd {
+								captured_usage,
+								captured_text_content: self.captured_data.content.take(),
+								captured_reasoning_content: self.captured_data.reasoning_content.take(),
+								captured_tool_calls: self.captured_data.tool_calls.take(),
+								captured_thought_signatures: None,
+								captured_citations: self.captured_citations.take(),
+							};
+
+							InterStreamEvent::End(inter_stream_end)
+						}
+
+						// Skip the "other" events (message-start, content-start, content-end, citation-end, ...)
+						_ => continue,
+					};
+
+					return Poll::Ready(Some(Ok(inter_event)));
 				}
 				Some(Err(err)) => {
 					tracing::error!("Cohere Adapter Stream Error: {}", err);
@@ -141,3 +224,19 @@ impl futures::Stream for CohereStreamer {
 		Poll::Pending
 	}
 }
+
+// region:    --- Support
+
+impl CohereStreamer {
+	/// Add to the captured reasoning content if chat options allow it
+	fn capture_reasoning(&mut self, reasoning: &str) {
+		if self.options.capture_reasoning_content {
+			match self.captured_data.reasoning_content {
+				Some(ref mut c) => c.push_str(reasoning),
+				None => self.captured_data.reasoning_content = Some(reasoning.to_string()),
+			}
+		}
+	}
+}
+
+// endregion: --- Support
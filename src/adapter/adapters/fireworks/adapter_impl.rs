@@ -1,11 +1,10 @@
-use crate::ModelIden;
 use crate::adapter::openai::{OpenAIAdapter, ToWebRequestCustom};
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{ModelIden, ModelInfo};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
 
 /// The Fireworks API is mostly compatible with the OpenAI API.
 ///
@@ -44,6 +43,10 @@ impl Adapter for FireworksAdapter {
 		OpenAIAdapter::list_model_names_for_end_target(kind, Self::default_endpoint(), Self::default_auth()).await
 	}
 
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		OpenAIAdapter::util_list_models(service_target, web_client).await
+	}
+
 	fn get_service_url(model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
 		OpenAIAdapter::util_get_service_url(model, service_type, endpoint)
 	}
@@ -90,10 +93,10 @@ impl Adapter for FireworksAdapter {
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		OpenAIAdapter::to_chat_stream(model_iden, reqwest_builder, options_set)
+		OpenAIAdapter::to_chat_stream(model_iden, stream_source, options_set)
 	}
 
 	fn to_embed_request_data(
@@ -1,15 +1,14 @@
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_no_regex_response_format, get_api_key};
 use crate::adapter::gemini::GeminiStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	Binary, BinarySource, ChatOptionsSet, ChatRequest, ChatResponse, ChatResponseFormat, ChatRole, ChatStream,
 	ChatStreamResponse, CompletionTokensDetails, ContentPart, MessageContent, PromptTokensDetails, ReasoningEffort,
-	Tool, ToolCall, ToolConfig, ToolName, Usage,
+	TokenCount, Tool, ToolCall, ToolConfig, ToolName, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{WebResponse, WebStream};
-use crate::{Error, Headers, ModelIden, Result, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::webc::{WebClient, WebResponse, WebStream, WebStreamSource};
+use crate::{Error, Headers, Modality, ModelIden, ModelInfo, Result, ServiceTarget};
 use serde_json::{Value, json};
 use value_ext::JsonValueExt;
 
@@ -100,6 +99,35 @@ impl Adapter for GeminiAdapter {
 		Ok(models)
 	}
 
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		let ServiceTarget { model, auth, endpoint } = service_target;
+
+		// -- url
+		let base_url = endpoint.base_url();
+		let url = format!("{base_url}models?pageSize=1000");
+
+		// -- auth / headers
+		let api_key = get_api_key(auth, &model)?;
+		let headers = Headers::from(("x-goog-api-key".to_string(), api_key));
+
+		// -- Exec request
+		let mut res = web_client
+			.do_get(&url, &headers)
+			.await
+			.map_err(|webc_error| Error::WebAdapterCall {
+				adapter_kind: model.adapter_kind,
+				webc_error,
+			})?;
+
+		let models: Vec<Value> = res.body.x_take("models").unwrap_or_default();
+		let infos = models
+			.into_iter()
+			.filter_map(|model_value| Self::into_model_info(model.adapter_kind, model_value))
+			.collect();
+
+		Ok(infos)
+	}
+
 	/// NOTE: As Google Gemini has decided to put their API_KEY in the URL,
 	///       this will return the URL without the API_KEY in it. The API_KEY will need to be added by the caller.
 	fn get_service_url(model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
@@ -121,6 +149,7 @@ impl Adapter for GeminiAdapter {
 	) -> Result<WebRequestData> {
 		let ServiceTarget { endpoint, auth, model } = target;
 		let (_, model_name) = model.model_name.namespace_and_name();
+		check_no_regex_response_format(&model, &options_set)?;
 
 		// -- api_key
 		let api_key = get_api_key(auth, &model)?;
@@ -248,6 +277,38 @@ impl Adapter for GeminiAdapter {
 		Ok(WebRequestData { url, headers, payload })
 	}
 
+	/// Gemini `:countTokens`, with the chat request payload as the `generateContentRequest`
+	/// (so the system instruction and the tools are counted).
+	fn to_count_tokens_request_data(
+		target: ServiceTarget,
+		chat_req: ChatRequest,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<Option<WebRequestData>> {
+		let WebRequestData {
+			url,
+			headers,
+			payload: mut generate_payload,
+		} = Self::to_web_request_data(target, ServiceType::Chat, chat_req, options_set)?;
+
+		// The url is `.../models/{provider_model_name}:generateContent`
+		let Some(model_url) = url.strip_suffix(":generateContent") else {
+			return Err(Error::Internal(format!("Gemini count tokens - unexpected url '{url}'")));
+		};
+		let provider_model_name = model_url.rsplit_once("models/").map(|(_, name)| name).unwrap_or_default();
+		generate_payload.x_insert("model", format!("models/{provider_model_name}"))?;
+
+		let url = format!("{model_url}:countTokens");
+		let payload = json!({ "generateContentRequest": generate_payload });
+
+		Ok(Some(WebRequestData { url, headers, payload }))
+	}
+
+	fn to_token_count(model_iden: ModelIden, web_response: WebResponse) -> Result<TokenCount> {
+		let WebResponse { mut body, .. } = web_response;
+		let input_tokens: u32 = body.x_take("totalTokens")?;
+		Ok(TokenCount::from_provider(model_iden, input_tokens))
+	}
+
 	fn to_chat_response(
 		model_iden: ModelIden,
 		web_response: WebResponse,
@@ -314,19 +375,20 @@ impl Adapter for GeminiAdapter {
 			model_iden,
 			provider_model_iden,
 			usage,
+			citations: None,
 			captured_raw_body: None, // Set by the client exec_chat
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let web_stream = WebStream::new_with_pretty_json_array(reqwest_builder);
+		let web_stream = WebStream::new_with_pretty_json_array(stream_source);
 
 		let gemini_stream = GeminiStreamer::new(web_stream, model_iden.clone(), options_set);
-		let chat_stream = ChatStream::from_inter_stream(gemini_stream);
+		let chat_stream = ChatStream::from_inter_stream(model_iden.clone(), gemini_stream);
 
 		Ok(ChatStreamResponse {
 			model_iden,
@@ -438,6 +500,28 @@ impl GeminiAdapter {
 	}
 
 	/// See gemini doc: https://ai.google.dev/api/generate-content#UsageMetadata
+	/// The Gemini model (`name`, `inputTokenLimit`, `supportedGenerationMethods`, ...) to a `ModelInfo`
+	fn into_model_info(adapter_kind: AdapterKind, mut model_value: Value) -> Option<ModelInfo> {
+		let name: String = model_value.x_take("name").ok()?;
+		// Gemini model names are usually prefixed with "models/"
+		let id = name.strip_prefix("models/").unwrap_or(&name);
+
+		let methods: Vec<String> = model_value.x_take("supportedGenerationMethods").unwrap_or_default();
+		let has_method = |name: &str| methods.iter().any(|m| m == name);
+
+		let mut info = ModelInfo::new(adapter_kind, id);
+		info.display_name = model_value.x_take("displayName").ok();
+		info.context_window = model_value.x_get("inputTokenLimit").ok();
+		info.max_output_tokens = model_value.x_get("outputTokenLimit").ok();
+		if has_method("generateContent") {
+			info.output_modalities.push(Modality::Text);
+		}
+		info.supports_reasoning = Some(model_value.x_get::<bool>("thinking").unwrap_or_default());
+		info.supports_embeddings = Some(has_method("embedContent"));
+
+		Some(info)
+	}
+
 	pub(super) fn into_usage(mut usage_value: Value) -> Usage {
 		let total_tokens: Option<i32> = usage_value.x_take("totalTokenCount").ok();
 
@@ -1,4 +1,4 @@
-use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions};
+use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions, whole_tool_call_events};
 use crate::adapter::gemini::{GeminiAdapter, GeminiChatResponse};
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
 use crate::chat::{ChatOptionsSet, ToolCall};
@@ -21,6 +21,8 @@ pub struct GeminiStreamer {
 	done: bool,
 	captured_data: StreamerCapturedData,
 	pending_events: VecDeque<InterStreamEvent>,
+	/// The number of tool calls received (the index of the next `ToolCallStart`)
+	tool_call_count: usize,
 }
 
 impl GeminiStreamer {
@@ -31,6 +33,7 @@ impl GeminiStreamer {
 			options: StreamerOptions::new(model_iden, options_set),
 			captured_data: Default::default(),
 			pending_events: VecDeque::new(),
+			tool_call_count: 0,
 		}
 	}
 }
@@ -65,6 +68,7 @@ impl futures::Stream for GeminiStreamer {
 								captured_reasoning_content: self.captured_data.reasoning_content.take(),
 								captured_tool_calls: self.captured_data.tool_calls.take(),
 								captured_thought_signatures: self.captured_data.thought_signatures.take(),
+								captured_citations: None,
 							};
 
 							return Poll::Ready(Some(Ok(InterStreamEvent::End(inter_stream_end))));
@@ -173,9 +177,17 @@ impl futures::Stream for GeminiStreamer {
 									}
 								}
 								if self.options.capture_usage {
-									self.captured_data.usage = Some(usage);
+									self.captured_data.usage = Some(usage.clone());
 								}
-								self.pending_events.push_back(InterStreamEvent::ToolCallChunk(tool_call));
+								// Gemini sends the whole tool call at once
+								let tool_call_events = whole_tool_call_events(self.tool_call_count, tool_call);
+								self.pending_events.extend(tool_call_events);
+								self.tool_call_count += 1;
+							}
+
+							// 4. Usage (the usageMetadata so far)
+							if !usage.is_empty() {
+								self.pending_events.push_back(InterStreamEvent::Usage(usage));
 							}
 
 							// Return the first event if any
@@ -0,0 +1,198 @@
+use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::openai::OpenAIAdapter;
+use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
+use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatResponseFormat, ChatStreamResponse};
+use crate::embed::{EmbedOptionsSet, EmbedRequest, EmbedResponse};
+use crate::resolver::{AuthData, Endpoint};
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{Error, Headers, Result, ServiceTarget};
+use crate::{ModelIden, ModelInfo};
+use serde_json::json;
+use value_ext::JsonValueExt;
+
+/// The Hugging Face Inference Providers router and Text Generation Inference (TGI) servers
+/// are compatible with the OpenAI chat completions API.
+/// NOTE: This adapter is activated for namespaced model names (e.g., `hf::meta-llama/Llama-3.1-8B-Instruct`)
+///       For a self-hosted TGI, set the endpoint (e.g., `http://localhost:8080/v1/`) with a `ServiceTargetResolver`.
+/// NOTE: The response format is the OpenAI one for the router, and the TGI grammar for the other endpoints
+///       (`ChatResponseFormat::Regex` is only supported by TGI).
+pub struct HuggingFaceAdapter;
+
+impl HuggingFaceAdapter {
+	pub const API_KEY_DEFAULT_ENV_NAME: &str = "HF_TOKEN";
+
+	const ROUTER_HOST: &str = "router.huggingface.co";
+}
+
+impl Adapter for HuggingFaceAdapter {
+	const DEFAULT_API_KEY_ENV_NAME: Option<&'static str> = Some(Self::API_KEY_DEFAULT_ENV_NAME);
+
+	fn default_endpoint() -> Endpoint {
+		const BASE_URL: &str = "https://router.huggingface.co/v1/";
+		Endpoint::from_static(BASE_URL)
+	}
+
+	fn default_auth() -> AuthData {
+		match Self::DEFAULT_API_KEY_ENV_NAME {
+			Some(env_name) => AuthData::from_env(env_name),
+			None => AuthData::None,
+		}
+	}
+
+	async fn all_model_names(kind: AdapterKind) -> Result<Vec<String>> {
+		OpenAIAdapter::list_model_names_for_end_target(kind, Self::default_endpoint(), Self::default_auth()).await
+	}
+
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		OpenAIAdapter::util_list_models(service_target, web_client).await
+	}
+
+	/// Note: The embeddings use the `feature-extraction` pipeline of the router (`hf-inference` provider),
+	///       which is not under the `/v1/` base path.
+	fn get_service_url(model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
+		match service_type {
+			ServiceType::Chat | ServiceType::ChatStream => {
+				OpenAIAdapter::util_get_service_url(model, service_type, endpoint)
+			}
+			ServiceType::Embed => {
+				let base_url = endpoint.base_url();
+				let base_without_version = base_url.trim_end_matches("v1/");
+				let (_, model_name) = model.model_name.namespace_and_name();
+				Ok(format!(
+					"{base_without_version}hf-inference/models/{model_name}/pipeline/feature-extraction"
+				))
+			}
+		}
+	}
+
+	fn to_web_request_data(
+		target: ServiceTarget,
+		service_type: ServiceType,
+		chat_req: ChatRequest,
+		chat_options: ChatOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		let ServiceTarget { model, auth, endpoint } = target;
+		let is_router = endpoint.base_url().contains(Self::ROUTER_HOST);
+
+		// -- api_key
+		let api_key = get_api_key(auth, &model)?;
+
+		// -- url & headers
+		let url = Self::get_service_url(&model, service_type, endpoint)?;
+		let headers = Headers::from(("Authorization".to_string(), format!("Bearer {api_key}")));
+
+		// -- payload
+		let mut payload = OpenAIAdapter::util_to_chat_payload(&model, service_type, chat_req, &chat_options, None)?;
+
+		// -- TGI grammar (replaces the OpenAI response_format, which the router expects)
+		// see: https://huggingface.co/docs/text-generation-inference/conceptual/guidance
+		let response_format = match (chat_options.response_format(), is_router) {
+			(Some(ChatResponseFormat::Regex(_)), true) => {
+				return Err(Error::AdapterNotSupported {
+					adapter_kind: model.adapter_kind,
+					feature: "regex response format (router, only supported by TGI endpoints)".to_string(),
+				});
+			}
+			(_, true) | (None, false) => None,
+			(Some(ChatResponseFormat::JsonMode), false) => Some(json!({"type": "json", "value": {"type": "object"}})),
+			(Some(ChatResponseFormat::JsonSpec(st_json)), false) => {
+				Some(json!({"type": "json", "value": st_json.schema}))
+			}
+			(Some(ChatResponseFormat::Regex(regex)), false) => Some(json!({"type": "regex", "value": regex})),
+		};
+		if let Some(response_format) = response_format {
+			payload.x_insert("response_format", response_format)?;
+		}
+
+		Ok(WebRequestData { url, headers, payload })
+	}
+
+	fn to_chat_response(
+		model_iden: ModelIden,
+		web_response: WebResponse,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatResponse> {
+		OpenAIAdapter::to_chat_response(model_iden, web_response, options_set)
+	}
+
+	fn to_chat_stream(
+		model_iden: ModelIden,
+		stream_source: WebStreamSource,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatStreamResponse> {
+		OpenAIAdapter::to_chat_stream(model_iden, stream_source, options_set)
+	}
+
+	fn to_embed_request_data(
+		service_target: ServiceTarget,
+		embed_req: EmbedRequest,
+		options_set: EmbedOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		super::embed::to_embed_request_data(service_target, embed_req, options_set)
+	}
+
+	fn to_embed_response(
+		model_iden: ModelIden,
+		web_response: WebResponse,
+		options_set: EmbedOptionsSet<'_, '_>,
+	) -> Result<EmbedResponse> {
+		super::embed::to_embed_response(model_iden, web_response, options_set)
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chat::{ChatOptions, JsonSpec};
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	fn to_payload(endpoint: Endpoint, options: &ChatOptions) -> crate::Result<serde_json::Value> {
+		let target = ServiceTarget {
+			model: ModelIden::new(AdapterKind::HuggingFace, "hf::meta-llama/Llama-3.1-8B-Instruct"),
+			auth: AuthData::from_single("hf-test-key"),
+			endpoint,
+		};
+		let chat_options = ChatOptionsSet::default().with_chat_options(Some(options));
+		let data = HuggingFaceAdapter::to_web_request_data(
+			target,
+			ServiceType::Chat,
+			ChatRequest::from_user("Hi"),
+			chat_options,
+		)?;
+		Ok(data.payload)
+	}
+
+	#[test]
+	fn test_huggingface_response_format_router_and_tgi() -> Result<()> {
+		// -- Setup & Fixtures
+		let schema = json!({"type": "object", "properties": {"name": {"type": "string"}}});
+		let json_spec = ChatOptions::default().with_response_format(JsonSpec::new("person", schema.clone()));
+		let regex = ChatOptions::default().with_response_format(ChatResponseFormat::Regex("[0-9]+".to_string()));
+		let tgi_endpoint = || Endpoint::from_static("http://localhost:8080/v1/");
+
+		// -- Exec
+		let router_payload = to_payload(HuggingFaceAdapter::default_endpoint(), &json_spec)?;
+		let router_regex_res = to_payload(HuggingFaceAdapter::default_endpoint(), &regex);
+		let tgi_payload = to_payload(tgi_endpoint(), &json_spec)?;
+		let tgi_regex_payload = to_payload(tgi_endpoint(), &regex)?;
+
+		// -- Check - router (OpenAI response_format)
+		assert_eq!(router_payload["response_format"]["type"], "json_schema");
+		assert_eq!(router_payload["response_format"]["json_schema"]["name"], "person");
+		assert!(matches!(router_regex_res, Err(Error::AdapterNotSupported { .. })));
+
+		// -- Check - TGI (grammar)
+		assert_eq!(tgi_payload["response_format"], json!({"type": "json", "value": schema}));
+		assert_eq!(
+			tgi_regex_payload["response_format"],
+			json!({"type": "regex", "value": "[0-9]+"})
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,106 @@
+//! Hugging Face feature-extraction (embeddings) implementation
+//! API Documentation: <https://huggingface.co/docs/inference-providers/tasks/feature-extraction>
+
+use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::{Adapter, ServiceType, WebRequestData};
+use crate::chat::Usage;
+use crate::embed::{EmbedInput, EmbedOptionsSet, EmbedRequest, EmbedResponse, Embedding};
+use crate::webc::WebResponse;
+use crate::{Error, Headers, ModelIden, Result, ServiceTarget};
+use serde::Serialize;
+
+// region:    --- HuggingFace Embed Request
+
+#[derive(Debug, Serialize)]
+struct HuggingFaceEmbedRequest {
+	/// Always a batch, so that the response is always a list of vectors
+	inputs: Vec<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	truncate: Option<bool>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	prompt_name: Option<String>,
+}
+
+// endregion: --- HuggingFace Embed Request
+
+// region:    --- Public Functions
+
+pub fn to_embed_request_data(
+	service_target: ServiceTarget,
+	embed_req: EmbedRequest,
+	options_set: EmbedOptionsSet<'_, '_>,
+) -> Result<WebRequestData> {
+	let ServiceTarget { model, auth, endpoint } = service_target;
+	let api_key = get_api_key(auth, &model)?;
+
+	// Build headers
+	let mut headers = Headers::from(vec![
+		("Authorization".to_string(), format!("Bearer {api_key}")),
+		("Content-Type".to_string(), "application/json".to_string()),
+	]);
+
+	// Add custom headers from options
+	if let Some(custom_headers) = options_set.headers() {
+		headers.merge_with(custom_headers);
+	}
+
+	let inputs = match embed_req.input {
+		EmbedInput::Single(text) => vec![text],
+		EmbedInput::Batch(texts) => texts,
+	};
+
+	// NOTE: The `truncate` option is a boolean for HF (any value other than "NONE" truncates)
+	//       and the `embedding_type` maps to the sentence-transformers `prompt_name` (e.g., "query")
+	let hf_req = HuggingFaceEmbedRequest {
+		inputs,
+		truncate: options_set.truncate().map(|truncate| !truncate.eq_ignore_ascii_case("none")),
+		prompt_name: options_set.embedding_type().map(|s| s.to_string()),
+	};
+
+	let payload = serde_json::to_value(hf_req).map_err(|serde_error| Error::StreamParse {
+		model_iden: model.clone(),
+		serde_error,
+	})?;
+
+	// Get the service URL
+	let url = <crate::adapter::huggingface::HuggingFaceAdapter as Adapter>::get_service_url(
+		&model,
+		ServiceType::Embed,
+		endpoint,
+	)?;
+
+	Ok(WebRequestData { url, headers, payload })
+}
+
+pub fn to_embed_response(
+	model_iden: ModelIden,
+	web_response: WebResponse,
+	options_set: EmbedOptionsSet<'_, '_>,
+) -> Result<EmbedResponse> {
+	let WebResponse { body, .. } = web_response;
+
+	// Parse the feature-extraction response (one pooled vector per input)
+	let vectors: Vec<Vec<f32>> = serde_json::from_value(body.clone()).map_err(|serde_error| Error::StreamParse {
+		model_iden: model_iden.clone(),
+		serde_error,
+	})?;
+
+	let embeddings: Vec<Embedding> = vectors
+		.into_iter()
+		.enumerate()
+		.map(|(index, vector)| Embedding::new(vector, index))
+		.collect();
+
+	// NOTE: The feature-extraction pipeline does not return the usage, nor the model name
+	let provider_model_iden = model_iden.clone();
+	let mut response = EmbedResponse::new(embeddings, model_iden, provider_model_iden, Usage::default());
+
+	// Capture raw body if requested
+	if options_set.capture_raw_body() {
+		response = response.with_captured_raw_body(body);
+	}
+
+	Ok(response)
+}
+
+// endregion: --- Public Functions
@@ -0,0 +1,13 @@
+//! API Documentation:     <https://huggingface.co/docs/inference-providers/index>
+//! TGI Documentation:     <https://huggingface.co/docs/text-generation-inference/index>
+//! Model Names:           <https://huggingface.co/models?inference_provider=all>
+//! Pricing:               <https://huggingface.co/docs/inference-providers/pricing>
+
+// region:    --- Modules
+
+mod adapter_impl;
+mod embed;
+
+pub use adapter_impl::*;
+
+// endregion: --- Modules
@@ -0,0 +1,105 @@
+use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
+use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
+use crate::embed::{EmbedOptionsSet, EmbedRequest, EmbedResponse};
+use crate::resolver::{AuthData, Endpoint};
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{Error, Headers, ModelIden, ModelInfo, Result, ServiceTarget};
+use serde_json::json;
+
+/// The adapter of the `AdapterKind::Mock` models.
+///
+/// NOTE: The mock chat calls are served in-process by the client `MockScript`, so this adapter only builds
+///       the dry run request data, and parses a serialized `ChatResponse` as the provider body.
+pub struct MockAdapter;
+
+impl Adapter for MockAdapter {
+	const DEFAULT_API_KEY_ENV_NAME: Option<&'static str> = None;
+
+	fn default_auth() -> AuthData {
+		AuthData::None
+	}
+
+	fn default_endpoint() -> Endpoint {
+		const BASE_URL: &str = "mock://";
+		Endpoint::from_static(BASE_URL)
+	}
+
+	/// The mock models are any `mock::` namespaced name.
+	async fn all_model_names(_kind: AdapterKind) -> Result<Vec<String>> {
+		Ok(Vec::new())
+	}
+
+	async fn list_models(_service_target: ServiceTarget, _web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		Ok(Vec::new())
+	}
+
+	fn get_service_url(model: &ModelIden, _service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
+		Ok(format!("{}{}", endpoint.base_url(), model.model_name))
+	}
+
+	fn to_web_request_data(
+		target: ServiceTarget,
+		service_type: ServiceType,
+		chat_req: ChatRequest,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		let ServiceTarget { model, endpoint, .. } = target;
+		let url = Self::get_service_url(&model, service_type, endpoint)?;
+
+		let payload = json!({
+			"model": model.model_name.to_string(),
+			"stream": matches!(service_type, ServiceType::ChatStream),
+			"chat_request": chat_req,
+			"options": options_set.to_chat_options(),
+		});
+
+		Ok(WebRequestData {
+			url,
+			headers: Headers::default(),
+			payload,
+		})
+	}
+
+	fn to_chat_response(
+		model_iden: ModelIden,
+		web_response: WebResponse,
+		_options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatResponse> {
+		let mut chat_res: ChatResponse = serde_json::from_value(web_response.body)?;
+		chat_res.model_iden = model_iden;
+		Ok(chat_res)
+	}
+
+	fn to_chat_stream(
+		model_iden: ModelIden,
+		_stream_source: WebStreamSource,
+		_options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatStreamResponse> {
+		Err(Error::AdapterNotSupported {
+			adapter_kind: model_iden.adapter_kind,
+			feature: "chat_stream from a provider body".to_string(),
+		})
+	}
+
+	fn to_embed_request_data(
+		service_target: ServiceTarget,
+		_embed_req: EmbedRequest,
+		_options_set: EmbedOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		Err(Error::AdapterNotSupported {
+			adapter_kind: service_target.model.adapter_kind,
+			feature: "embed".to_string(),
+		})
+	}
+
+	fn to_embed_response(
+		model_iden: ModelIden,
+		_web_response: WebResponse,
+		_options_set: EmbedOptionsSet<'_, '_>,
+	) -> Result<EmbedResponse> {
+		Err(Error::AdapterNotSupported {
+			adapter_kind: model_iden.adapter_kind,
+			feature: "embed".to_string(),
+		})
+	}
+}
@@ -0,0 +1,462 @@
+use crate::adapter::adapters::support::whole_tool_call_events;
+use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
+use crate::chat::{
+	ChatOptions, ChatOptionsSet, ChatRequest, ChatResponse, ChatStream, ChatStreamResponse, ContentPart,
+	MessageContent, ToolCall, Usage,
+};
+use crate::{Error, ModelIden, Result};
+use futures::Stream;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+// region:    --- MockScript
+
+/// The scripted behavior of the `AdapterKind::Mock` models (e.g., `mock::my-model`), for tests without HTTP.
+///
+/// Set on the client with `ClientBuilder::with_mock_script(...)`. Each chat call (streamed or not) consumes
+/// the next queued reply, and is recorded with its `ChatRequest` and resolved `ChatOptions`.
+///
+/// ```
+/// use genai::Client;
+/// use genai::adapter::MockScript;
+///
+/// let mock = MockScript::new();
+/// mock.push_text("Hello!").push_text("Goodbye!");
+/// let client = Client::builder().with_mock_script(mock.clone()).build();
+/// // client.exec_chat("mock::any-name", chat_req, None) returns "Hello!", then "Goodbye!".
+/// ```
+///
+/// Notes:
+/// - A non-streamed call gets the concatenation of a scripted stream (and waits for its delays).
+/// - A call with no queued reply fails with `Error::MockNoReply`.
+/// - The script is cheap to clone (all clones share the same replies and calls).
+#[derive(Debug, Clone, Default)]
+pub struct MockScript {
+	inner: Arc<Mutex<MockScriptInner>>,
+}
+
+#[derive(Debug, Default)]
+struct MockScriptInner {
+	replies: VecDeque<Vec<MockStreamItem>>,
+	calls: Vec<MockCall>,
+}
+
+/// A step of a scripted reply (see `MockScript::push_stream`).
+#[derive(Debug)]
+pub enum MockStreamItem {
+	/// A text chunk.
+	Chunk(String),
+	/// A reasoning content chunk.
+	ReasoningChunk(String),
+	/// A thought signature.
+	ThoughtSignature(String),
+	/// A (complete) tool call (streamed as the `ToolCallStart`, `ToolCallArgsDelta`, `ToolCallEnd`, and `ToolCallChunk` events).
+	ToolCall(ToolCall),
+	/// The usage of the reply so far (sent as a `Usage` event, and captured at the stream end with `ChatOptions::with_capture_usage(true)`).
+	Usage(Usage),
+	/// Waits before the next item.
+	Delay(Duration),
+	/// Fails the call (or the stream) at this point.
+	Error(Error),
+}
+
+/// A chat call received by the `MockScript`.
+#[derive(Debug, Clone)]
+pub struct MockCall {
+	pub model_iden: ModelIden,
+	pub chat_req: ChatRequest,
+	/// The resolved options (the call options over the client options).
+	pub options: ChatOptions,
+	/// True for `exec_chat_stream`.
+	pub stream: bool,
+}
+
+/// Constructors & Script
+impl MockScript {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Queues a text reply.
+	pub fn push_text(&self, text: impl Into<String>) -> &Self {
+		self.push_stream([MockStreamItem::Chunk(text.into())])
+	}
+
+	/// Queues a tool calls reply.
+	pub fn push_tool_calls(&self, tool_calls: impl IntoIterator<Item = ToolCall>) -> &Self {
+		self.push_stream(tool_calls.into_iter().map(MockStreamItem::ToolCall))
+	}
+
+	/// Queues a full response (its content, reasoning content, and usage).
+	pub fn push_response(&self, chat_res: ChatResponse) -> &Self {
+		let mut items = Vec::new();
+		if let Some(reasoning_content) = chat_res.reasoning_content {
+			items.push(MockStreamItem::ReasoningChunk(reasoning_content));
+		}
+		for part in chat_res.content.into_parts() {
+			match part {
+				ContentPart::Text(text) => items.push(MockStreamItem::Chunk(text)),
+				ContentPart::ToolCall(tool_call) => items.push(MockStreamItem::ToolCall(tool_call)),
+				ContentPart::ThoughtSignature(signature) => items.push(MockStreamItem::ThoughtSignature(signature)),
+				// Binary, tool response, and custom parts are not streamed.
+				_ => (),
+			}
+		}
+		items.push(MockStreamItem::Usage(chat_res.usage));
+		self.push_stream(items)
+	}
+
+	/// Queues a scripted stream reply (chunks, tool calls, usage, delays, and errors).
+	pub fn push_stream(&self, items: impl IntoIterator<Item = MockStreamItem>) -> &Self {
+		self.lock().replies.push_back(items.into_iter().collect());
+		self
+	}
+
+	/// Queues a failing reply.
+	pub fn push_error(&self, error: Error) -> &Self {
+		self.push_stream([MockStreamItem::Error(error)])
+	}
+}
+
+/// Inspection
+impl MockScript {
+	/// Returns all the calls received, in order.
+	pub fn calls(&self) -> Vec<MockCall> {
+		self.lock().calls.clone()
+	}
+
+	pub fn last_call(&self) -> Option<MockCall> {
+		self.lock().calls.last().cloned()
+	}
+
+	pub fn call_count(&self) -> usize {
+		self.lock().calls.len()
+	}
+
+	/// The number of queued replies not yet consumed.
+	pub fn remaining_replies(&self) -> usize {
+		self.lock().replies.len()
+	}
+
+	/// Clears the received calls (the queued replies are kept).
+	pub fn clear_calls(&self) {
+		self.lock().calls.clear();
+	}
+}
+
+/// Execution (called by the client)
+impl MockScript {
+	pub(crate) async fn exec_chat(
+		&self,
+		model_iden: ModelIden,
+		chat_req: ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+	) -> Result<ChatResponse> {
+		let items = self.next_reply(&model_iden, chat_req, options_set, false)?;
+
+		let mut capture = MockCapture::default();
+		for item in items {
+			match item {
+				MockStreamItem::Delay(delay) => tokio::time::sleep(delay).await,
+				MockStreamItem::Error(err) => return Err(err),
+				item => capture.add(item),
+			}
+		}
+
+		Ok(ChatResponse {
+			content: capture.content(),
+			reasoning_content: capture.reasoning_content,
+			model_iden: model_iden.clone(),
+			provider_model_iden: model_iden,
+			usage: capture.usage.unwrap_or_default(),
+			citations: None,
+			captured_raw_body: None,
+		})
+	}
+
+	pub(crate) fn exec_chat_stream(
+		&self,
+		model_iden: ModelIden,
+		chat_req: ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+	) -> Result<ChatStreamResponse> {
+		let items = self.next_reply(&model_iden, chat_req, options_set, true)?;
+
+		let state = MockStreamState {
+			items: items.into(),
+			started: false,
+			ended: false,
+			pending_events: VecDeque::new(),
+			tool_call_count: 0,
+			capture: MockCapture::default(),
+			capture_options: MockCaptureOptions {
+				usage: options_set.capture_usage().unwrap_or(false),
+				content: options_set.capture_content().unwrap_or(false),
+				reasoning_content: options_set.capture_reasoning_content().unwrap_or(false),
+				tool_calls: options_set.capture_tool_calls().unwrap_or(false),
+			},
+		};
+
+		Ok(ChatStreamResponse {
+			stream: ChatStream::from_inter_stream(model_iden.clone(), mock_inter_stream(state)),
+			model_iden,
+		})
+	}
+}
+
+// Private
+impl MockScript {
+	/// Records the call, and pops the next reply.
+	fn next_reply(
+		&self,
+		model_iden: &ModelIden,
+		chat_req: ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+		stream: bool,
+	) -> Result<Vec<MockStreamItem>> {
+		let mut inner = self.lock();
+		inner.calls.push(MockCall {
+			model_iden: model_iden.clone(),
+			chat_req,
+			options: options_set.to_chat_options(),
+			stream,
+		});
+		inner.replies.pop_front().ok_or_else(|| Error::MockNoReply {
+			model_iden: model_iden.clone(),
+		})
+	}
+
+	fn lock(&self) -> MutexGuard<'_, MockScriptInner> {
+		self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+// endregion: --- MockScript
+
+// region:    --- Mock Stream
+
+/// The accumulated reply (for the non-streamed response, and the stream end captures).
+#[derive(Default)]
+struct MockCapture {
+	text: Option<String>,
+	reasoning_content: Option<String>,
+	thought_signatures: Vec<String>,
+	tool_calls: Vec<ToolCall>,
+	usage: Option<Usage>,
+}
+
+impl MockCapture {
+	fn add(&mut self, item: MockStreamItem) {
+		match item {
+			MockStreamItem::Chunk(text) => self.text.get_or_insert_with(String::new).push_str(&text),
+			MockStreamItem::ReasoningChunk(text) => {
+				self.reasoning_content.get_or_insert_with(String::new).push_str(&text)
+			}
+			MockStreamItem::ThoughtSignature(signature) => self.thought_signatures.push(signature),
+			MockStreamItem::ToolCall(tool_call) => self.tool_calls.push(tool_call),
+			MockStreamItem::Usage(usage) => self.usage = Some(usage),
+			MockStreamItem::Delay(_) | MockStreamItem::Error(_) => (),
+		}
+	}
+
+	/// Same order as the stream end captured content: thought signatures, text, tool calls.
+	fn content(&self) -> MessageContent {
+		let mut parts: Vec<ContentPart> = self
+			.thought_signatures
+			.iter()
+			.cloned()
+			.map(ContentPart::ThoughtSignature)
+			.collect();
+		if let Some(text) = &self.text {
+			parts.push(ContentPart::Text(text.clone()));
+		}
+		parts.extend(self.tool_calls.iter().cloned().map(ContentPart::ToolCall));
+		MessageContent::from_parts(parts)
+	}
+}
+
+struct MockCaptureOptions {
+	usage: bool,
+	content: bool,
+	reasoning_content: bool,
+	tool_calls: bool,
+}
+
+struct MockStreamState {
+	items: VecDeque<MockStreamItem>,
+	started: bool,
+	ended: bool,
+	/// The events of the last tool call item (see `whole_tool_call_events`)
+	pending_events: VecDeque<InterStreamEvent>,
+	tool_call_count: usize,
+	capture: MockCapture,
+	capture_options: MockCaptureOptions,
+}
+
+impl MockStreamState {
+	fn take_end(&mut self) -> InterStreamEnd {
+		let capture = std::mem::take(&mut self.capture);
+		let options = &self.capture_options;
+
+		InterStreamEnd {
+			captured_usage: options.usage.then_some(capture.usage).flatten(),
+			captured_text_content: options.content.then_some(capture.text).flatten(),
+			captured_reasoning_content: options.reasoning_content.then_some(capture.reasoning_content).flatten(),
+			captured_tool_calls: options.tool_calls.then(|| non_empty(capture.tool_calls)).flatten(),
+			captured_thought_signatures: (options.content || options.tool_calls)
+				.then(|| non_empty(capture.thought_signatures))
+				.flatten(),
+			captured_citations: None,
+		}
+	}
+}
+
+fn mock_inter_stream(state: MockStreamState) -> impl Stream<Item = Result<InterStreamEvent>> + Send {
+	futures::stream::unfold(state, |mut state| async move {
+		if !state.started {
+			state.started = true;
+			return Some((Ok(InterStreamEvent::Start), state));
+		}
+
+		loop {
+			if let Some(event) = state.pending_events.pop_front() {
+				return Some((Ok(event), state));
+			}
+
+			let event = match state.items.pop_front() {
+				Some(MockStreamItem::Delay(delay)) => {
+					tokio::time::sleep(delay).await;
+					continue;
+				}
+				Some(MockStreamItem::Usage(usage)) => {
+					state.capture.usage = Some(usage.clone());
+					if usage.is_empty() {
+						continue;
+					}
+					InterStreamEvent::Usage(usage)
+				}
+				Some(MockStreamItem::Error(err)) => {
+					state.items.clear();
+					state.ended = true;
+					return Some((Err(err), state));
+				}
+				Some(MockStreamItem::Chunk(text)) => {
+					state.capture.add(MockStreamItem::Chunk(text.clone()));
+					InterStreamEvent::Chunk(text)
+				}
+				Some(MockStreamItem::ReasoningChunk(text)) => {
+					state.capture.add(MockStreamItem::ReasoningChunk(text.clone()));
+					InterStreamEvent::ReasoningChunk(text)
+				}
+				Some(MockStreamItem::ThoughtSignature(signature)) => {
+					state.capture.add(MockStreamItem::ThoughtSignature(signature.clone()));
+					InterStreamEvent::ThoughtSignatureChunk(signature)
+				}
+				Some(MockStreamItem::ToolCall(tool_call)) => {
+					state.capture.add(MockStreamItem::ToolCall(tool_call.clone()));
+					// A whole tool call, as the Gemini and Ollama streams
+					state
+						.pending_events
+						.extend(whole_tool_call_events(state.tool_call_count, tool_call));
+					state.tool_call_count += 1;
+					continue;
+				}
+				None if !state.ended => {
+					state.ended = true;
+					InterStreamEvent::End(state.take_end())
+				}
+				None => return None,
+			};
+			return Some((Ok(event), state));
+		}
+	})
+}
+
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+	(!values.is_empty()).then_some(values)
+}
+
+// endregion: --- Mock Stream
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Client;
+	use crate::adapter::AdapterKind;
+	use crate::chat::{ChatMessage, ChatStreamEvent};
+	use futures::StreamExt;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_mock_script_chat_stream_and_calls() -> Result<()> {
+		// -- Setup & Fixtures
+		let tool_call = ToolCall {
+			call_id: "call_1".to_string(),
+			fn_name: "get_weather".to_string(),
+			fn_arguments: json!({"city": "Paris"}),
+			thought_signatures: None,
+		};
+		let mock = MockScript::new();
+		mock.push_text("Hello!")
+			.push_stream([
+				MockStreamItem::Chunk("Checking".to_string()),
+				MockStreamItem::Delay(Duration::from_millis(5)),
+				MockStreamItem::ToolCall(tool_call.clone()),
+			])
+			.push_error(Error::Internal("boom".to_string()));
+		let client = Client::builder()
+			.with_chat_options(ChatOptions::default().with_temperature(0.5))
+			.with_mock_script(mock.clone())
+			.build();
+		let chat_req = ChatRequest::new(vec![ChatMessage::user("Hi")]);
+		let options = ChatOptions::default().with_capture_content(true).with_capture_tool_calls(true);
+
+		// -- Exec & Check - chat
+		let chat_res = client.exec_chat("mock::test-model", chat_req.clone(), None).await?;
+		assert_eq!(chat_res.first_text(), Some("Hello!"));
+		assert_eq!(chat_res.model_iden.adapter_kind, AdapterKind::Mock);
+
+		// -- Exec & Check - stream
+		let mut stream = client
+			.exec_chat_stream("mock::test-model", chat_req.clone(), Some(&options))
+			.await?
+			.stream;
+		let mut chunks = Vec::new();
+		let mut end = None;
+		while let Some(event) = stream.next().await {
+			match event? {
+				ChatStreamEvent::Chunk(chunk) => chunks.push(chunk.content),
+				ChatStreamEvent::End(stream_end) => end = Some(stream_end),
+				_ => (),
+			}
+		}
+		assert_eq!(chunks, vec!["Checking".to_string()]);
+		let end = end.ok_or("Should have a stream end")?;
+		let captured_tool_calls = end.captured_tool_calls().ok_or("Should have tool calls")?;
+		assert_eq!(captured_tool_calls[0].fn_name, "get_weather");
+
+		// -- Exec & Check - error, and no reply
+		let res = client.exec_chat("mock::test-model", chat_req.clone(), None).await;
+		assert!(matches!(res, Err(Error::Internal(_))));
+		let res = client.exec_chat("mock::test-model", chat_req, None).await;
+		assert!(matches!(res, Err(Error::MockNoReply { .. })));
+
+		// -- Check - calls
+		let calls = mock.calls();
+		assert_eq!(calls.len(), 4);
+		assert_eq!(calls[0].options.temperature, Some(0.5));
+		assert!(calls[1].stream);
+		assert_eq!(calls[1].options.capture_tool_calls, Some(true));
+		assert_eq!(calls[1].chat_req.messages.len(), 1);
+		assert_eq!(mock.remaining_replies(), 0);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,11 @@
+//! In-process mock adapter (`AdapterKind::Mock`), scripted by a `MockScript`, for tests without HTTP.
+
+// region:    --- Modules
+
+mod adapter_impl;
+mod mock_script;
+
+pub use adapter_impl::*;
+pub use mock_script::*;
+
+// endregion: --- Modules
@@ -8,11 +8,15 @@ pub(super) mod deepseek;
 pub(super) mod fireworks;
 pub(super) mod gemini;
 pub(super) mod groq;
+pub(super) mod huggingface;
 pub(super) mod mimo;
+pub(super) mod mock;
 pub(super) mod nebius;
 pub(super) mod ollama;
 pub(super) mod openai;
+pub(super) mod openai_compat;
 pub(super) mod openai_resp;
+pub(super) mod perplexity;
 pub(super) mod together;
 pub(super) mod xai;
 pub(super) mod zai;
@@ -1,18 +1,19 @@
 //! API DOC: <https://github.com/ollama/ollama/blob/main/docs/api.md>
 
 use crate::Headers;
+use crate::adapter::adapters::support::check_no_regex_response_format;
 use crate::adapter::ollama::OllamaStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	Binary, BinarySource, ChatOptionsSet, ChatRequest, ChatResponse, ChatStream, ChatStreamResponse, ContentPart,
-	MessageContent, Tool, ToolCall, ToolName, Usage,
+	MessageContent, OllamaOptions, TokenCount, Tool, ToolCall, ToolName, Usage,
 };
 use crate::embed::{EmbedResponse, Embedding};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
+use crate::support::rfc3339_to_unix_secs;
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
 use crate::{Error, Result};
-use crate::{ModelIden, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::{ModelIden, ModelInfo, ServiceTarget};
 use serde_json::{Value, json};
 use value_ext::JsonValueExt;
 
@@ -64,6 +65,39 @@ impl Adapter for OllamaAdapter {
 		Ok(models)
 	}
 
+	/// Note: The `/api/tags` list does not have the model limits nor capabilities (see `Client::ollama_show`)
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		let ServiceTarget { model, endpoint, .. } = service_target;
+
+		let base_url = endpoint.base_url();
+		let url = format!("{base_url}api/tags");
+
+		let mut res =
+			web_client
+				.do_get(&url, &Headers::default())
+				.await
+				.map_err(|webc_error| Error::WebAdapterCall {
+					adapter_kind: model.adapter_kind,
+					webc_error,
+				})?;
+
+		let models: Vec<Value> = res.body.x_take("models").unwrap_or_default();
+		let infos = models
+			.into_iter()
+			.filter_map(|mut model_value| {
+				let name: String = model_value.x_take("name").ok()?;
+				let mut info = ModelInfo::new(model.adapter_kind, name);
+				info.created = model_value
+					.x_get::<String>("modified_at")
+					.ok()
+					.and_then(|modified_at| rfc3339_to_unix_secs(&modified_at));
+				Some(info)
+			})
+			.collect();
+
+		Ok(infos)
+	}
+
 	fn get_service_url(_model_iden: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
 		let base_url = endpoint.base_url();
 		match service_type {
@@ -79,6 +113,7 @@ impl Adapter for OllamaAdapter {
 		chat_options: ChatOptionsSet<'_, '_>,
 	) -> Result<WebRequestData> {
 		let ServiceTarget { model, endpoint, .. } = target;
+		check_no_regex_response_format(&model, &chat_options)?;
 
 		// -- Service URL
 		let url = Self::get_service_url(&model, service_type, endpoint)?;
@@ -103,6 +138,10 @@ impl Adapter for OllamaAdapter {
 		if !chat_options.stop_sequences().is_empty() {
 			options.x_insert("stop", chat_options.stop_sequences())?;
 		}
+		let ollama_options = chat_options.ollama_options();
+		if let Some(ollama_options) = ollama_options {
+			Self::insert_ollama_options(&mut options, ollama_options)?;
+		}
 
 		// -- Build Payload
 		let stream = matches!(service_type, ServiceType::ChatStream);
@@ -118,6 +157,10 @@ impl Adapter for OllamaAdapter {
 			payload.x_insert("options", options)?;
 		}
 
+		if let Some(keep_alive) = ollama_options.and_then(|o| o.keep_alive.as_ref()) {
+			payload.x_insert("keep_alive", keep_alive)?;
+		}
+
 		if let Some(tools) = tools {
 			payload.x_insert("tools", tools)?;
 		}
@@ -138,6 +181,25 @@ impl Adapter for OllamaAdapter {
 		Ok(WebRequestData { url, headers, payload })
 	}
 
+	/// Ollama has no tokenize API, so this is an `api/chat` request generating a single token,
+	/// and the count is its `prompt_eval_count`.
+	/// NOTE: This loads the model (if not loaded yet), like a chat request.
+	fn to_count_tokens_request_data(
+		target: ServiceTarget,
+		chat_req: ChatRequest,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<Option<WebRequestData>> {
+		let mut web_request_data = Self::to_web_request_data(target, ServiceType::Chat, chat_req, options_set)?;
+		web_request_data.payload.x_insert("/options/num_predict", 1)?;
+		Ok(Some(web_request_data))
+	}
+
+	fn to_token_count(model_iden: ModelIden, web_response: WebResponse) -> Result<TokenCount> {
+		let WebResponse { mut body, .. } = web_response;
+		let input_tokens: u32 = body.x_take("prompt_eval_count")?;
+		Ok(TokenCount::from_provider(model_iden, input_tokens))
+	}
+
 	fn to_chat_response(
 		model_iden: ModelIden,
 		web_response: WebResponse,
@@ -192,22 +254,23 @@ impl Adapter for OllamaAdapter {
 			model_iden: model_iden.clone(),
 			provider_model_iden: model_iden,
 			usage,
+			citations: None,
 			captured_raw_body,
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
 		let streamer = OllamaStreamer::new(
-			crate::webc::WebStream::new_with_delimiter(reqwest_builder, "\n"),
+			crate::webc::WebStream::new_with_delimiter(stream_source, "\n"),
 			model_iden.clone(),
 			options_set,
 		);
 		Ok(ChatStreamResponse {
-			stream: ChatStream::from_inter_stream(streamer),
+			stream: ChatStream::from_inter_stream(model_iden.clone(), streamer),
 			model_iden,
 		})
 	}
@@ -280,6 +343,30 @@ impl Adapter for OllamaAdapter {
 // region:    --- Support
 
 impl OllamaAdapter {
+	/// Insert the `OllamaOptions` runtime options into the Ollama `options` object.
+	/// Note: The `keep_alive` is a top-level property, so it is handled by the caller.
+	fn insert_ollama_options(options: &mut Value, ollama_options: &OllamaOptions) -> Result<()> {
+		if let Some(num_ctx) = ollama_options.num_ctx {
+			options.x_insert("num_ctx", num_ctx)?;
+		}
+		if let Some(num_gpu) = ollama_options.num_gpu {
+			options.x_insert("num_gpu", num_gpu)?;
+		}
+		if let Some(repeat_penalty) = ollama_options.repeat_penalty {
+			options.x_insert("repeat_penalty", repeat_penalty)?;
+		}
+		if let Some(mirostat) = ollama_options.mirostat {
+			options.x_insert("mirostat", mirostat)?;
+		}
+		if let Some(mirostat_eta) = ollama_options.mirostat_eta {
+			options.x_insert("mirostat_eta", mirostat_eta)?;
+		}
+		if let Some(mirostat_tau) = ollama_options.mirostat_tau {
+			options.x_insert("mirostat_tau", mirostat_tau)?;
+		}
+		Ok(())
+	}
+
 	fn into_usage(body: &mut Value) -> Usage {
 		let prompt_tokens = body.x_take::<i32>("prompt_eval_count").ok();
 		let completion_tokens = body.x_take::<i32>("eval_count").ok();
@@ -321,15 +408,13 @@ impl OllamaAdapter {
 			for part in msg.content {
 				match part {
 					ContentPart::Text(txt) => content.push_str(&txt),
+					// Note: Ollama native API expects images in base64 format in a field named "images" as an array.
 					ContentPart::Binary(Binary {
-						content_type, source, ..
-					}) => {
-						if content_type.starts_with("image/") {
-							// Note: Ollama native API expects images in base64 format in a field named "images" as an array.
-							if let BinarySource::Base64(data) = source {
-								images.push(data);
-							}
-						}
+						content_type,
+						source: BinarySource::Base64(data),
+						..
+					}) if content_type.starts_with("image/") => {
+						images.push(data);
 					}
 					ContentPart::ToolCall(tool_call) => {
 						tool_calls.push(json!({
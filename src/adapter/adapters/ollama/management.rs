@@ -0,0 +1,302 @@
+//! Ollama model management API (pull, show, unload)
+//! API DOC: <https://github.com/ollama/ollama/blob/main/docs/api.md#pull-a-model>
+
+use crate::adapter::WebRequestData;
+use crate::adapter::ollama::OllamaAdapter;
+use crate::webc::{WebResponse, WebStream, WebStreamSource};
+use crate::{Error, Headers, ModelIden, Result, ServiceTarget};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use value_ext::JsonValueExt;
+
+// region:    --- OllamaPullProgress
+
+/// One progress event of an Ollama model pull (`/api/pull` streamed response).
+///
+/// The `status` is, for example, `"pulling manifest"`, `"pulling <digest>"`, `"verifying sha256 digest"`,
+/// and finally `"success"`. The `total` and `completed` bytes are only present for the layer downloads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaPullProgress {
+	pub status: String,
+	pub digest: Option<String>,
+	pub total: Option<u64>,
+	pub completed: Option<u64>,
+}
+
+impl OllamaPullProgress {
+	/// Returns true for the final `success` event.
+	pub fn is_success(&self) -> bool {
+		self.status == "success"
+	}
+
+	/// Returns the completion ratio (0.0 to 1.0) of the current layer download, when known.
+	pub fn ratio(&self) -> Option<f64> {
+		match (self.completed, self.total) {
+			(Some(completed), Some(total)) if total > 0 => Some(completed as f64 / total as f64),
+			_ => None,
+		}
+	}
+}
+
+// endregion: --- OllamaPullProgress
+
+// region:    --- OllamaPullStream
+
+/// The stream of `OllamaPullProgress` returned by `Client::ollama_pull(...)`.
+///
+/// Note: An `{"error": ...}` event from Ollama (e.g., unknown model) is returned as an `Error::ChatResponse`
+///       and ends the stream.
+pub struct OllamaPullStream {
+	inner: WebStream,
+	model_iden: ModelIden,
+	done: bool,
+}
+
+impl OllamaPullStream {
+	pub(crate) fn new(inner: WebStream, model_iden: ModelIden) -> Self {
+		Self {
+			inner,
+			model_iden,
+			done: false,
+		}
+	}
+}
+
+impl futures::Stream for OllamaPullStream {
+	type Item = Result<OllamaPullProgress>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if self.done {
+			return Poll::Ready(None);
+		}
+
+		while let Poll::Ready(event) = Pin::new(&mut self.inner).poll_next(cx) {
+			match event {
+				Some(Ok(line)) => {
+					if line.trim().is_empty() {
+						continue;
+					}
+
+					let data: Value = match serde_json::from_str(&line) {
+						Ok(data) => data,
+						Err(serde_error) => {
+							self.done = true;
+							return Poll::Ready(Some(Err(Error::StreamParse {
+								model_iden: self.model_iden.clone(),
+								serde_error,
+							})));
+						}
+					};
+
+					if data.get("error").is_some() {
+						self.done = true;
+						return Poll::Ready(Some(Err(Error::ChatResponse {
+							model_iden: self.model_iden.clone(),
+							body: data,
+						})));
+					}
+
+					let progress: OllamaPullProgress = match serde_json::from_value(data) {
+						Ok(progress) => progress,
+						Err(serde_error) => {
+							self.done = true;
+							return Poll::Ready(Some(Err(Error::StreamParse {
+								model_iden: self.model_iden.clone(),
+								serde_error,
+							})));
+						}
+					};
+
+					if progress.is_success() {
+						self.done = true;
+					}
+
+					return Poll::Ready(Some(Ok(progress)));
+				}
+				Some(Err(err)) => {
+					self.done = true;
+					return Poll::Ready(Some(Err(Error::WebStream {
+						model_iden: self.model_iden.clone(),
+						cause: err.to_string(),
+						error: err,
+					})));
+				}
+				None => {
+					self.done = true;
+					return Poll::Ready(None);
+				}
+			}
+		}
+
+		Poll::Pending
+	}
+}
+
+// endregion: --- OllamaPullStream
+
+// region:    --- OllamaModelInfo
+
+/// The model details returned by `Client::ollama_show(...)` (`/api/show`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaModelInfo {
+	/// The model name, as requested (e.g., `gemma3:4b`).
+	pub model_name: String,
+
+	/// e.g., `gguf`
+	pub format: Option<String>,
+	/// e.g., `gemma3`
+	pub family: Option<String>,
+	pub families: Vec<String>,
+	/// e.g., `4.3B`
+	pub parameter_size: Option<String>,
+	/// e.g., `Q4_K_M`
+	pub quantization_level: Option<String>,
+
+	/// The maximum context length of the model (from the `<architecture>.context_length` model info).
+	/// Note: This is the model limit, the context used at runtime is the `OllamaOptions::num_ctx`.
+	pub context_length: Option<u64>,
+	/// The embedding length of the model (from the `<architecture>.embedding_length` model info).
+	pub embedding_length: Option<u64>,
+
+	/// e.g., `["completion", "vision", "tools", "thinking", "embedding"]`
+	pub capabilities: Vec<String>,
+
+	/// The Modelfile parameters (one `name value` per line).
+	pub parameters: Option<String>,
+	pub template: Option<String>,
+	pub modified_at: Option<String>,
+
+	/// The full `model_info` object (e.g., `general.architecture`, `<architecture>.block_count`, ...).
+	pub model_info: Value,
+}
+
+impl OllamaModelInfo {
+	/// Returns true if the model has the given capability (e.g., `"tools"`).
+	pub fn has_capability(&self, capability: &str) -> bool {
+		self.capabilities.iter().any(|c| c == capability)
+	}
+}
+
+// endregion: --- OllamaModelInfo
+
+// region:    --- OllamaAdapter Management
+
+impl OllamaAdapter {
+	/// `/api/pull` request, always streamed.
+	pub(crate) fn to_pull_request_data(target: ServiceTarget) -> Result<WebRequestData> {
+		Self::to_management_request_data(target, "api/pull", json!({"stream": true}))
+	}
+
+	/// `/api/show` request.
+	pub(crate) fn to_show_request_data(target: ServiceTarget) -> Result<WebRequestData> {
+		Self::to_management_request_data(target, "api/show", json!({}))
+	}
+
+	/// `/api/generate` request without prompt and with `keep_alive: 0`, which unloads the model from memory.
+	pub(crate) fn to_unload_request_data(target: ServiceTarget) -> Result<WebRequestData> {
+		Self::to_management_request_data(target, "api/generate", json!({"keep_alive": 0}))
+	}
+
+	pub(crate) fn to_pull_stream(model_iden: ModelIden, stream_source: WebStreamSource) -> OllamaPullStream {
+		OllamaPullStream::new(WebStream::new_with_delimiter(stream_source, "\n"), model_iden)
+	}
+
+	pub(crate) fn to_model_info(model_iden: &ModelIden, web_response: WebResponse) -> Result<OllamaModelInfo> {
+		let WebResponse { mut body, .. } = web_response;
+
+		let (_, model_name) = model_iden.model_name.namespace_and_name();
+
+		let mut details: Value = body.x_take("details").unwrap_or_default();
+		let model_info: Value = body.x_take("model_info").unwrap_or_default();
+
+		// The model limits are prefixed by the architecture (e.g., `llama.context_length`)
+		let architecture: Option<String> = model_info.x_get("general.architecture").ok();
+		let arch_u64 = |name: &str| -> Option<u64> {
+			let arch = architecture.as_deref()?;
+			model_info.get(format!("{arch}.{name}"))?.as_u64()
+		};
+		let context_length = arch_u64("context_length");
+		let embedding_length = arch_u64("embedding_length");
+
+		Ok(OllamaModelInfo {
+			model_name: model_name.to_string(),
+			format: details.x_take("format").ok(),
+			family: details.x_take("family").ok(),
+			families: details.x_take("families").unwrap_or_default(),
+			parameter_size: details.x_take("parameter_size").ok(),
+			quantization_level: details.x_take("quantization_level").ok(),
+			context_length,
+			embedding_length,
+			capabilities: body.x_take("capabilities").unwrap_or_default(),
+			parameters: body.x_take("parameters").ok(),
+			template: body.x_take("template").ok(),
+			modified_at: body.x_take("modified_at").ok(),
+			model_info,
+		})
+	}
+
+	fn to_management_request_data(target: ServiceTarget, path: &str, mut payload: Value) -> Result<WebRequestData> {
+		let ServiceTarget { model, endpoint, .. } = target;
+
+		let base_url = endpoint.base_url();
+		let url = format!("{base_url}{path}");
+
+		let (_, model_name) = model.model_name.namespace_and_name();
+		payload.x_insert("model", model_name)?;
+
+		Ok(WebRequestData {
+			url,
+			headers: Headers::default(),
+			payload,
+		})
+	}
+}
+
+// endregion: --- OllamaAdapter Management
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::adapter::AdapterKind;
+	use reqwest::StatusCode;
+
+	#[test]
+	fn test_ollama_to_model_info() {
+		let model_iden = ModelIden::new(AdapterKind::Ollama, "gemma3:4b");
+		let body = json!({
+			"parameters": "stop \"<end_of_turn>\"",
+			"details": {
+				"format": "gguf",
+				"family": "gemma3",
+				"families": ["gemma3"],
+				"parameter_size": "4.3B",
+				"quantization_level": "Q4_K_M"
+			},
+			"model_info": {
+				"general.architecture": "gemma3",
+				"gemma3.context_length": 131072,
+				"gemma3.embedding_length": 2560
+			},
+			"capabilities": ["completion", "vision"]
+		});
+		let web_response = WebResponse {
+			status: StatusCode::OK,
+			body,
+		};
+
+		let info = OllamaAdapter::to_model_info(&model_iden, web_response).unwrap();
+
+		assert_eq!(info.model_name, "gemma3:4b");
+		assert_eq!(info.context_length, Some(131072));
+		assert_eq!(info.embedding_length, Some(2560));
+		assert_eq!(info.quantization_level.as_deref(), Some("Q4_K_M"));
+		assert!(info.has_capability("vision"));
+		assert!(!info.has_capability("tools"));
+	}
+}
+
+// endregion: --- Tests
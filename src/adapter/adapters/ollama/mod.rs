@@ -6,9 +6,11 @@
 // region:    --- Modules
 
 mod adapter_impl;
+mod management;
 mod streamer;
 
 pub use adapter_impl::*;
+pub use management::*;
 pub use streamer::*;
 
 // endregion: --- Modules
@@ -1,9 +1,10 @@
-use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions};
+use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions, whole_tool_call_events};
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
 use crate::chat::{ChatOptionsSet, ToolCall, Usage};
 use crate::webc::WebStream;
 use crate::{Error, ModelIden, Result};
 use serde_json::Value;
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use value_ext::JsonValueExt;
@@ -20,6 +21,9 @@ pub struct OllamaStreamer {
 	done: bool,
 
 	captured_data: StreamerCapturedData,
+	pending_events: VecDeque<InterStreamEvent>,
+	/// The number of tool calls received (the index of the next `ToolCallStart`)
+	tool_call_count: usize,
 }
 
 impl OllamaStreamer {
@@ -29,6 +33,8 @@ impl OllamaStreamer {
 			done: false,
 			options: StreamerOptions::new(model_iden, options_set),
 			captured_data: Default::default(),
+			pending_events: VecDeque::new(),
+			tool_call_count: 0,
 		}
 	}
 }
@@ -37,6 +43,9 @@ impl futures::Stream for OllamaStreamer {
 	type Item = Result<InterStreamEvent>;
 
 	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if let Some(event) = self.pending_events.pop_front() {
+			return Poll::Ready(Some(Ok(event)));
+		}
 		if self.done {
 			return Poll::Ready(None);
 		}
@@ -125,9 +134,14 @@ impl futures::Stream for OllamaStreamer {
 										None => self.captured_data.tool_calls = Some(tcs.clone()),
 									}
 								}
-								// Return the tool call as a chunk
-								if let Some(tc) = tcs.into_iter().next() {
-									return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallChunk(tc))));
+								// Ollama sends the whole tool calls at once
+								for tc in tcs {
+									let tool_call_events = whole_tool_call_events(self.tool_call_count, tc);
+									self.pending_events.extend(tool_call_events);
+									self.tool_call_count += 1;
+								}
+								if let Some(event) = self.pending_events.pop_front() {
+									return Poll::Ready(Some(Ok(event)));
 								}
 							}
 						}
@@ -159,6 +173,7 @@ impl futures::Stream for OllamaStreamer {
 								captured_reasoning_content: self.captured_data.reasoning_content.take(),
 								captured_tool_calls: self.captured_data.tool_calls.take(),
 								captured_thought_signatures: None,
+								captured_citations: None,
 							};
 
 							return Poll::Ready(Some(Ok(InterStreamEvent::End(inter_stream_end))));
@@ -181,6 +196,7 @@ impl futures::Stream for OllamaStreamer {
 							captured_reasoning_content: self.captured_data.reasoning_content.take(),
 							captured_tool_calls: self.captured_data.tool_calls.take(),
 							captured_thought_signatures: None,
+							captured_citations: None,
 						};
 						return Poll::Ready(Some(Ok(InterStreamEvent::End(inter_stream_end))));
 					}
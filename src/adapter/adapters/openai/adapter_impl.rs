@@ -4,10 +4,9 @@ use crate::chat::{
 	ChatOptionsSet, ChatRequest, ChatResponse, ChatStream, ChatStreamResponse, MessageContent, ToolCall,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{EventSourceStream, WebResponse};
+use crate::webc::{EventSourceStream, WebClient, WebResponse, WebStreamSource};
 use crate::{Error, Result};
-use crate::{ModelIden, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::{ModelIden, ModelInfo, ServiceTarget};
 use serde::Deserialize;
 use serde_json::Value;
 use value_ext::JsonValueExt;
@@ -38,6 +37,10 @@ impl Adapter for OpenAIAdapter {
 		OpenAIAdapter::list_model_names_for_end_target(kind, Self::default_endpoint(), Self::default_auth()).await
 	}
 
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		Self::util_list_models(service_target, web_client).await
+	}
+
 	fn get_service_url(model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
 		Self::util_get_service_url(model, service_type, endpoint)
 	}
@@ -123,18 +126,19 @@ impl Adapter for OpenAIAdapter {
 			model_iden,
 			provider_model_iden,
 			usage,
+			citations: None,
 			captured_raw_body: None, // Set by the client exec_chat
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_sets: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let event_source = EventSourceStream::new(reqwest_builder);
+		let event_source = EventSourceStream::new(stream_source);
 		let openai_stream = OpenAIStreamer::new(event_source, model_iden.clone(), options_sets);
-		let chat_stream = ChatStream::from_inter_stream(openai_stream);
+		let chat_stream = ChatStream::from_inter_stream(model_iden.clone(), openai_stream);
 
 		Ok(ChatStreamResponse {
 			model_iden,
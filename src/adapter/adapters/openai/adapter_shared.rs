@@ -1,13 +1,14 @@
 //! This is support implementation of the OpenAI Adapter which can also be called by other OpenAI Adapter Variants
 
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_no_regex_response_format, get_api_key};
 use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::{AdapterDispatcher, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	BinarySource, ChatOptionsSet, ChatRequest, ChatResponseFormat, ChatRole, ContentPart, ReasoningEffort, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::{Error, Headers, Result};
+use crate::webc::WebClient;
+use crate::{Error, Headers, Modality, ModelInfo, Result};
 use crate::{ModelIden, ServiceTarget};
 use serde_json::{Value, json};
 use tracing::error;
@@ -50,8 +51,6 @@ impl OpenAIAdapter {
 		custom: Option<ToWebRequestCustom>,
 	) -> Result<WebRequestData> {
 		let ServiceTarget { model, auth, endpoint } = target;
-		let (_, model_name) = model.model_name.namespace_and_name();
-		let adapter_kind = model.adapter_kind;
 
 		// -- url
 		let url = AdapterDispatcher::get_service_url(&model, service_type, endpoint)?;
@@ -60,26 +59,45 @@ impl OpenAIAdapter {
 		let api_key = get_api_key(auth, &model)?;
 		let headers = Headers::from(("Authorization".to_string(), format!("Bearer {api_key}")));
 
+		// -- payload
+		let payload = Self::util_to_chat_payload(&model, service_type, chat_req, &options_set, custom.as_ref())?;
+
+		Ok(WebRequestData { url, headers, payload })
+	}
+
+	/// Shared OpenAI chat completions payload builder (no url, no headers).
+	/// Used by [`Self::util_to_web_request_data`] and by adapters that manage their own auth headers.
+	pub(in crate::adapter::adapters) fn util_to_chat_payload(
+		model: &ModelIden,
+		service_type: ServiceType,
+		chat_req: ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+		custom: Option<&ToWebRequestCustom>,
+	) -> Result<Value> {
+		let (_, model_name) = model.model_name.namespace_and_name();
+		let adapter_kind = model.adapter_kind;
+
 		let stream = matches!(service_type, ServiceType::ChatStream);
 
+		if adapter_kind != AdapterKind::HuggingFace {
+			check_no_regex_response_format(model, options_set)?;
+		}
+
 		// -- compute reasoning_effort and eventual trimmed model_name
-		// For now, just for openai AdapterKind
-		let (reasoning_effort, model_name): (Option<ReasoningEffort>, &str) =
-			if matches!(adapter_kind, AdapterKind::OpenAI) {
-				let (reasoning_effort, model_name) = options_set
-					.reasoning_effort()
-					.cloned()
-					.map(|v| (Some(v), model_name))
-					.unwrap_or_else(|| ReasoningEffort::from_model_name(model_name));
-
-				(reasoning_effort, model_name)
-			} else {
-				(None, model_name)
-			};
+		let (reasoning_effort, model_name): (Option<ReasoningEffort>, &str) = match adapter_kind {
+			AdapterKind::OpenAI => options_set
+				.reasoning_effort()
+				.cloned()
+				.map(|v| (Some(v), model_name))
+				.unwrap_or_else(|| ReasoningEffort::from_model_name(model_name)),
+			// For OpenAI compatible backends, only the explicit reasoning_effort is used (no model name suffix inference)
+			AdapterKind::OpenAICompat => (options_set.reasoning_effort().cloned(), model_name),
+			_ => (None, model_name),
+		};
 
 		// -- Build the basic payload
 
-		let OpenAIRequestParts { messages, tools } = Self::into_openai_request_parts(&model, chat_req)?;
+		let OpenAIRequestParts { messages, tools } = Self::into_openai_request_parts(model, chat_req)?;
 		let mut payload = json!({
 			"model": model_name,
 			"messages": messages,
@@ -109,6 +127,8 @@ impl OpenAIAdapter {
 		let response_format = if let Some(response_format) = options_set.response_format() {
 			match response_format {
 				ChatResponseFormat::JsonMode => Some(json!({"type": "json_object"})),
+				// Not supported by OpenAI (rejected above), set as the TGI grammar by the HuggingFace adapter
+				ChatResponseFormat::Regex(_) => None,
 				ChatResponseFormat::JsonSpec(st_json) => {
 					// "type": "json_schema", "json_schema": {...}
 
@@ -157,7 +177,7 @@ impl OpenAIAdapter {
 
 		if let Some(max_tokens) = options_set.max_tokens() {
 			payload.x_insert("max_tokens", max_tokens)?;
-		} else if let Some(custom) = custom.as_ref()
+		} else if let Some(custom) = custom
 			&& let Some(max_tokens) = custom.default_max_tokens
 		{
 			payload.x_insert("max_tokens", max_tokens)?;
@@ -174,7 +194,7 @@ impl OpenAIAdapter {
 			payload.x_insert("service_tier", keyword)?;
 		}
 
-		Ok(WebRequestData { url, headers, payload })
+		Ok(payload)
 	}
 
 	/// Note: Needs to be called from super::streamer as well
@@ -417,6 +437,110 @@ impl OpenAIAdapter {
 
 		Ok(models)
 	}
+
+	/// List the models from the OpenAI-like `{base_url}models` endpoint, with the Bearer auth (if any).
+	pub(in crate::adapter::adapters) async fn util_list_models(
+		service_target: ServiceTarget,
+		web_client: &WebClient,
+	) -> Result<Vec<ModelInfo>> {
+		let api_key = service_target.auth.single_key_value().ok();
+		let headers = api_key
+			.map(|api_key| Headers::from(("Authorization".to_string(), format!("Bearer {api_key}"))))
+			.unwrap_or_default();
+
+		Self::util_list_models_with_headers(service_target, headers, web_client).await
+	}
+
+	/// Same as [`OpenAIAdapter::util_list_models`], but with the headers of the caller (e.g., custom auth header).
+	pub(in crate::adapter::adapters) async fn util_list_models_with_headers(
+		service_target: ServiceTarget,
+		headers: Headers,
+		web_client: &WebClient,
+	) -> Result<Vec<ModelInfo>> {
+		let ServiceTarget { model, endpoint, .. } = service_target;
+		let adapter_kind = model.adapter_kind;
+
+		// -- url
+		let base_url = endpoint.base_url();
+		let url = format!("{base_url}models");
+
+		// -- Exec request
+		let res = web_client
+			.do_get(&url, &headers)
+			.await
+			.map_err(|webc_error| Error::WebAdapterCall {
+				adapter_kind,
+				webc_error,
+			})?;
+
+		Ok(Self::util_to_model_infos(adapter_kind, res.body))
+	}
+
+	/// Parse an OpenAI-like models list (`{"data": [...]}`, or a top-level array).
+	///
+	/// Besides the OpenAI `id` and `created`, this takes the common extensions of the compatible providers:
+	/// - `context_window` / `max_completion_tokens` (e.g., Groq)
+	/// - `display_name`, `context_length` and `type` (e.g., Together)
+	/// - `architecture` modalities, `supported_parameters` (e.g., OpenRouter)
+	/// - `providers` with `context_length` and `supports_tools` (e.g., Hugging Face router)
+	pub(in crate::adapter::adapters) fn util_to_model_infos(
+		adapter_kind: AdapterKind,
+		mut body: Value,
+	) -> Vec<ModelInfo> {
+		let items: Vec<Value> = match body {
+			Value::Array(items) => items,
+			_ => body.x_take("data").unwrap_or_default(),
+		};
+
+		items
+			.into_iter()
+			.filter_map(|item| Self::util_to_model_info(adapter_kind, item))
+			.collect()
+	}
+
+	fn util_to_model_info(adapter_kind: AdapterKind, mut item: Value) -> Option<ModelInfo> {
+		let id: String = item.x_take("id").ok()?;
+		let mut info = ModelInfo::new(adapter_kind, id);
+
+		info.display_name = item.x_take("display_name").or_else(|_| item.x_take("name")).ok();
+		info.created = item.x_get("created").ok();
+		info.context_window = item.x_get("context_window").or_else(|_| item.x_get("context_length")).ok();
+		info.max_output_tokens = item
+			.x_get("max_completion_tokens")
+			.or_else(|_| item.x_get("/top_provider/max_completion_tokens"))
+			.ok();
+
+		// -- Modalities
+		let to_modalities = |names: Vec<String>| names.iter().filter_map(|n| Modality::from_keyword(n)).collect();
+		if let Ok(names) = item.x_get::<Vec<String>>("/architecture/input_modalities") {
+			info.input_modalities = to_modalities(names);
+		}
+		if let Ok(names) = item.x_get::<Vec<String>>("/architecture/output_modalities") {
+			info.output_modalities = to_modalities(names);
+		}
+
+		// -- Capabilities
+		if let Ok(params) = item.x_get::<Vec<String>>("supported_parameters") {
+			info.supports_tools = Some(params.iter().any(|p| p == "tools"));
+			info.supports_reasoning = Some(params.iter().any(|p| p == "reasoning"));
+		}
+		if let Ok(providers) = item.x_get::<Vec<Value>>("providers") {
+			let supports_tools = providers.iter().any(|p| p.x_get::<bool>("supports_tools").unwrap_or_default());
+			info.supports_tools = Some(supports_tools);
+			if info.context_window.is_none() {
+				info.context_window = providers.iter().filter_map(|p| p.x_get::<u32>("context_length").ok()).max();
+			}
+		}
+
+		// NOTE: Most providers do not have a model type, so we fallback on the naming convention (e.g., `text-embedding-3-small`)
+		info.supports_embeddings = match item.x_get::<String>("type").ok().as_deref() {
+			Some("embedding") => Some(true),
+			Some("chat") | Some("language") | Some("code") => Some(false),
+			_ => info.id.contains("embed").then_some(true),
+		};
+
+		Some(info)
+	}
 }
 
 /// Custom OpenAI structure for Adapters to use to customize
@@ -433,3 +557,57 @@ struct OpenAIRequestParts {
 }
 
 // endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_openai_util_to_model_infos() {
+		let body = json!({
+			"object": "list",
+			"data": [
+				{"id": "llama-3.3-70b-versatile", "created": 1733447754, "context_window": 131072, "max_completion_tokens": 32768},
+				{"id": "text-embedding-3-small", "created": 1705948997},
+				{
+					"id": "Qwen/Qwen3-8B",
+					"architecture": {"input_modalities": ["text", "image"], "output_modalities": ["text"]},
+					"providers": [{"context_length": 32768, "supports_tools": false}, {"context_length": 40960, "supports_tools": true}]
+				}
+			]
+		});
+
+		let infos = OpenAIAdapter::util_to_model_infos(AdapterKind::Groq, body);
+
+		assert_eq!(infos.len(), 3);
+		assert_eq!(infos[0].context_window, Some(131072));
+		assert_eq!(infos[0].max_output_tokens, Some(32768));
+		assert_eq!(infos[0].created, Some(1733447754));
+		assert_eq!(infos[1].supports_embeddings, Some(true));
+		assert_eq!(infos[2].context_window, Some(40960));
+		assert_eq!(infos[2].supports_tools, Some(true));
+		assert_eq!(infos[2].input_modalities, vec![Modality::Text, Modality::Image]);
+	}
+
+	#[test]
+	fn test_openai_util_to_chat_payload_regex_not_supported() {
+		let model = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let options =
+			crate::chat::ChatOptions::default().with_response_format(ChatResponseFormat::Regex("[0-9]+".to_string()));
+		let options_set = ChatOptionsSet::default().with_chat_options(Some(&options));
+
+		let res = OpenAIAdapter::util_to_chat_payload(
+			&model,
+			ServiceType::Chat,
+			ChatRequest::from_user("Hi"),
+			&options_set,
+			None,
+		);
+
+		assert!(matches!(res, Err(Error::AdapterNotSupported { .. })));
+	}
+}
+
+// endregion: --- Tests
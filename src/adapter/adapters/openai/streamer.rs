@@ -2,10 +2,13 @@ use crate::adapter::AdapterKind;
 use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions};
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
 use crate::adapter::openai::OpenAIAdapter;
-use crate::chat::{ChatOptionsSet, ToolCall};
+use crate::adapter::openai_compat::CompatProfile;
+use crate::adapter::perplexity::PerplexityAdapter;
+use crate::chat::{ChatOptionsSet, Citation, ToolCall, ToolCallArgsDelta, ToolCallEnd, ToolCallStart};
 use crate::webc::{Event, EventSourceStream};
 use crate::{Error, ModelIden, Result};
 use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use value_ext::JsonValueExt;
@@ -18,21 +21,87 @@ pub struct OpenAIStreamer {
 	/// Flag to prevent polling the EventSource after a MessageStop event
 	done: bool,
 	captured_data: StreamerCapturedData,
+
+	/// The profile used to normalize the chunks for `AdapterKind::OpenAICompat`
+	compat_profile: Option<CompatProfile>,
+
+	/// The citations of the last chunk that had some (`AdapterKind::Perplexity` sends them on each chunk)
+	captured_citations: Option<Vec<Citation>>,
+
+	/// The started tool calls (by tool call index), with their arguments so far, until their `ToolCallEnd`
+	in_progress_tool_calls: BTreeMap<usize, ToolCall>,
+	pending_events: VecDeque<InterStreamEvent>,
 }
 
 impl OpenAIStreamer {
 	pub fn new(inner: EventSourceStream, model_iden: ModelIden, options_set: ChatOptionsSet<'_, '_>) -> Self {
+		let compat_profile = matches!(model_iden.adapter_kind, AdapterKind::OpenAICompat)
+			.then(|| options_set.compat_profile().cloned().unwrap_or_default());
 		Self {
 			inner,
 			done: false,
 			options: StreamerOptions::new(model_iden, options_set),
 			captured_data: Default::default(),
+			compat_profile,
+			captured_citations: None,
+			in_progress_tool_calls: BTreeMap::new(),
+			pending_events: VecDeque::new(),
+		}
+	}
+
+	/// Queues the `ToolCallStart` (on the first delta of the tool call) and the `ToolCallArgsDelta` events.
+	/// Returns the started tool call with the arguments so far (the later deltas only have a fallback id).
+	fn queue_tool_call_delta_events(
+		&mut self,
+		index: usize,
+		call_id: &str,
+		fn_name: &str,
+		arguments: &str,
+	) -> ToolCall {
+		let tool_call = self.in_progress_tool_calls.entry(index).or_insert_with(|| {
+			self.pending_events.push_back(InterStreamEvent::ToolCallStart(ToolCallStart {
+				call_id: call_id.to_string(),
+				fn_name: fn_name.to_string(),
+				index,
+			}));
+			ToolCall {
+				call_id: call_id.to_string(),
+				fn_name: fn_name.to_string(),
+				fn_arguments: Value::String(String::new()),
+				thought_signatures: None,
+			}
+		});
+
+		if !arguments.is_empty() {
+			if let Some(args) = tool_call.fn_arguments.as_str() {
+				tool_call.fn_arguments = Value::String(format!("{args}{arguments}"));
+			}
+			self.pending_events
+				.push_back(InterStreamEvent::ToolCallArgsDelta(ToolCallArgsDelta {
+					call_id: tool_call.call_id.clone(),
+					delta: arguments.to_string(),
+				}));
+		}
+
+		tool_call.clone()
+	}
+
+	/// Queues the `ToolCallEnd` events of the tool calls in progress.
+	/// NOTE: The OpenAI stream does not mark the end of each tool call, so they all end with the choice.
+	fn queue_tool_call_end_events(&mut self) {
+		for (_, tool_call) in std::mem::take(&mut self.in_progress_tool_calls) {
+			self.pending_events.push_back(InterStreamEvent::ToolCallEnd(ToolCallEnd {
+				call_id: tool_call.call_id,
+			}));
 		}
 	}
 
 	/// Captures a single tool call into `captured_data.tool_calls`, merging with existing if needed.
-	/// Returns the (possibly merged) tool call for use in events.
-	fn capture_tool_call(&mut self, index: usize, call_id: String, fn_name: String, arguments: String) -> ToolCall {
+	fn capture_tool_call(&mut self, index: usize, call_id: String, fn_name: String, arguments: String) {
+		if !self.options.capture_tool_calls {
+			return;
+		}
+
 		let tool_call = ToolCall {
 			call_id: call_id.clone(),
 			fn_name: fn_name.clone(),
@@ -40,10 +109,6 @@ impl OpenAIStreamer {
 			thought_signatures: None,
 		};
 
-		if !self.options.capture_tool_calls {
-			return tool_call;
-		}
-
 		let calls = self.captured_data.tool_calls.get_or_insert_with(Vec::new);
 
 		if let Some(existing_call) = calls.get_mut(index) {
@@ -57,11 +122,9 @@ impl OpenAIStreamer {
 				existing_call.call_id = call_id;
 				existing_call.fn_name = fn_name;
 			}
-			existing_call.clone()
 		} else {
 			// New tool call - resize to handle potential gaps (though unlikely in streaming)
-			calls.resize(index + 1, tool_call.clone());
-			tool_call
+			calls.resize(index + 1, tool_call);
 		}
 	}
 }
@@ -70,6 +133,9 @@ impl futures::Stream for OpenAIStreamer {
 	type Item = Result<InterStreamEvent>;
 
 	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if let Some(event) = self.pending_events.pop_front() {
+			return Poll::Ready(Some(Ok(event)));
+		}
 		if self.done {
 			// The last poll was definitely the end, so end the stream.
 			// This will prevent triggering a stream ended error
@@ -137,9 +203,13 @@ impl futures::Stream for OpenAIStreamer {
 							captured_reasoning_content: self.captured_data.reasoning_content.take(),
 							captured_tool_calls,
 							captured_thought_signatures: None,
+							captured_citations: self.captured_citations.take(),
 						};
 
-						return Poll::Ready(Some(Ok(InterStreamEvent::End(inter_stream_end))));
+						// The tool calls end before the stream end
+						self.queue_tool_call_end_events();
+						self.pending_events.push_back(InterStreamEvent::End(inter_stream_end));
+						return Poll::Ready(self.pending_events.pop_front().map(Ok));
 					}
 
 					// -- Other Content Messages
@@ -150,10 +220,22 @@ impl futures::Stream for OpenAIStreamer {
 							serde_error,
 						})?;
 
+					if let Some(compat_profile) = self.compat_profile.as_ref() {
+						compat_profile.normalize_body(&mut message_data);
+					}
+
 					let first_choice: Option<Value> = message_data.x_take("/choices/0").ok();
 
 					let adapter_kind = self.options.model_iden.adapter_kind;
 
+					// -- Capture the Perplexity citations (if chat options allow it)
+					if matches!(adapter_kind, AdapterKind::Perplexity)
+						&& self.options.capture_content
+						&& let Some(citations) = PerplexityAdapter::take_citations(&mut message_data)
+					{
+						self.captured_citations = Some(citations);
+					}
+
 					// If we have a first choice, then it's a normal message
 					if let Some(mut first_choice) = first_choice {
 						// -- Finish Reason
@@ -180,10 +262,17 @@ impl futures::Stream for OpenAIStreamer {
 										let fn_name = function.x_take::<String>("name").unwrap_or_default();
 										let arguments = function.x_take::<String>("arguments").unwrap_or_default();
 
-										self.capture_tool_call(index as usize, call_id, fn_name, arguments);
+										let tool_call = self.queue_tool_call_delta_events(
+											index as usize,
+											&call_id,
+											&fn_name,
+											&arguments,
+										);
+										self.capture_tool_call(index as usize, tool_call.call_id, fn_name, arguments);
 									}
 								}
 							}
+							self.queue_tool_call_end_events();
 
 							// NOTE: For Groq, the usage is captured when finish_reason indicates stopping, and in the `/x_groq/usage`
 							if self.options.capture_usage {
@@ -205,10 +294,22 @@ impl futures::Stream for OpenAIStreamer {
 											.unwrap_or_default();
 										self.captured_data.usage = Some(usage)
 									}
+									// NOTE: For OpenAI compatible backends (and TGI), capture it here only if present (otherwise, OpenAI way below)
+									AdapterKind::OpenAICompat | AdapterKind::HuggingFace | AdapterKind::Perplexity => {
+										if let Ok(usage) = message_data.x_take::<Value>("usage")
+											&& !usage.is_null()
+										{
+											self.captured_data.usage =
+												Some(OpenAIAdapter::into_usage(adapter_kind, usage));
+										}
+									}
 									_ => (), // do nothing, will be captured the OpenAI way
 								}
 							}
 
+							if let Some(event) = self.pending_events.pop_front() {
+								return Poll::Ready(Some(Ok(event)));
+							}
 							continue;
 						}
 						// -- Tool Call
@@ -233,10 +334,23 @@ impl futures::Stream for OpenAIStreamer {
 									let fn_name = function.x_take::<String>("name").unwrap_or_default();
 									let arguments = function.x_take::<String>("arguments").unwrap_or_default();
 
-									let tool_call = self.capture_tool_call(index as usize, call_id, fn_name, arguments);
+									// NOTE: The tool call events share the id of the started call, and the chunk has the arguments so far
+									let tool_call = self.queue_tool_call_delta_events(
+										index as usize,
+										&call_id,
+										&fn_name,
+										&arguments,
+									);
+									self.capture_tool_call(
+										index as usize,
+										tool_call.call_id.clone(),
+										fn_name,
+										arguments,
+									);
 
-									// Return the ToolCallChunk event
-									return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallChunk(tool_call))));
+									// Return the fine-grained events, then the ToolCallChunk event
+									self.pending_events.push_back(InterStreamEvent::ToolCallChunk(tool_call));
+									return Poll::Ready(self.pending_events.pop_front().map(Ok));
 								}
 							}
 							// No valid tool call found, continue to next message
@@ -318,3 +432,127 @@ impl futures::Stream for OpenAIStreamer {
 		Poll::Pending
 	}
 }
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chat::ChatOptions;
+	use crate::webc::WebStreamSource;
+	use futures::StreamExt;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_openai_streamer_tool_call_events() -> Result<()> {
+		// -- Setup & Fixtures
+		let transcript = [
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_1","function":{"name":"get_weather","arguments":""}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":"}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}"#,
+			"[DONE]",
+		]
+		.map(|data| format!("data: {data}\n\n"))
+		.concat();
+		let model_iden = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let options = ChatOptions::default().with_capture_tool_calls(true);
+		let inner = EventSourceStream::new(WebStreamSource::from_text(transcript));
+		let streamer = OpenAIStreamer::new(
+			inner,
+			model_iden,
+			ChatOptionsSet::default().with_chat_options(Some(&options)),
+		);
+
+		// -- Exec
+		let events: Vec<InterStreamEvent> = streamer.map(|event| event.unwrap()).collect().await;
+
+		// -- Check
+		let mut deltas = String::new();
+		let mut names = Vec::new();
+		for event in &events {
+			match event {
+				InterStreamEvent::Start => names.push("Start"),
+				InterStreamEvent::ToolCallStart(start) => {
+					assert_eq!(
+						(start.call_id.as_str(), start.fn_name.as_str(), start.index),
+						("call_1", "get_weather", 0)
+					);
+					names.push("ToolCallStart");
+				}
+				InterStreamEvent::ToolCallArgsDelta(delta) => {
+					assert_eq!(delta.call_id, "call_1");
+					deltas.push_str(&delta.delta);
+					names.push("ToolCallArgsDelta");
+				}
+				InterStreamEvent::ToolCallEnd(end) => {
+					assert_eq!(end.call_id, "call_1");
+					names.push("ToolCallEnd");
+				}
+				InterStreamEvent::ToolCallChunk(_) => names.push("ToolCallChunk"),
+				InterStreamEvent::End(end) => {
+					let tool_calls = end.captured_tool_calls.as_ref().ok_or("Should have tool calls")?;
+					assert_eq!(tool_calls[0].fn_arguments["city"], "Paris");
+					names.push("End");
+				}
+				_ => names.push("Other"),
+			}
+		}
+		assert_eq!(deltas, r#"{"city":"Paris"}"#);
+		assert_eq!(
+			names,
+			[
+				"Start",
+				"ToolCallStart",
+				"ToolCallChunk",
+				"ToolCallArgsDelta",
+				"ToolCallChunk",
+				"ToolCallArgsDelta",
+				"ToolCallChunk",
+				"ToolCallEnd",
+				"End"
+			]
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_openai_streamer_tool_call_chunks_no_capture() -> Result<()> {
+		// -- Setup & Fixtures
+		let transcript = [
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_1","function":{"name":"get_weather","arguments":""}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":"}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}"#,
+			"[DONE]",
+		]
+		.map(|data| format!("data: {data}\n\n"))
+		.concat();
+		let model_iden = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let inner = EventSourceStream::new(WebStreamSource::from_text(transcript));
+		let streamer = OpenAIStreamer::new(inner, model_iden, ChatOptionsSet::default());
+
+		// -- Exec
+		let events: Vec<InterStreamEvent> = streamer.map(|event| event.unwrap()).collect().await;
+
+		// -- Check
+		let chunks: Vec<&ToolCall> = events
+			.iter()
+			.filter_map(|event| match event {
+				InterStreamEvent::ToolCallChunk(tool_call) => Some(tool_call),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(chunks.len(), 3);
+		assert!(chunks.iter().all(|tool_call| tool_call.call_id == "call_1"));
+		assert!(chunks.iter().all(|tool_call| tool_call.fn_name == "get_weather"));
+		assert_eq!(chunks[1].fn_arguments, Value::String(r#"{"city":"#.to_string()));
+		assert_eq!(chunks[2].fn_arguments, Value::String(r#"{"city":"Paris"}"#.to_string()));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
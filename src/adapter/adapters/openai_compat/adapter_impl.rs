@@ -0,0 +1,169 @@
+use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::openai::OpenAIAdapter;
+use crate::adapter::openai_compat::{CompatAuthStyle, CompatParam, CompatProfile, MaxTokensField};
+use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
+use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
+use crate::embed::{EmbedOptionsSet, EmbedRequest, EmbedResponse};
+use crate::resolver::{AuthData, Endpoint};
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{Headers, ModelIden, ModelInfo, Result, ServiceTarget};
+use value_ext::JsonValueExt;
+
+/// Generic adapter for OpenAI compatible backends, driven by the [`CompatProfile`] of the model namespace.
+/// NOTE: This adapter is activated for `openai_compat::` or client profile namespaced model names (e.g., `vllm::Qwen/Qwen3-8B`)
+///       The client profile is passed with the options set (the default profile when none).
+pub struct OpenAICompatAdapter;
+
+impl Adapter for OpenAICompatAdapter {
+	/// Note: The api key env name is per profile (see [`CompatProfile::api_key_env`])
+	const DEFAULT_API_KEY_ENV_NAME: Option<&'static str> = None;
+
+	/// Note: This is the endpoint of the default profile.
+	///       The model aware one is resolved by `AdapterDispatcher::default_endpoint_for_model`
+	fn default_endpoint() -> Endpoint {
+		CompatProfile::default().endpoint
+	}
+
+	fn default_auth() -> AuthData {
+		CompatProfile::default().default_auth()
+	}
+
+	async fn all_model_names(kind: AdapterKind) -> Result<Vec<String>> {
+		OpenAIAdapter::list_model_names_for_end_target(kind, Self::default_endpoint(), Self::default_auth()).await
+	}
+
+	/// Note: Lists with the default profile (the client profile one is `list_models_with_profile`).
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		Self::list_models_with_profile(service_target, &CompatProfile::default(), web_client).await
+	}
+
+	fn get_service_url(model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
+		OpenAIAdapter::util_get_service_url(model, service_type, endpoint)
+	}
+
+	fn to_web_request_data(
+		target: ServiceTarget,
+		service_type: ServiceType,
+		chat_req: ChatRequest,
+		chat_options: ChatOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		let ServiceTarget { model, auth, endpoint } = target;
+		let profile = chat_options.compat_profile().cloned().unwrap_or_default();
+
+		// -- url & headers
+		let url = Self::get_service_url(&model, service_type, endpoint)?;
+		let headers = Self::auth_headers(&profile, auth, &model)?;
+
+		// -- payload
+		let mut payload = OpenAIAdapter::util_to_chat_payload(&model, service_type, chat_req, &chat_options, None)?;
+
+		// remove the unsupported params
+		for param in CompatParam::ALL {
+			if !profile.supports(*param) {
+				let _ = payload.x_remove::<serde_json::Value>(param.payload_key());
+			}
+		}
+
+		// rename the max_tokens if needed
+		if matches!(profile.max_tokens_field, MaxTokensField::MaxCompletionTokens)
+			&& let Ok(max_tokens) = payload.x_remove::<u32>("max_tokens")
+		{
+			payload.x_insert("max_completion_tokens", max_tokens)?;
+		}
+
+		Ok(WebRequestData { url, headers, payload })
+	}
+
+	fn to_chat_response(
+		model_iden: ModelIden,
+		mut web_response: WebResponse,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatResponse> {
+		let profile = options_set.compat_profile().cloned().unwrap_or_default();
+		profile.normalize_body(&mut web_response.body);
+		OpenAIAdapter::to_chat_response(model_iden, web_response, options_set)
+	}
+
+	/// Note: The stream chunks are normalized with the profile by the `OpenAIStreamer`
+	fn to_chat_stream(
+		model_iden: ModelIden,
+		stream_source: WebStreamSource,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatStreamResponse> {
+		OpenAIAdapter::to_chat_stream(model_iden, stream_source, options_set)
+	}
+
+	fn to_embed_request_data(
+		service_target: ServiceTarget,
+		embed_req: EmbedRequest,
+		options_set: EmbedOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		let ServiceTarget { model, auth, endpoint } = service_target;
+		let profile = options_set.compat_profile().cloned().unwrap_or_default();
+		let auth_headers = Self::auth_headers(&profile, auth, &model)?;
+
+		// NOTE: The OpenAI embed request data uses a Bearer auth, so the headers get rebuilt with the profile auth style.
+		let target = ServiceTarget {
+			model,
+			auth: AuthData::from_single(""),
+			endpoint,
+		};
+		let WebRequestData { url, payload, .. } =
+			OpenAIAdapter::to_embed_request_data(target, embed_req, options_set.clone())?;
+
+		let mut headers = auth_headers;
+		headers.merge(("Content-Type", "application/json"));
+		if let Some(custom_headers) = options_set.headers() {
+			headers.merge_with(custom_headers);
+		}
+
+		Ok(WebRequestData { url, headers, payload })
+	}
+
+	fn to_embed_response(
+		model_iden: ModelIden,
+		web_response: WebResponse,
+		options_set: EmbedOptionsSet<'_, '_>,
+	) -> Result<EmbedResponse> {
+		OpenAIAdapter::to_embed_response(model_iden, web_response, options_set)
+	}
+}
+
+// region:    --- Support
+
+impl OpenAICompatAdapter {
+	/// Note: The models endpoint of the local servers usually does not require a key,
+	///       so a missing key is not an error here.
+	pub(crate) async fn list_models_with_profile(
+		service_target: ServiceTarget,
+		profile: &CompatProfile,
+		web_client: &WebClient,
+	) -> Result<Vec<ModelInfo>> {
+		let headers =
+			Self::auth_headers(profile, service_target.auth.clone(), &service_target.model).unwrap_or_default();
+		OpenAIAdapter::util_list_models_with_headers(service_target, headers, web_client).await
+	}
+
+	/// Build the auth headers per the profile auth style.
+	/// Note: An empty api key does not send any auth header (typical for local servers).
+	fn auth_headers(profile: &CompatProfile, auth: AuthData, model: &ModelIden) -> Result<Headers> {
+		if matches!(profile.auth_style, CompatAuthStyle::None) {
+			return Ok(Headers::default());
+		}
+
+		let api_key = get_api_key(auth, model)?;
+		if api_key.is_empty() {
+			return Ok(Headers::default());
+		}
+
+		let headers = match &profile.auth_style {
+			CompatAuthStyle::Bearer => Headers::from(("Authorization".to_string(), format!("Bearer {api_key}"))),
+			CompatAuthStyle::Header(name) => Headers::from((name.clone(), api_key)),
+			CompatAuthStyle::None => Headers::default(),
+		};
+
+		Ok(headers)
+	}
+}
+
+// endregion: --- Support
@@ -0,0 +1,298 @@
+use crate::adapter::AdapterKind;
+use crate::resolver::{AuthData, Endpoint};
+use serde_json::Value;
+use value_ext::JsonValueExt;
+
+// region:    --- CompatProfile
+
+/// Declarative description of an OpenAI-compatible backend (vLLM, SGLang, LM Studio, LiteLLM, ...)
+/// used by the `AdapterKind::OpenAICompat` adapter.
+///
+/// A profile is set on the client under its namespace, and models are then addressed with it,
+/// e.g., `vllm::Qwen/Qwen3-8B`:
+///
+/// ```no_run
+/// use genai::Client;
+/// use genai::adapter::CompatProfile;
+///
+/// let profile = CompatProfile::new("vllm", "http://localhost:8000/v1/")
+///     .with_api_key_env("VLLM_API_KEY")
+///     .with_reasoning_path("/reasoning_content");
+/// let client = Client::builder().with_compat_profile(profile).build();
+/// ```
+///
+/// NOTE: Built-in adapter namespaces (e.g., `openai::`, `groq::`) take precedence over the client profiles.
+///       The `openai_compat::` namespace uses the client `openai_compat` profile if any, otherwise [`CompatProfile::default`].
+#[derive(Debug, Clone)]
+pub struct CompatProfile {
+	/// The namespace of the profile (e.g., `vllm`)
+	pub name: String,
+
+	/// The base url (e.g., `http://localhost:8000/v1/`)
+	pub endpoint: Endpoint,
+
+	/// The eventual environment variable name of the api key.
+	/// When `None`, no key is required (typical for local servers).
+	pub api_key_env: Option<String>,
+
+	/// How the api key is sent
+	pub auth_style: CompatAuthStyle,
+
+	/// JSON pointer of the reasoning content, relative to the `message` (or streaming `delta`) object.
+	/// When `None`, the OpenAI adapter defaults are used (`/reasoning` or `/reasoning_content`).
+	pub reasoning_path: Option<String>,
+
+	/// JSON pointer of the usage in the response body (or stream chunk), e.g., `/x_groq/usage`.
+	pub usage_path: String,
+
+	/// The request parameters supported by the backend. When `None`, all are supported.
+	/// Unsupported parameters are removed from the request payload.
+	pub supported_params: Option<Vec<CompatParam>>,
+
+	/// The payload property used for the max tokens.
+	pub max_tokens_field: MaxTokensField,
+}
+
+/// How the api key is sent to the backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CompatAuthStyle {
+	/// `Authorization: Bearer {api_key}`
+	#[default]
+	Bearer,
+	/// `{header_name}: {api_key}` (e.g., `api-key` for Azure like gateways)
+	Header(String),
+	/// No auth header is sent
+	None,
+}
+
+/// The payload property name for the `ChatOptions.max_tokens`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MaxTokensField {
+	/// `max_tokens` (most OpenAI compatible servers)
+	#[default]
+	MaxTokens,
+	/// `max_completion_tokens` (newer OpenAI API style)
+	MaxCompletionTokens,
+}
+
+/// The request parameters that a backend might not support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompatParam {
+	Temperature,
+	TopP,
+	MaxTokens,
+	StopSequences,
+	Seed,
+	ReasoningEffort,
+	Verbosity,
+	ServiceTier,
+	ResponseFormat,
+	Tools,
+	/// The `stream_options.include_usage` sent when `capture_usage` is set for streaming
+	StreamOptions,
+}
+
+impl CompatParam {
+	/// The OpenAI payload property for this parameter
+	pub fn payload_key(&self) -> &'static str {
+		match self {
+			CompatParam::Temperature => "temperature",
+			CompatParam::TopP => "top_p",
+			CompatParam::MaxTokens => "max_tokens",
+			CompatParam::StopSequences => "stop",
+			CompatParam::Seed => "seed",
+			CompatParam::ReasoningEffort => "reasoning_effort",
+			CompatParam::Verbosity => "verbosity",
+			CompatParam::ServiceTier => "service_tier",
+			CompatParam::ResponseFormat => "response_format",
+			CompatParam::Tools => "tools",
+			CompatParam::StreamOptions => "stream_options",
+		}
+	}
+
+	pub const ALL: &'static [CompatParam] = &[
+		CompatParam::Temperature,
+		CompatParam::TopP,
+		CompatParam::MaxTokens,
+		CompatParam::StopSequences,
+		CompatParam::Seed,
+		CompatParam::ReasoningEffort,
+		CompatParam::Verbosity,
+		CompatParam::ServiceTier,
+		CompatParam::ResponseFormat,
+		CompatParam::Tools,
+		CompatParam::StreamOptions,
+	];
+}
+
+impl Default for CompatProfile {
+	/// The profile for the `openai_compat::` namespace when none is set on the client
+	/// (vLLM default local url, no api key).
+	fn default() -> Self {
+		Self::new(AdapterKind::OpenAICompat.as_lower_str(), "http://localhost:8000/v1/")
+	}
+}
+
+/// Constructors
+impl CompatProfile {
+	/// Create a new profile with the OpenAI defaults for everything but the namespace and base url.
+	pub fn new(name: impl Into<String>, base_url: impl Into<String>) -> Self {
+		let mut base_url: String = base_url.into();
+		// Note: The url join for the service url requires the trailing `/`
+		if !base_url.ends_with('/') {
+			base_url.push('/');
+		}
+
+		Self {
+			name: name.into(),
+			endpoint: Endpoint::from_owned(base_url),
+			api_key_env: None,
+			auth_style: CompatAuthStyle::Bearer,
+			reasoning_path: None,
+			usage_path: "/usage".to_string(),
+			supported_params: None,
+			max_tokens_field: MaxTokensField::MaxTokens,
+		}
+	}
+}
+
+/// Chainable Setters
+impl CompatProfile {
+	pub fn with_api_key_env(mut self, env_name: impl Into<String>) -> Self {
+		self.api_key_env = Some(env_name.into());
+		self
+	}
+
+	pub fn with_auth_style(mut self, auth_style: CompatAuthStyle) -> Self {
+		self.auth_style = auth_style;
+		self
+	}
+
+	pub fn with_reasoning_path(mut self, path: impl Into<String>) -> Self {
+		self.reasoning_path = Some(path.into());
+		self
+	}
+
+	pub fn with_usage_path(mut self, path: impl Into<String>) -> Self {
+		self.usage_path = path.into();
+		self
+	}
+
+	pub fn with_supported_params(mut self, params: impl IntoIterator<Item = CompatParam>) -> Self {
+		self.supported_params = Some(params.into_iter().collect());
+		self
+	}
+
+	pub fn with_max_tokens_field(mut self, max_tokens_field: MaxTokensField) -> Self {
+		self.max_tokens_field = max_tokens_field;
+		self
+	}
+}
+
+/// Crate Support
+impl CompatProfile {
+	pub(crate) fn default_auth(&self) -> AuthData {
+		match (&self.auth_style, &self.api_key_env) {
+			(CompatAuthStyle::None, _) | (_, None) => AuthData::from_single(""),
+			(_, Some(env_name)) => AuthData::from_env(env_name),
+		}
+	}
+
+	pub(crate) fn supports(&self, param: CompatParam) -> bool {
+		self.supported_params.as_ref().is_none_or(|params| params.contains(&param))
+	}
+
+	/// Normalize a chat completions body (or stream chunk) to the OpenAI layout,
+	/// so that the OpenAI response/streamer logic can be reused as is.
+	/// - Moves the usage at `usage_path` to `/usage`
+	/// - Moves the reasoning at `reasoning_path` to `reasoning_content` of the first choice `message` or `delta`
+	pub(crate) fn normalize_body(&self, body: &mut Value) {
+		if self.usage_path != "/usage"
+			&& let Ok(usage) = body.x_remove::<Value>(&self.usage_path)
+			&& !usage.is_null()
+		{
+			let _ = body.x_insert("usage", usage);
+		}
+
+		if let Some(reasoning_path) = self.reasoning_path.as_deref() {
+			for container_path in ["/choices/0/message", "/choices/0/delta"] {
+				if let Some(container) = body.pointer_mut(container_path)
+					&& let Ok(reasoning) = container.x_remove::<Value>(reasoning_path)
+					&& !reasoning.is_null()
+				{
+					let _ = container.x_insert("reasoning_content", reasoning);
+				}
+			}
+		}
+	}
+}
+
+// endregion: --- CompatProfile
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Client;
+	use crate::chat::{ChatOptions, ChatRequest};
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_compat_profile_normalize_body_groq_like() {
+		let profile = CompatProfile::new("test-groq-like", "http://localhost:1234/v1")
+			.with_usage_path("/x_groq/usage")
+			.with_reasoning_path("/reasoning");
+		let mut body = json!({
+			"choices": [{"delta": {"content": "", "reasoning": "thinking..."}}],
+			"x_groq": {"usage": {"prompt_tokens": 10, "completion_tokens": 5}}
+		});
+
+		profile.normalize_body(&mut body);
+
+		assert_eq!(profile.endpoint.base_url(), "http://localhost:1234/v1/");
+		assert_eq!(body.pointer("/usage/prompt_tokens"), Some(&json!(10)));
+		assert_eq!(
+			body.pointer("/choices/0/delta/reasoning_content"),
+			Some(&json!("thinking..."))
+		);
+		assert!(body.pointer("/choices/0/delta/reasoning").is_none());
+	}
+
+	#[tokio::test]
+	async fn test_compat_profile_per_client() -> Result<()> {
+		// -- Setup & Fixtures
+		let profile = CompatProfile::new("vllm", "http://localhost:1234/v1/").with_api_key_env("VLLM_TEST_KEY");
+		let other_profile =
+			CompatProfile::new("vllm", "http://gpu-box:8000/v1/").with_supported_params([CompatParam::Temperature]);
+		let client = Client::builder().with_compat_profile(profile.clone()).build();
+		let other_client = Client::builder().with_compat_profile(other_profile).build();
+		let plain_client = Client::default();
+
+		// -- Exec
+		let target = client.resolve_service_target("vllm::Qwen/Qwen3-8B").await?;
+		let other_target = other_client.resolve_service_target("vllm::Qwen/Qwen3-8B").await?;
+		let plain_target = plain_client.resolve_service_target("vllm::Qwen/Qwen3-8B").await?;
+		let options = ChatOptions::default().with_temperature(0.2).with_seed(42);
+		let other_data = other_client
+			.build_chat_request("vllm::Qwen/Qwen3-8B", ChatRequest::from_user("Hi"), Some(&options))
+			.await?;
+
+		// -- Check
+		assert_eq!(target.model.adapter_kind, AdapterKind::OpenAICompat);
+		assert_eq!(target.endpoint.base_url(), "http://localhost:1234/v1/");
+		assert!(matches!(target.auth, AuthData::FromEnv(ref name) if name == "VLLM_TEST_KEY"));
+		assert_eq!(other_target.model.adapter_kind, AdapterKind::OpenAICompat);
+		assert_eq!(other_target.endpoint.base_url(), "http://gpu-box:8000/v1/");
+		assert_eq!(other_data.url, "http://gpu-box:8000/v1/chat/completions");
+		assert_eq!(other_data.payload["temperature"], json!(0.2));
+		assert!(other_data.payload.get("seed").is_none());
+		assert_ne!(plain_target.model.adapter_kind, AdapterKind::OpenAICompat);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
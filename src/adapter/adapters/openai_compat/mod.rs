@@ -0,0 +1,15 @@
+//! Generic adapter for OpenAI compatible backends (vLLM, SGLang, LM Studio, LiteLLM, ...).
+//! The backend differences (base url, auth, reasoning/usage location, supported params)
+//! are described by a [`CompatProfile`] set on the client under a model namespace.
+//!
+//! API Documentation:     <https://platform.openai.com/docs/api-reference/chat>
+
+// region:    --- Modules
+
+mod adapter_impl;
+mod compat_profile;
+
+pub use adapter_impl::*;
+pub use compat_profile::*;
+
+// endregion: --- Modules
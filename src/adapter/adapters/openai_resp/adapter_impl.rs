@@ -1,4 +1,4 @@
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_no_regex_response_format, get_api_key};
 use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::openai_resp::OpenAIRespStreamer;
 use crate::adapter::openai_resp::resp_types::RespResponse;
@@ -8,10 +8,9 @@ use crate::chat::{
 	ContentPart, MessageContent, ReasoningEffort, Tool, ToolConfig, ToolName, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{EventSourceStream, WebResponse};
+use crate::webc::{EventSourceStream, WebClient, WebResponse, WebStreamSource};
 use crate::{Error, Headers, Result};
-use crate::{ModelIden, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::{ModelIden, ModelInfo, ServiceTarget};
 use serde_json::{Map, Value, json};
 use value_ext::JsonValueExt;
 
@@ -47,6 +46,10 @@ impl Adapter for OpenAIRespAdapter {
 		.await
 	}
 
+	async fn list_models(service_target: ServiceTarget, web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		OpenAIAdapter::util_list_models(service_target, web_client).await
+	}
+
 	fn get_service_url(model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
 		Self::util_get_service_url(model, service_type, endpoint)
 	}
@@ -68,6 +71,7 @@ impl Adapter for OpenAIRespAdapter {
 		let ServiceTarget { model, auth, endpoint } = target;
 		let (_, model_name) = model.model_name.namespace_and_name();
 		let adapter_kind = model.adapter_kind;
+		check_no_regex_response_format(&model, &chat_options)?;
 
 		// -- api_key
 		let api_key = get_api_key(auth, &model)?;
@@ -128,6 +132,8 @@ impl Adapter for OpenAIRespAdapter {
 		let response_format = if let Some(response_format) = chat_options.response_format() {
 			match response_format {
 				ChatResponseFormat::JsonMode => Some(json!({"type": "json_object"})),
+				// Not supported by OpenAI (rejected above)
+				ChatResponseFormat::Regex(_) => None,
 				ChatResponseFormat::JsonSpec(st_json) => {
 					// "type": "json_schema", "json_schema": {...}
 					let mut schema = st_json.schema.clone();
@@ -225,18 +231,19 @@ impl Adapter for OpenAIRespAdapter {
 			model_iden,
 			provider_model_iden,
 			usage,
+			citations: None,
 			captured_raw_body,
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_sets: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let event_source = EventSourceStream::new(reqwest_builder);
+		let event_source = EventSourceStream::new(stream_source);
 		let openai_stream = OpenAIRespStreamer::new(event_source, model_iden.clone(), options_sets);
-		let chat_stream = ChatStream::from_inter_stream(openai_stream);
+		let chat_stream = ChatStream::from_inter_stream(model_iden.clone(), openai_stream);
 
 		Ok(ChatStreamResponse {
 			model_iden,
@@ -1,12 +1,12 @@
 use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions};
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
 use crate::adapter::openai_resp::resp_types::RespResponse;
-use crate::chat::{ChatOptionsSet, ToolCall};
+use crate::chat::{ChatOptionsSet, ToolCall, ToolCallArgsDelta, ToolCallEnd, ToolCallStart};
 use crate::webc::{Event, EventSourceStream};
 use crate::{Error, ModelIden, Result};
 use serde::Deserialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use value_ext::JsonValueExt;
@@ -21,6 +21,9 @@ pub struct OpenAIRespStreamer {
 	captured_data: StreamerCapturedData,
 
 	in_progress_tool_calls: HashMap<usize, ToolCall>,
+	/// The number of tool calls started (the index of the next `ToolCallStart`)
+	tool_call_count: usize,
+	pending_events: VecDeque<InterStreamEvent>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -68,6 +71,12 @@ enum RespStreamEvent {
 		delta: String,
 	},
 
+	#[serde(rename = "response.function_call_arguments.done")]
+	FunctionCallArgumentsDone {
+		#[serde(default)]
+		output_index: usize,
+	},
+
 	#[serde(rename = "response.completed")]
 	ResponseCompleted { response: RespResponse },
 
@@ -89,6 +98,8 @@ impl OpenAIRespStreamer {
 			options: StreamerOptions::new(model_iden, options_set),
 			captured_data: Default::default(),
 			in_progress_tool_calls: HashMap::new(),
+			tool_call_count: 0,
+			pending_events: VecDeque::new(),
 		}
 	}
 }
@@ -97,6 +108,9 @@ impl futures::Stream for OpenAIRespStreamer {
 	type Item = Result<InterStreamEvent>;
 
 	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if let Some(event) = self.pending_events.pop_front() {
+			return Poll::Ready(Some(Ok(event)));
+		}
 		if self.done {
 			return Poll::Ready(None);
 		}
@@ -128,6 +142,13 @@ impl futures::Stream for OpenAIRespStreamer {
 								let call_id = item.x_get_str("call_id").unwrap_or_default().to_string();
 								let fn_name = item.x_get_str("name").unwrap_or_default().to_string();
 
+								let tool_call_start = ToolCallStart {
+									call_id: call_id.clone(),
+									fn_name: fn_name.clone(),
+									index: self.tool_call_count,
+								};
+								self.tool_call_count += 1;
+
 								let tool_call = ToolCall {
 									call_id,
 									fn_name,
@@ -136,6 +157,8 @@ impl futures::Stream for OpenAIRespStreamer {
 								};
 
 								self.in_progress_tool_calls.insert(output_index, tool_call);
+
+								return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallStart(tool_call_start))));
 							}
 							continue;
 						}
@@ -172,8 +195,23 @@ impl futures::Stream for OpenAIRespStreamer {
 									tool_call.fn_arguments = Value::String(new_args);
 								}
 
+								// Return the ToolCallArgsDelta, then the ToolCallChunk with the accumulated arguments
 								let tool_call_to_send = tool_call.clone();
-								return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallChunk(tool_call_to_send))));
+								let args_delta = ToolCallArgsDelta {
+									call_id: tool_call.call_id.clone(),
+									delta,
+								};
+								self.pending_events
+									.push_back(InterStreamEvent::ToolCallChunk(tool_call_to_send));
+								return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallArgsDelta(args_delta))));
+							}
+							continue;
+						}
+
+						RespStreamEvent::FunctionCallArgumentsDone { output_index } => {
+							if let Some(tool_call) = self.in_progress_tool_calls.get(&output_index) {
+								let call_id = tool_call.call_id.clone();
+								return Poll::Ready(Some(Ok(InterStreamEvent::ToolCallEnd(ToolCallEnd { call_id }))));
 							}
 							continue;
 						}
@@ -206,6 +244,7 @@ impl futures::Stream for OpenAIRespStreamer {
 								captured_reasoning_content: self.captured_data.reasoning_content.take(),
 								captured_tool_calls: self.captured_data.tool_calls.take(),
 								captured_thought_signatures: None,
+								captured_citations: None,
 							};
 
 							return Poll::Ready(Some(Ok(InterStreamEvent::End(inter_stream_end))));
@@ -235,6 +274,7 @@ impl futures::Stream for OpenAIRespStreamer {
 								captured_reasoning_content: self.captured_data.reasoning_content.take(),
 								captured_tool_calls: self.captured_data.tool_calls.take(),
 								captured_thought_signatures: None,
+								captured_citations: None,
 							};
 
 							return Poll::Ready(Some(Ok(InterStreamEvent::End(inter_stream_end))));
@@ -262,6 +302,7 @@ impl futures::Stream for OpenAIRespStreamer {
 							captured_reasoning_content: self.captured_data.reasoning_content.take(),
 							captured_tool_calls: self.captured_data.tool_calls.take(),
 							captured_thought_signatures: None,
+							captured_citations: None,
 						};
 						return Poll::Ready(Some(Ok(InterStreamEvent::End(inter_stream_end))));
 					}
@@ -0,0 +1,263 @@
+use crate::adapter::openai::OpenAIAdapter;
+use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
+use crate::chat::{
+	ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse, Citation, CitationSource, ToolConfig, ToolName,
+	WebSearchConfig,
+};
+use crate::resolver::{AuthData, Endpoint};
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{ModelIden, ModelInfo};
+use crate::{Result, ServiceTarget};
+use serde_json::Value;
+use value_ext::JsonValueExt;
+
+/// The Perplexity Sonar API is compatible with the OpenAI API, plus the search options and results.
+/// - The `ToolName::WebSearch` tool config is mapped to the search options (the search is always on)
+/// - The `search_results` (or `citations` urls) are returned as `ChatResponse.citations` / `StreamEnd.captured_citations`
+pub struct PerplexityAdapter;
+
+const MODELS: &[&str] = &[
+	"sonar",
+	"sonar-pro",
+	"sonar-reasoning",
+	"sonar-reasoning-pro",
+	"sonar-deep-research",
+];
+
+impl PerplexityAdapter {
+	pub const API_KEY_DEFAULT_ENV_NAME: &str = "PERPLEXITY_API_KEY";
+}
+
+impl Adapter for PerplexityAdapter {
+	const DEFAULT_API_KEY_ENV_NAME: Option<&'static str> = Some(Self::API_KEY_DEFAULT_ENV_NAME);
+
+	fn default_endpoint() -> Endpoint {
+		const BASE_URL: &str = "https://api.perplexity.ai/";
+		Endpoint::from_static(BASE_URL)
+	}
+
+	fn default_auth() -> AuthData {
+		match Self::DEFAULT_API_KEY_ENV_NAME {
+			Some(env_name) => AuthData::from_env(env_name),
+			None => AuthData::None,
+		}
+	}
+
+	/// Note: Perplexity does not have a model list endpoint, so it returns the static list above
+	async fn all_model_names(_kind: AdapterKind) -> Result<Vec<String>> {
+		Ok(MODELS.iter().map(|s| s.to_string()).collect())
+	}
+
+	/// Note: Perplexity does not have a model list endpoint, so it returns the static list above (model ids only)
+	async fn list_models(_service_target: ServiceTarget, _web_client: &WebClient) -> Result<Vec<ModelInfo>> {
+		Ok(MODELS.iter().map(|id| ModelInfo::new(AdapterKind::Perplexity, *id)).collect())
+	}
+
+	fn get_service_url(model: &ModelIden, service_type: ServiceType, endpoint: Endpoint) -> Result<String> {
+		OpenAIAdapter::util_get_service_url(model, service_type, endpoint)
+	}
+
+	fn to_web_request_data(
+		target: ServiceTarget,
+		service_type: ServiceType,
+		mut chat_req: ChatRequest,
+		chat_options: ChatOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		// -- Extract the web search config (Perplexity does not accept the built-in tool)
+		let web_search_config = Self::take_web_search_config(&mut chat_req);
+
+		let mut web_request_data =
+			OpenAIAdapter::util_to_web_request_data(target, service_type, chat_req, chat_options, None)?;
+
+		// -- Add the search options
+		if let Some(config) = web_search_config {
+			let payload = &mut web_request_data.payload;
+
+			// allowed domains as is, blocked domains with the `-` prefix
+			let domain_filter: Vec<String> = config
+				.allowed_domains
+				.into_iter()
+				.flatten()
+				.chain(config.blocked_domains.into_iter().flatten().map(|domain| format!("-{domain}")))
+				.collect();
+			if !domain_filter.is_empty() {
+				payload.x_insert("search_domain_filter", domain_filter)?;
+			}
+			if let Some(recency) = config.recency {
+				payload.x_insert("search_recency_filter", recency.as_keyword())?;
+			}
+			if let Some(return_images) = config.return_images {
+				payload.x_insert("return_images", return_images)?;
+			}
+		}
+
+		Ok(web_request_data)
+	}
+
+	fn to_chat_response(
+		model_iden: ModelIden,
+		mut web_response: WebResponse,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatResponse> {
+		let citations = Self::take_citations(&mut web_response.body);
+		let mut chat_response = OpenAIAdapter::to_chat_response(model_iden, web_response, options_set)?;
+		chat_response.citations = citations;
+		Ok(chat_response)
+	}
+
+	/// Note: The citations are captured by the `OpenAIStreamer` (with [`PerplexityAdapter::take_citations`])
+	fn to_chat_stream(
+		model_iden: ModelIden,
+		stream_source: WebStreamSource,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatStreamResponse> {
+		OpenAIAdapter::to_chat_stream(model_iden, stream_source, options_set)
+	}
+
+	fn to_embed_request_data(
+		_service_target: crate::ServiceTarget,
+		_embed_req: crate::embed::EmbedRequest,
+		_options_set: crate::embed::EmbedOptionsSet<'_, '_>,
+	) -> Result<crate::adapter::WebRequestData> {
+		Err(crate::Error::AdapterNotSupported {
+			adapter_kind: crate::adapter::AdapterKind::Perplexity,
+			feature: "embeddings".to_string(),
+		})
+	}
+
+	fn to_embed_response(
+		_model_iden: crate::ModelIden,
+		_web_response: crate::webc::WebResponse,
+		_options_set: crate::embed::EmbedOptionsSet<'_, '_>,
+	) -> Result<crate::embed::EmbedResponse> {
+		Err(crate::Error::AdapterNotSupported {
+			adapter_kind: crate::adapter::AdapterKind::Perplexity,
+			feature: "embeddings".to_string(),
+		})
+	}
+}
+
+// region:    --- Support
+
+impl PerplexityAdapter {
+	/// Remove the `ToolName::WebSearch` tools from the request, and return the eventual config.
+	fn take_web_search_config(chat_req: &mut ChatRequest) -> Option<WebSearchConfig> {
+		let tools = chat_req.tools.take()?;
+
+		let mut web_search_config: Option<WebSearchConfig> = None;
+		let mut custom_tools = Vec::new();
+		for tool in tools {
+			if matches!(tool.name, ToolName::WebSearch) {
+				let config = match tool.config {
+					Some(ToolConfig::WebSearch(config)) => config,
+					_ => WebSearchConfig::default(),
+				};
+				web_search_config = Some(config);
+			} else {
+				custom_tools.push(tool);
+			}
+		}
+
+		if !custom_tools.is_empty() {
+			chat_req.tools = Some(custom_tools);
+		}
+
+		web_search_config
+	}
+
+	/// Take the citations from a response body (or stream chunk).
+	/// - Uses the `search_results` (`title`, `url`, `snippet`) when present
+	/// - Otherwise, the `citations` urls
+	///
+	/// NOTE: The source `id` is the 1-based index used by the `[1]` like markers in the response text.
+	pub(in crate::adapter::adapters) fn take_citations(body: &mut Value) -> Option<Vec<Citation>> {
+		let search_results = body.x_remove::<Vec<Value>>("search_results").ok();
+		let urls = body.x_remove::<Vec<String>>("citations").ok();
+
+		let sources: Vec<CitationSource> = match (search_results, urls) {
+			(Some(search_results), _) if !search_results.is_empty() => search_results
+				.into_iter()
+				.enumerate()
+				.map(|(idx, mut search_result)| CitationSource {
+					id: Some((idx + 1).to_string()),
+					url: search_result.x_take("url").ok(),
+					title: search_result.x_take("title").ok(),
+					snippet: search_result.x_take("snippet").ok(),
+				})
+				.collect(),
+			(_, Some(urls)) => urls
+				.into_iter()
+				.enumerate()
+				.map(|(idx, url)| CitationSource {
+					id: Some((idx + 1).to_string()),
+					url: Some(url),
+					..Default::default()
+				})
+				.collect(),
+			_ => return None,
+		};
+
+		if sources.is_empty() {
+			return None;
+		}
+
+		// One citation per source, as Perplexity does not return the cited spans
+		let citations = sources
+			.into_iter()
+			.map(|source| Citation {
+				sources: vec![source],
+				..Default::default()
+			})
+			.collect();
+
+		Some(citations)
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_perplexity_take_citations_from_search_results() {
+		let mut body = json!({
+			"citations": ["https://a.com", "https://b.com"],
+			"search_results": [
+				{"title": "A", "url": "https://a.com", "date": "2025-01-01"},
+				{"title": "B", "url": "https://b.com", "snippet": "b snippet"}
+			]
+		});
+
+		let citations = PerplexityAdapter::take_citations(&mut body).unwrap();
+
+		assert_eq!(citations.len(), 2);
+		let source = &citations[1].sources[0];
+		assert_eq!(source.id.as_deref(), Some("2"));
+		assert_eq!(source.url.as_deref(), Some("https://b.com"));
+		assert_eq!(source.title.as_deref(), Some("B"));
+		assert_eq!(source.snippet.as_deref(), Some("b snippet"));
+		assert!(body.get("citations").is_none());
+	}
+
+	#[tokio::test]
+	async fn test_perplexity_embed_not_supported() {
+		let client = crate::Client::default();
+
+		let res = client.embed("perplexity::sonar", "Hello", None).await;
+
+		assert!(matches!(
+			res,
+			Err(crate::Error::AdapterNotSupported {
+				adapter_kind: AdapterKind::Perplexity,
+				..
+			})
+		));
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,11 @@
+//! API Documentation:     <https://docs.perplexity.ai/api-reference/chat-completions-post>
+//! Model Names:           <https://docs.perplexity.ai/getting-started/models>
+//! Pricing:               <https://docs.perplexity.ai/getting-started/pricing>
+
+// region:    --- Modules
+
+mod adapter_impl;
+
+pub use adapter_impl::*;
+
+// endregion: --- Modules
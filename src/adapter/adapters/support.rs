@@ -2,9 +2,11 @@
 //! It should be private to the `crate::adapter::adapters` module.
 
 use crate::ModelIden;
-use crate::chat::{ChatOptionsSet, Usage};
+use crate::adapter::inter_stream::InterStreamEvent;
+use crate::chat::{ChatOptionsSet, ChatResponseFormat, ToolCall, ToolCallArgsDelta, ToolCallEnd, ToolCallStart, Usage};
 use crate::resolver::AuthData;
 use crate::{Error, Result};
+use serde_json::Value;
 
 pub fn get_api_key(auth: AuthData, model: &ModelIden) -> Result<String> {
 	auth.single_key_value().map_err(|resolver_error| Error::Resolver {
@@ -13,6 +15,17 @@ pub fn get_api_key(auth: AuthData, model: &ModelIden) -> Result<String> {
 	})
 }
 
+/// Returns the `AdapterNotSupported` error for a `ChatResponseFormat::Regex` (only supported by the HuggingFace TGI grammar).
+pub fn check_no_regex_response_format(model: &ModelIden, options_set: &ChatOptionsSet<'_, '_>) -> Result<()> {
+	match options_set.response_format() {
+		Some(ChatResponseFormat::Regex(_)) => Err(Error::AdapterNotSupported {
+			adapter_kind: model.adapter_kind,
+			feature: "regex response format".to_string(),
+		}),
+		_ => Ok(()),
+	}
+}
+
 // region:    --- StreamerChatOptions
 
 #[derive(Debug)]
@@ -50,3 +63,32 @@ pub struct StreamerCapturedData {
 }
 
 // endregion: --- Streamer Captured Data
+
+// region:    --- Tool Call Events
+
+/// The events of a tool call received at once: the `ToolCallStart`, the whole arguments as one `ToolCallArgsDelta`,
+/// the `ToolCallEnd`, and the complete `ToolCallChunk`.
+pub fn whole_tool_call_events(index: usize, tool_call: ToolCall) -> [InterStreamEvent; 4] {
+	let delta = match &tool_call.fn_arguments {
+		Value::String(arguments) => arguments.clone(),
+		arguments => arguments.to_string(),
+	};
+
+	[
+		InterStreamEvent::ToolCallStart(ToolCallStart {
+			call_id: tool_call.call_id.clone(),
+			fn_name: tool_call.fn_name.clone(),
+			index,
+		}),
+		InterStreamEvent::ToolCallArgsDelta(ToolCallArgsDelta {
+			call_id: tool_call.call_id.clone(),
+			delta,
+		}),
+		InterStreamEvent::ToolCallEnd(ToolCallEnd {
+			call_id: tool_call.call_id.clone(),
+		}),
+		InterStreamEvent::ToolCallChunk(tool_call),
+	]
+}
+
+// endregion: --- Tool Call Events
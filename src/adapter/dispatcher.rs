@@ -1,5 +1,6 @@
 use super::groq::GroqAdapter;
 use crate::adapter::adapters::mimo::MimoAdapter;
+use crate::adapter::adapters::mock::MockAdapter;
 use crate::adapter::adapters::together::TogetherAdapter;
 use crate::adapter::adapters::zai::ZaiAdapter;
 use crate::adapter::aliyun::AliyunAdapter;
@@ -9,19 +10,21 @@ use crate::adapter::cohere::CohereAdapter;
 use crate::adapter::deepseek::DeepSeekAdapter;
 use crate::adapter::fireworks::FireworksAdapter;
 use crate::adapter::gemini::GeminiAdapter;
+use crate::adapter::huggingface::HuggingFaceAdapter;
 use crate::adapter::nebius::NebiusAdapter;
 use crate::adapter::ollama::OllamaAdapter;
 use crate::adapter::openai::OpenAIAdapter;
+use crate::adapter::openai_compat::{CompatProfile, OpenAICompatAdapter};
 use crate::adapter::openai_resp::OpenAIRespAdapter;
+use crate::adapter::perplexity::PerplexityAdapter;
 use crate::adapter::xai::XaiAdapter;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
-use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
+use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse, TokenCount};
 use crate::embed::{EmbedOptionsSet, EmbedRequest, EmbedResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
-use crate::{Error, ModelIden};
+use crate::webc::{WebClient, WebResponse, WebStreamSource};
+use crate::{Error, ModelIden, ModelInfo};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
 
 /// A construct that allows dispatching calls to the Adapters.
 ///
@@ -48,7 +51,11 @@ impl AdapterDispatcher {
 			AdapterKind::BigModel => BigModelAdapter::default_endpoint(),
 			AdapterKind::Aliyun => AliyunAdapter::default_endpoint(),
 			AdapterKind::Cohere => CohereAdapter::default_endpoint(),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::default_endpoint(),
+			AdapterKind::Perplexity => PerplexityAdapter::default_endpoint(),
 			AdapterKind::Ollama => OllamaAdapter::default_endpoint(),
+			AdapterKind::Mock => MockAdapter::default_endpoint(),
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::default_endpoint(),
 		}
 	}
 
@@ -69,7 +76,11 @@ impl AdapterDispatcher {
 			AdapterKind::BigModel => BigModelAdapter::default_auth(),
 			AdapterKind::Aliyun => AliyunAdapter::default_auth(),
 			AdapterKind::Cohere => CohereAdapter::default_auth(),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::default_auth(),
+			AdapterKind::Perplexity => PerplexityAdapter::default_auth(),
 			AdapterKind::Ollama => OllamaAdapter::default_auth(),
+			AdapterKind::Mock => MockAdapter::default_auth(),
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::default_auth(),
 		}
 	}
 
@@ -90,7 +101,100 @@ impl AdapterDispatcher {
 			AdapterKind::BigModel => BigModelAdapter::all_model_names(kind).await,
 			AdapterKind::Aliyun => AliyunAdapter::all_model_names(kind).await,
 			AdapterKind::Cohere => CohereAdapter::all_model_names(kind).await,
+			AdapterKind::HuggingFace => HuggingFaceAdapter::all_model_names(kind).await,
+			AdapterKind::Perplexity => PerplexityAdapter::all_model_names(kind).await,
 			AdapterKind::Ollama => OllamaAdapter::all_model_names(kind).await,
+			AdapterKind::Mock => MockAdapter::all_model_names(kind).await,
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::all_model_names(kind).await,
+		}
+	}
+
+	/// Note: The `compat_profile` is the client `CompatProfile` of the model (for `AdapterKind::OpenAICompat`).
+	pub async fn list_models(
+		service_target: ServiceTarget,
+		web_client: &WebClient,
+		compat_profile: Option<&CompatProfile>,
+	) -> Result<Vec<ModelInfo>> {
+		match service_target.model.adapter_kind {
+			AdapterKind::OpenAI => OpenAIAdapter::list_models(service_target, web_client).await,
+			AdapterKind::OpenAIResp => OpenAIRespAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Gemini => GeminiAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Anthropic => AnthropicAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Fireworks => FireworksAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Together => TogetherAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Groq => GroqAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Mimo => MimoAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Nebius => NebiusAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Xai => XaiAdapter::list_models(service_target, web_client).await,
+			AdapterKind::DeepSeek => DeepSeekAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Zai => ZaiAdapter::list_models(service_target, web_client).await,
+			AdapterKind::BigModel => BigModelAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Aliyun => AliyunAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Cohere => CohereAdapter::list_models(service_target, web_client).await,
+			AdapterKind::HuggingFace => HuggingFaceAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Perplexity => PerplexityAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Ollama => OllamaAdapter::list_models(service_target, web_client).await,
+			AdapterKind::Mock => MockAdapter::list_models(service_target, web_client).await,
+			AdapterKind::OpenAICompat => {
+				let profile = compat_profile.cloned().unwrap_or_default();
+				OpenAICompatAdapter::list_models_with_profile(service_target, &profile, web_client).await
+			}
+		}
+	}
+
+	pub fn to_count_tokens_request_data(
+		target: ServiceTarget,
+		chat_req: ChatRequest,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<Option<WebRequestData>> {
+		match target.model.adapter_kind {
+			AdapterKind::OpenAI => OpenAIAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::OpenAIResp => OpenAIRespAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Gemini => GeminiAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Anthropic => AnthropicAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Fireworks => FireworksAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Together => TogetherAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Groq => GroqAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Mimo => MimoAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Nebius => NebiusAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Xai => XaiAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::DeepSeek => DeepSeekAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Zai => ZaiAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::BigModel => BigModelAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Aliyun => AliyunAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Cohere => CohereAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Perplexity => PerplexityAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Ollama => OllamaAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::Mock => MockAdapter::to_count_tokens_request_data(target, chat_req, options_set),
+			AdapterKind::OpenAICompat => {
+				OpenAICompatAdapter::to_count_tokens_request_data(target, chat_req, options_set)
+			}
+		}
+	}
+
+	pub fn to_token_count(model_iden: ModelIden, web_response: WebResponse) -> Result<TokenCount> {
+		match model_iden.adapter_kind {
+			AdapterKind::OpenAI => OpenAIAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::OpenAIResp => OpenAIRespAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Gemini => GeminiAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Anthropic => AnthropicAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Fireworks => FireworksAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Together => TogetherAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Groq => GroqAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Mimo => MimoAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Nebius => NebiusAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Xai => XaiAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::DeepSeek => DeepSeekAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Zai => ZaiAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::BigModel => BigModelAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Aliyun => AliyunAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Cohere => CohereAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Perplexity => PerplexityAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Ollama => OllamaAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::Mock => MockAdapter::to_token_count(model_iden, web_response),
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::to_token_count(model_iden, web_response),
 		}
 	}
 
@@ -111,7 +215,11 @@ impl AdapterDispatcher {
 			AdapterKind::BigModel => BigModelAdapter::get_service_url(model, service_type, endpoint),
 			AdapterKind::Aliyun => AliyunAdapter::get_service_url(model, service_type, endpoint),
 			AdapterKind::Cohere => CohereAdapter::get_service_url(model, service_type, endpoint),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::get_service_url(model, service_type, endpoint),
+			AdapterKind::Perplexity => PerplexityAdapter::get_service_url(model, service_type, endpoint),
 			AdapterKind::Ollama => OllamaAdapter::get_service_url(model, service_type, endpoint),
+			AdapterKind::Mock => MockAdapter::get_service_url(model, service_type, endpoint),
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::get_service_url(model, service_type, endpoint),
 		}
 	}
 
@@ -144,7 +252,17 @@ impl AdapterDispatcher {
 			AdapterKind::BigModel => BigModelAdapter::to_web_request_data(target, service_type, chat_req, options_set),
 			AdapterKind::Aliyun => AliyunAdapter::to_web_request_data(target, service_type, chat_req, options_set),
 			AdapterKind::Cohere => CohereAdapter::to_web_request_data(target, service_type, chat_req, options_set),
+			AdapterKind::HuggingFace => {
+				HuggingFaceAdapter::to_web_request_data(target, service_type, chat_req, options_set)
+			}
+			AdapterKind::Perplexity => {
+				PerplexityAdapter::to_web_request_data(target, service_type, chat_req, options_set)
+			}
 			AdapterKind::Ollama => OllamaAdapter::to_web_request_data(target, service_type, chat_req, options_set),
+			AdapterKind::Mock => MockAdapter::to_web_request_data(target, service_type, chat_req, options_set),
+			AdapterKind::OpenAICompat => {
+				OpenAICompatAdapter::to_web_request_data(target, service_type, chat_req, options_set)
+			}
 		}
 	}
 
@@ -169,32 +287,40 @@ impl AdapterDispatcher {
 			AdapterKind::BigModel => BigModelAdapter::to_chat_response(model_iden, web_response, options_set),
 			AdapterKind::Aliyun => AliyunAdapter::to_chat_response(model_iden, web_response, options_set),
 			AdapterKind::Cohere => CohereAdapter::to_chat_response(model_iden, web_response, options_set),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::to_chat_response(model_iden, web_response, options_set),
+			AdapterKind::Perplexity => PerplexityAdapter::to_chat_response(model_iden, web_response, options_set),
 			AdapterKind::Ollama => OllamaAdapter::to_chat_response(model_iden, web_response, options_set),
+			AdapterKind::Mock => MockAdapter::to_chat_response(model_iden, web_response, options_set),
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::to_chat_response(model_iden, web_response, options_set),
 		}
 	}
 
 	pub fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_source: WebStreamSource,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
 		match model_iden.adapter_kind {
-			AdapterKind::OpenAI => OpenAIAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::OpenAIResp => OpenAIRespAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Gemini => GeminiAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Anthropic => AnthropicAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Fireworks => FireworksAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Together => TogetherAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Groq => GroqAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Mimo => MimoAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Nebius => NebiusAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Xai => XaiAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::DeepSeek => DeepSeekAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Zai => ZaiAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::BigModel => BigModelAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Aliyun => AliyunAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Cohere => CohereAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Ollama => OllamaAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
+			AdapterKind::OpenAI => OpenAIAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::OpenAIResp => OpenAIRespAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Gemini => GeminiAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Anthropic => AnthropicAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Fireworks => FireworksAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Together => TogetherAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Groq => GroqAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Mimo => MimoAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Nebius => NebiusAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Xai => XaiAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::DeepSeek => DeepSeekAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Zai => ZaiAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::BigModel => BigModelAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Aliyun => AliyunAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Cohere => CohereAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Perplexity => PerplexityAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Ollama => OllamaAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::Mock => MockAdapter::to_chat_stream(model_iden, stream_source, options_set),
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::to_chat_stream(model_iden, stream_source, options_set),
 		}
 	}
 
@@ -223,7 +349,11 @@ impl AdapterDispatcher {
 			AdapterKind::BigModel => BigModelAdapter::to_embed_request_data(target, embed_req, options_set),
 			AdapterKind::Aliyun => AliyunAdapter::to_embed_request_data(target, embed_req, options_set),
 			AdapterKind::Cohere => CohereAdapter::to_embed_request_data(target, embed_req, options_set),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::to_embed_request_data(target, embed_req, options_set),
+			AdapterKind::Perplexity => PerplexityAdapter::to_embed_request_data(target, embed_req, options_set),
 			AdapterKind::Ollama => OllamaAdapter::to_embed_request_data(target, embed_req, options_set),
+			AdapterKind::Mock => MockAdapter::to_embed_request_data(target, embed_req, options_set),
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::to_embed_request_data(target, embed_req, options_set),
 		}
 	}
 
@@ -251,7 +381,11 @@ impl AdapterDispatcher {
 			AdapterKind::BigModel => BigModelAdapter::to_embed_response(model_iden, web_response, options_set),
 			AdapterKind::Aliyun => AliyunAdapter::to_embed_response(model_iden, web_response, options_set),
 			AdapterKind::Cohere => CohereAdapter::to_embed_response(model_iden, web_response, options_set),
+			AdapterKind::HuggingFace => HuggingFaceAdapter::to_embed_response(model_iden, web_response, options_set),
+			AdapterKind::Perplexity => PerplexityAdapter::to_embed_response(model_iden, web_response, options_set),
 			AdapterKind::Ollama => OllamaAdapter::to_embed_response(model_iden, web_response, options_set),
+			AdapterKind::Mock => MockAdapter::to_embed_response(model_iden, web_response, options_set),
+			AdapterKind::OpenAICompat => OpenAICompatAdapter::to_embed_response(model_iden, web_response, options_set),
 		}
 	}
 }
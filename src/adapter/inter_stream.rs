@@ -5,7 +5,7 @@
 //!
 //! NOTE: This might be removed at some point as it may not be needed, and we could go directly to the GenAI stream.
 
-use crate::chat::Usage;
+use crate::chat::{Citation, ToolCallArgsDelta, ToolCallEnd, ToolCallStart, Usage};
 
 #[derive(Debug, Default)]
 pub struct InterStreamEnd {
@@ -23,6 +23,9 @@ pub struct InterStreamEnd {
 
 	// When `ChatOptions..capture_thought_signatures == true` (implied or explicit)
 	pub captured_thought_signatures: Option<Vec<String>>,
+
+	// When `ChatOptions..capture_content == true` and the provider returns citations
+	pub captured_citations: Option<Vec<Citation>>,
 }
 
 /// Intermediary StreamEvent
@@ -32,6 +35,11 @@ pub enum InterStreamEvent {
 	Chunk(String),
 	ReasoningChunk(String),
 	ThoughtSignatureChunk(String),
+	ToolCallStart(ToolCallStart),
+	ToolCallArgsDelta(ToolCallArgsDelta),
+	ToolCallEnd(ToolCallEnd),
 	ToolCallChunk(crate::chat::ToolCall),
+	/// The usage so far (sent by some providers during the stream)
+	Usage(Usage),
 	End(InterStreamEnd),
 }
@@ -5,7 +5,8 @@
 //! Notes:
 //! - All `Adapter` trait methods take the `AdapterKind` as an argument, and for now, the `Adapter` trait functions
 //!   are all static (i.e., no `&self`). This reduces state management and ensures that all states are passed as arguments.
-//! - Only `AdapterKind` from `AdapterConfig` is publicly exported.
+//! - Only `AdapterKind` from `AdapterConfig` is publicly exported (plus the `CompatProfile` types for `AdapterKind::OpenAICompat`,
+//!   and the Ollama management types for the `Client::ollama_*` APIs).
 
 // region:    --- Modules
 
@@ -18,9 +19,14 @@ mod dispatcher;
 use adapters::*;
 
 pub(crate) use adapter_types::*;
+pub(crate) use adapters::ollama::OllamaAdapter;
 pub(crate) use dispatcher::*;
 
 pub use adapter_kind::*;
+pub use adapter_types::WebRequestData;
+pub use adapters::mock::{MockCall, MockScript, MockStreamItem};
+pub use adapters::ollama::{OllamaModelInfo, OllamaPullProgress, OllamaPullStream};
+pub use adapters::openai_compat::{CompatAuthStyle, CompatParam, CompatProfile, MaxTokensField};
 
 // -- Crate modules
 pub(crate) mod inter_stream;
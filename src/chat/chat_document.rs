@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// A document the model should ground its answer on (e.g., retrieval results for RAG).
+///
+/// > Note: Currently used by the Cohere adapter (`documents`), ignored by the other adapters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatDocument {
+	/// Optional id, returned in the citation sources (generated by the provider when absent).
+	pub id: Option<String>,
+
+	/// Optional title of the document.
+	pub title: Option<String>,
+
+	/// The text content of the document.
+	pub text: String,
+}
+
+/// Constructors
+impl ChatDocument {
+	/// Create a document from its text content.
+	pub fn new(text: impl Into<String>) -> Self {
+		Self {
+			id: None,
+			title: None,
+			text: text.into(),
+		}
+	}
+}
+
+/// Chainable Setters
+impl ChatDocument {
+	/// Set the document id.
+	pub fn with_id(mut self, id: impl Into<String>) -> Self {
+		self.id = Some(id.into());
+		self
+	}
+
+	/// Set the document title.
+	pub fn with_title(mut self, title: impl Into<String>) -> Self {
+		self.title = Some(title.into());
+		self
+	}
+}
+
+impl From<&str> for ChatDocument {
+	fn from(text: &str) -> Self {
+		Self::new(text)
+	}
+}
+
+impl From<String> for ChatDocument {
+	fn from(text: String) -> Self {
+		Self::new(text)
+	}
+}
@@ -1,5 +1,4 @@
 use crate::chat::{ContentPart, MessageContent, ToolCall, ToolResponse};
-use derive_more::From;
 use serde::{Deserialize, Serialize};
 
 /// A single chat message (system, user, assistant, or tool).
@@ -68,6 +67,17 @@ impl ChatMessage {
 		self
 	}
 
+	/// Pins (or unpins) this message, so it is never dropped by a `ContextPolicy`.
+	pub fn with_pinned(mut self, pinned: bool) -> Self {
+		self.options.get_or_insert_with(MessageOptions::default).pinned = Some(pinned);
+		self
+	}
+
+	/// Returns true if this message is pinned (see `with_pinned`).
+	pub fn is_pinned(&self) -> bool {
+		self.options.as_ref().and_then(|o| o.pinned).unwrap_or(false)
+	}
+
 	/// Convenience: build an assistant message that contains an optional list
 	/// of thought signatures followed by tool calls. Useful for providers
 	/// (e.g., Gemini 3) that require the thought signature to appear before
@@ -80,12 +90,24 @@ impl ChatMessage {
 }
 // region:    --- MessageOptions
 
-#[derive(Debug, Clone, Serialize, Deserialize, From)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 /// Per-message options (e.g., cache control).
 pub struct MessageOptions {
-	#[from]
 	/// Per-provider cache behavior hint.
 	pub cache_control: Option<CacheControl>,
+
+	/// Pinned messages are never dropped by a `ContextPolicy`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub pinned: Option<bool>,
+}
+
+impl From<Option<CacheControl>> for MessageOptions {
+	fn from(cache_control: Option<CacheControl>) -> Self {
+		Self {
+			cache_control,
+			pinned: None,
+		}
+	}
 }
 
 /// Cache control for prompt caching.
@@ -122,6 +144,7 @@ impl From<CacheControl> for MessageOptions {
 	fn from(cache_control: CacheControl) -> Self {
 		Self {
 			cache_control: Some(cache_control),
+			pinned: None,
 		}
 	}
 }
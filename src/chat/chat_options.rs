@@ -6,10 +6,14 @@
 //! Note 2: Kept separate from `ChatRequest` for easier reuse and composition.
 
 use crate::Headers;
+use crate::UsageTracker;
+use crate::adapter::CompatProfile;
+use crate::chat::ContextPolicy;
 use crate::chat::chat_req_response_format::ChatResponseFormat;
 use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::ops::Deref;
+use std::time::Duration;
 
 /// Options considered by all `Client::exec_*` chat calls.
 ///
@@ -66,8 +70,34 @@ pub struct ChatOptions {
 	/// Service tier preference (OpenAI-specific, for flex processing).
 	pub service_tier: Option<ServiceTier>,
 
+	/// Ollama runtime options (Ollama-specific, e.g., `num_ctx`, `keep_alive`).
+	pub ollama_options: Option<OllamaOptions>,
+
+	/// Validate the request against the model capabilities (see `ModelCapabilitiesRegistry`) before sending it.
+	pub capability_check: Option<bool>,
+
+	/// Trims the request messages before sending it (see `ContextPolicy`).
+	#[serde(skip)]
+	pub context_policy: Option<ContextPolicy>,
+
+	/// The label (e.g., tenant or feature name) the usage is recorded under in the `UsageTracker`.
+	pub usage_label: Option<String>,
+
+	/// The `UsageTracker` for this request (takes precedence over the client one).
+	#[serde(skip)]
+	pub usage_tracker: Option<UsageTracker>,
+
 	/// Additional HTTP headers to include with the request.
 	pub extra_headers: Option<Headers>,
+
+	/// The total duration of the request (for streams, until the stream end).
+	pub timeout: Option<Duration>,
+
+	/// For streams, the maximum wait for the first content event (text, reasoning, or tool call).
+	pub first_token_timeout: Option<Duration>,
+
+	/// For streams, the maximum wait between two events after the first content event.
+	pub idle_timeout: Option<Duration>,
 }
 
 /// Chainable Setters
@@ -162,12 +192,60 @@ impl ChatOptions {
 		self
 	}
 
+	/// Sets the Ollama runtime options (Ollama-specific).
+	pub fn with_ollama_options(mut self, value: OllamaOptions) -> Self {
+		self.ollama_options = Some(value);
+		self
+	}
+
+	/// Enables the pre-flight check of the request against the model capabilities.
+	pub fn with_capability_check(mut self, value: bool) -> Self {
+		self.capability_check = Some(value);
+		self
+	}
+
+	/// Sets the context policy, trimming the request messages before sending it.
+	pub fn with_context_policy(mut self, value: ContextPolicy) -> Self {
+		self.context_policy = Some(value);
+		self
+	}
+
+	/// Sets the label the usage is recorded under in the `UsageTracker`.
+	pub fn with_usage_label(mut self, value: impl Into<String>) -> Self {
+		self.usage_label = Some(value.into());
+		self
+	}
+
+	/// Sets the `UsageTracker` for the request(s) using these options.
+	pub fn with_usage_tracker(mut self, value: UsageTracker) -> Self {
+		self.usage_tracker = Some(value);
+		self
+	}
+
 	/// Adds extra HTTP headers.
 	pub fn with_extra_headers(mut self, headers: impl Into<Headers>) -> Self {
 		self.extra_headers = Some(headers.into());
 		self
 	}
 
+	/// Sets the total timeout of the request (fails with `Error::Timeout`).
+	pub fn with_timeout(mut self, value: Duration) -> Self {
+		self.timeout = Some(value);
+		self
+	}
+
+	/// Sets the stream first content event timeout (fails with `Error::Timeout`).
+	pub fn with_first_token_timeout(mut self, value: Duration) -> Self {
+		self.first_token_timeout = Some(value);
+		self
+	}
+
+	/// Sets the stream idle timeout between two events (fails with `Error::Timeout`).
+	pub fn with_idle_timeout(mut self, value: Duration) -> Self {
+		self.idle_timeout = Some(value);
+		self
+	}
+
 	// -- Deprecated
 
 	/// Deprecated: use `with_response_format(ChatResponseFormat::JsonMode)`.
@@ -416,6 +494,85 @@ impl std::str::FromStr for ServiceTier {
 
 // endregion: --- ServiceTier
 
+// region:    --- OllamaOptions
+
+/// Ollama runtime options, sent as the Ollama `options` (and top-level `keep_alive`) of the request.
+///
+/// Note: The common options (`temperature`, `top_p`, `max_tokens` as `num_predict`, `seed`, `stop_sequences`)
+///       are set from the `ChatOptions` and do not need to be repeated here.
+///
+/// API DOC: <https://github.com/ollama/ollama/blob/main/docs/modelfile.md#valid-parameters-and-values>
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OllamaOptions {
+	/// Size of the context window (in tokens).
+	pub num_ctx: Option<u32>,
+
+	/// Number of layers to offload to the GPU(s) (`0` for CPU only).
+	pub num_gpu: Option<i32>,
+
+	/// Penalty for the repetitions (e.g., `1.1`).
+	pub repeat_penalty: Option<f64>,
+
+	/// Mirostat sampling (`0` disabled, `1` Mirostat, `2` Mirostat 2.0).
+	pub mirostat: Option<u8>,
+
+	/// Mirostat learning rate (`mirostat_eta`).
+	pub mirostat_eta: Option<f64>,
+
+	/// Mirostat target entropy (`mirostat_tau`).
+	pub mirostat_tau: Option<f64>,
+
+	/// How long the model stays loaded after the request (e.g., `"10m"`, `"0"` to unload, `"-1m"` to keep it loaded).
+	pub keep_alive: Option<String>,
+}
+
+/// Chainable Setters
+impl OllamaOptions {
+	/// Sets the context window size.
+	pub fn with_num_ctx(mut self, value: u32) -> Self {
+		self.num_ctx = Some(value);
+		self
+	}
+
+	/// Sets the number of GPU layers.
+	pub fn with_num_gpu(mut self, value: i32) -> Self {
+		self.num_gpu = Some(value);
+		self
+	}
+
+	/// Sets the repeat penalty.
+	pub fn with_repeat_penalty(mut self, value: f64) -> Self {
+		self.repeat_penalty = Some(value);
+		self
+	}
+
+	/// Sets the Mirostat mode (`0`, `1`, or `2`).
+	pub fn with_mirostat(mut self, value: u8) -> Self {
+		self.mirostat = Some(value);
+		self
+	}
+
+	/// Sets the Mirostat learning rate.
+	pub fn with_mirostat_eta(mut self, value: f64) -> Self {
+		self.mirostat_eta = Some(value);
+		self
+	}
+
+	/// Sets the Mirostat target entropy.
+	pub fn with_mirostat_tau(mut self, value: f64) -> Self {
+		self.mirostat_tau = Some(value);
+		self
+	}
+
+	/// Sets the keep alive duration (e.g., `"10m"`, `"0"`).
+	pub fn with_keep_alive(mut self, value: impl Into<String>) -> Self {
+		self.keep_alive = Some(value.into());
+		self
+	}
+}
+
+// endregion: --- OllamaOptions
+
 // region:    --- ChatOptionsSet
 
 /// This is an internal crate struct to resolve the ChatOptions value in a cascading manner.
@@ -425,6 +582,8 @@ impl std::str::FromStr for ServiceTier {
 pub(crate) struct ChatOptionsSet<'a, 'b> {
 	client: Option<&'a ChatOptions>,
 	chat: Option<&'b ChatOptions>,
+	/// The client `CompatProfile` of the model namespace (for `AdapterKind::OpenAICompat`).
+	compat_profile: Option<&'a CompatProfile>,
 }
 
 impl<'a, 'b> ChatOptionsSet<'a, 'b> {
@@ -436,9 +595,17 @@ impl<'a, 'b> ChatOptionsSet<'a, 'b> {
 		self.chat = options;
 		self
 	}
+	pub fn with_compat_profile(mut self, compat_profile: Option<&'a CompatProfile>) -> Self {
+		self.compat_profile = compat_profile;
+		self
+	}
 }
 
 impl ChatOptionsSet<'_, '_> {
+	pub fn compat_profile(&self) -> Option<&CompatProfile> {
+		self.compat_profile
+	}
+
 	pub fn temperature(&self) -> Option<f64> {
 		self.chat
 			.and_then(|chat| chat.temperature)
@@ -530,6 +697,36 @@ impl ChatOptionsSet<'_, '_> {
 			.or_else(|| self.client.and_then(|client| client.service_tier.as_ref()))
 	}
 
+	pub fn ollama_options(&self) -> Option<&OllamaOptions> {
+		self.chat
+			.and_then(|chat| chat.ollama_options.as_ref())
+			.or_else(|| self.client.and_then(|client| client.ollama_options.as_ref()))
+	}
+
+	pub fn capability_check(&self) -> Option<bool> {
+		self.chat
+			.and_then(|chat| chat.capability_check)
+			.or_else(|| self.client.and_then(|client| client.capability_check))
+	}
+
+	pub fn context_policy(&self) -> Option<&ContextPolicy> {
+		self.chat
+			.and_then(|chat| chat.context_policy.as_ref())
+			.or_else(|| self.client.and_then(|client| client.context_policy.as_ref()))
+	}
+
+	pub fn usage_label(&self) -> Option<&str> {
+		self.chat
+			.and_then(|chat| chat.usage_label.as_deref())
+			.or_else(|| self.client.and_then(|client| client.usage_label.as_deref()))
+	}
+
+	pub fn usage_tracker(&self) -> Option<&UsageTracker> {
+		self.chat
+			.and_then(|chat| chat.usage_tracker.as_ref())
+			.or_else(|| self.client.and_then(|client| client.usage_tracker.as_ref()))
+	}
+
 	#[allow(unused)]
 	pub fn extra_headers(&self) -> Option<&Headers> {
 		self.chat
@@ -537,6 +734,24 @@ impl ChatOptionsSet<'_, '_> {
 			.or_else(|| self.client.and_then(|client| client.extra_headers.as_ref()))
 	}
 
+	pub fn timeout(&self) -> Option<Duration> {
+		self.chat
+			.and_then(|chat| chat.timeout)
+			.or_else(|| self.client.and_then(|client| client.timeout))
+	}
+
+	pub fn first_token_timeout(&self) -> Option<Duration> {
+		self.chat
+			.and_then(|chat| chat.first_token_timeout)
+			.or_else(|| self.client.and_then(|client| client.first_token_timeout))
+	}
+
+	pub fn idle_timeout(&self) -> Option<Duration> {
+		self.chat
+			.and_then(|chat| chat.idle_timeout)
+			.or_else(|| self.client.and_then(|client| client.idle_timeout))
+	}
+
 	/// Returns true only if there is a ChatResponseFormat::JsonMode
 	#[deprecated(note = "Use .response_format()")]
 	#[allow(unused)]
@@ -547,6 +762,36 @@ impl ChatOptionsSet<'_, '_> {
 			_ => Some(false),
 		}
 	}
+
+	/// The resolved options, as a single `ChatOptions` (e.g., for the `MockCall` inspection).
+	pub fn to_chat_options(&self) -> ChatOptions {
+		ChatOptions {
+			temperature: self.temperature(),
+			max_tokens: self.max_tokens(),
+			top_p: self.top_p(),
+			stop_sequences: self.stop_sequences().to_vec(),
+			capture_usage: self.capture_usage(),
+			capture_content: self.capture_content(),
+			capture_reasoning_content: self.capture_reasoning_content(),
+			capture_tool_calls: self.capture_tool_calls(),
+			capture_raw_body: self.capture_raw_body(),
+			response_format: self.response_format().cloned(),
+			normalize_reasoning_content: self.normalize_reasoning_content(),
+			reasoning_effort: self.reasoning_effort().cloned(),
+			verbosity: self.verbosity().cloned(),
+			seed: self.seed(),
+			service_tier: self.service_tier().cloned(),
+			ollama_options: self.ollama_options().cloned(),
+			capability_check: self.capability_check(),
+			context_policy: self.context_policy().cloned(),
+			usage_label: self.usage_label().map(|label| label.to_string()),
+			usage_tracker: self.usage_tracker().cloned(),
+			extra_headers: self.extra_headers().cloned(),
+			timeout: self.timeout(),
+			first_token_timeout: self.first_token_timeout(),
+			idle_timeout: self.idle_timeout(),
+		}
+	}
 }
 
 // endregion: --- ChatOptionsSet
@@ -15,6 +15,11 @@ pub enum ChatResponseFormat {
 	/// Request structured output.
 	#[from]
 	JsonSpec(JsonSpec),
+
+	/// Request output matching a regular expression (grammar constrained generation).
+	/// NOTE: Currently only supported by the HuggingFace adapter with a TGI endpoint (grammar),
+	///       the other adapters return an `Error::AdapterNotSupported`.
+	Regex(String),
 }
 
 /// JSON specification used to enforce structured output.
@@ -1,6 +1,6 @@
 //! This module contains all the types related to a Chat Request (except ChatOptions, which has its own file).
 
-use crate::chat::{ChatMessage, ChatRole, StreamEnd, Tool, ToolCall, ToolResponse};
+use crate::chat::{ChatDocument, ChatMessage, ChatRole, StreamEnd, Tool, ToolCall, ToolResponse};
 use crate::support;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +18,10 @@ pub struct ChatRequest {
 
 	/// Optional tool definitions available to the model.
 	pub tools: Option<Vec<Tool>>,
+
+	/// Optional documents to ground the response on (citations are returned when supported).
+	/// NOTE: Currently only supported by the Cohere adapter.
+	pub documents: Option<Vec<ChatDocument>>,
 }
 
 /// Constructors
@@ -28,6 +32,7 @@ impl ChatRequest {
 			messages,
 			system: None,
 			tools: None,
+			documents: None,
 		}
 	}
 
@@ -37,6 +42,7 @@ impl ChatRequest {
 			system: Some(content.into()),
 			messages: Vec::new(),
 			tools: None,
+			documents: None,
 		}
 	}
 
@@ -46,6 +52,7 @@ impl ChatRequest {
 			system: None,
 			messages: vec![ChatMessage::user(content.into())],
 			tools: None,
+			documents: None,
 		}
 	}
 
@@ -55,6 +62,7 @@ impl ChatRequest {
 			system: None,
 			messages,
 			tools: None,
+			documents: None,
 		}
 	}
 }
@@ -99,6 +107,22 @@ impl ChatRequest {
 		self
 	}
 
+	/// Replace the grounding documents.
+	pub fn with_documents<I>(mut self, documents: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<ChatDocument>,
+	{
+		self.documents = Some(documents.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Append one grounding document.
+	pub fn append_document(mut self, document: impl Into<ChatDocument>) -> Self {
+		self.documents.get_or_insert_with(Vec::new).push(document.into());
+		self
+	}
+
 	/// Append an assistant tool-use turn and the corresponding tool response based on a
 	/// streaming `StreamEnd` capture. Thought signatures are included automatically and
 	/// ordered before tool calls when present.
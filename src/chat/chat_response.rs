@@ -2,8 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::ModelIden;
-use crate::chat::{ChatStream, MessageContent, ToolCall, Usage};
+use crate::adapter::AdapterDispatcher;
+use crate::chat::{CancelHandle, ChatOptions, ChatOptionsSet, ChatStream, Citation, MessageContent, ToolCall, Usage};
+use crate::common::{Cost, Pricing};
+use crate::webc::{WebResponse, WebStreamSource};
+use crate::{ModelIden, Result};
 
 // region:    --- ChatResponse
 
@@ -30,11 +33,36 @@ pub struct ChatResponse {
 	/// Token usage reported by the provider.
 	pub usage: Usage,
 
+	/// Citations of the response text, when returned by the provider (e.g., Cohere grounded generation).
+	pub citations: Option<Vec<Citation>>,
+
 	/// IMPORTANT: (since 0.5.3) This is populated at the client.exec_chat when the options capture_raw_body is set to true
 	/// Raw response body (only if asked via options.capture_raw_body)
 	pub captured_raw_body: Option<serde_json::Value>,
 }
 
+/// Replay
+impl ChatResponse {
+	/// Parses a provider response body (e.g., captured with `ChatOptions::with_capture_raw_body(true)`)
+	/// with the adapter of the `model_iden`, as `Client::exec_chat(...)` does, without any web call.
+	///
+	/// The `options` parsing flags (e.g., `normalize_reasoning_content`) apply as for the original call.
+	///
+	/// Note: `captured_raw_body` is left empty.
+	pub fn from_provider_body(
+		model_iden: ModelIden,
+		body: serde_json::Value,
+		options: Option<&ChatOptions>,
+	) -> Result<ChatResponse> {
+		let web_response = WebResponse {
+			status: reqwest::StatusCode::OK,
+			body,
+		};
+		let options_set = ChatOptionsSet::default().with_chat_options(options);
+		AdapterDispatcher::to_chat_response(model_iden, web_response, options_set)
+	}
+}
+
 // Getters
 impl ChatResponse {
 	/// Returns the first text segment, if any.
@@ -68,6 +96,16 @@ impl ChatResponse {
 	}
 }
 
+/// Cost
+impl ChatResponse {
+	/// Returns the itemized cost of this response from its usage, or `None` if the model has no price.
+	///
+	/// The model is looked up by `model_iden`, then by `provider_model_iden`.
+	pub fn cost(&self, pricing: &Pricing) -> Option<Cost> {
+		pricing.cost_with_fallback(&self.model_iden, &self.provider_model_iden, &self.usage)
+	}
+}
+
 /// Deprecated Getters
 impl ChatResponse {
 	/// Deprecated: use `first_text` or `texts`.
@@ -98,4 +136,131 @@ pub struct ChatStreamResponse {
 	pub model_iden: ModelIden,
 }
 
+impl ChatStreamResponse {
+	/// Returns the handle cancelling the stream (see `CancelHandle`).
+	pub fn cancel_handle(&self) -> CancelHandle {
+		self.stream.cancel_handle()
+	}
+}
+
+/// Replay
+impl ChatStreamResponse {
+	/// Parses a saved streaming response body (e.g., the SSE `data:` events, or the Gemini JSON array)
+	/// with the adapter of the `model_iden`, as `Client::exec_chat_stream(...)` does, without any web call.
+	///
+	/// The `options` capture flags (e.g., `capture_content`, `capture_usage`) define the `StreamEnd` content.
+	pub fn from_provider_transcript(
+		model_iden: ModelIden,
+		transcript: impl Into<String>,
+		options: Option<&ChatOptions>,
+	) -> Result<ChatStreamResponse> {
+		let options_set = ChatOptionsSet::default().with_chat_options(options);
+		AdapterDispatcher::to_chat_stream(model_iden, WebStreamSource::from_text(transcript), options_set)
+	}
+}
+
 // endregion: --- ChatStreamResponse
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::adapter::AdapterKind;
+	use crate::chat::ChatStreamEvent;
+	use futures::StreamExt;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_chat_response_from_provider_body_openai() -> Result<()> {
+		// -- Setup & Fixtures
+		let model_iden = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let body = json!({
+			"id": "chatcmpl-1",
+			"object": "chat.completion",
+			"model": "gpt-4o-mini-2024-07-18",
+			"choices": [{
+				"index": 0,
+				"message": {"role": "assistant", "content": "Hello!"},
+				"finish_reason": "stop"
+			}],
+			"usage": {"prompt_tokens": 9, "completion_tokens": 3, "total_tokens": 12}
+		});
+
+		// -- Exec
+		let chat_res = ChatResponse::from_provider_body(model_iden, body, None)?;
+
+		// -- Check
+		assert_eq!(chat_res.first_text(), Some("Hello!"));
+		assert_eq!(
+			chat_res.provider_model_iden.model_name.as_str(),
+			"gpt-4o-mini-2024-07-18"
+		);
+		assert_eq!(chat_res.usage.total_tokens, Some(12));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_chat_response_from_provider_body_options() -> Result<()> {
+		// -- Setup & Fixtures
+		let model_iden = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let body = json!({
+			"model": "gpt-4o-mini",
+			"choices": [{
+				"index": 0,
+				"message": {"role": "assistant", "content": "<think>Greeting back</think>Hello!"},
+				"finish_reason": "stop"
+			}]
+		});
+		let options = ChatOptions::default().with_normalize_reasoning_content(true);
+
+		// -- Exec
+		let chat_res = ChatResponse::from_provider_body(model_iden, body, Some(&options))?;
+
+		// -- Check
+		assert_eq!(chat_res.first_text(), Some("Hello!"));
+		assert_eq!(chat_res.reasoning_content.as_deref(), Some("Greeting back"));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_chat_stream_response_from_provider_transcript_openai() -> Result<()> {
+		// -- Setup & Fixtures
+		let model_iden = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let transcript = r#"data: {"id":"1","object":"chat.completion.chunk","model":"gpt-4o-mini","choices":[{"index":0,"delta":{"role":"assistant","content":"Hel"},"finish_reason":null}]}
+
+data: {"id":"1","object":"chat.completion.chunk","model":"gpt-4o-mini","choices":[{"index":0,"delta":{"content":"lo!"},"finish_reason":null}]}
+
+data: {"id":"1","object":"chat.completion.chunk","model":"gpt-4o-mini","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}
+
+data: [DONE]
+
+"#;
+		let options = ChatOptions::default().with_capture_content(true);
+
+		// -- Exec
+		let stream_res = ChatStreamResponse::from_provider_transcript(model_iden, transcript, Some(&options))?;
+		let mut stream = stream_res.stream;
+		let mut chunks = String::new();
+		let mut end_text = None;
+		while let Some(event) = stream.next().await {
+			match event? {
+				ChatStreamEvent::Chunk(chunk) => chunks.push_str(&chunk.content),
+				ChatStreamEvent::End(end) => end_text = end.captured_into_first_text(),
+				_ => (),
+			}
+		}
+
+		// -- Check
+		assert_eq!(chunks, "Hello!");
+		assert_eq!(end_text.as_deref(), Some("Hello!"));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,7 +1,13 @@
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
-use crate::chat::{ChatMessage, ContentPart, MessageContent, ToolCall, Usage};
+use crate::chat::chat_stream_blocks::BlockTracker;
+use crate::chat::chat_stream_control::StreamControl;
+use crate::chat::chat_stream_metrics::MetricsRecorder;
+use crate::chat::{CancelHandle, ChatMessage, Citation, ContentPart, MessageContent, StreamMetrics, ToolCall, Usage};
+use crate::common::{Cost, Pricing};
+use crate::{ModelIden, UsageTracker};
 use futures::Stream;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -9,20 +15,78 @@ type InterStreamType = Pin<Box<dyn Stream<Item = crate::Result<InterStreamEvent>
 
 /// A stream of chat events produced by a streaming chat request.
 pub struct ChatStream {
+	model_iden: ModelIden,
 	inter_stream: InterStreamType,
+	usage_recorder: Option<UsageRecorder>,
+	control: Option<StreamControl>,
+	cancel_handle: CancelHandle,
+	/// True once the stream end (or a timeout error) was returned.
+	ended: bool,
+	blocks: BlockTracker,
+	metrics: MetricsRecorder,
+	/// The events to return before polling the inner stream (e.g., the block events before a chunk).
+	pending_events: VecDeque<ChatStreamEvent>,
+}
+
+/// The `UsageTracker` (with the model and label) recording the captured usage at the stream end.
+struct UsageRecorder {
+	tracker: UsageTracker,
+	model_iden: ModelIden,
+	label: Option<String>,
 }
 
 impl ChatStream {
-	pub(crate) fn new(inter_stream: InterStreamType) -> Self {
-		ChatStream { inter_stream }
+	pub(crate) fn new(model_iden: ModelIden, inter_stream: InterStreamType) -> Self {
+		ChatStream {
+			model_iden,
+			inter_stream,
+			usage_recorder: None,
+			control: None,
+			cancel_handle: CancelHandle::default(),
+			ended: false,
+			blocks: BlockTracker::default(),
+			metrics: MetricsRecorder::default(),
+			pending_events: VecDeque::new(),
+		}
+	}
+
+	/// Applies the timeouts and the cancellation of the `StreamControl`.
+	pub(crate) fn with_control(mut self, control: StreamControl) -> Self {
+		self.control = Some(control);
+		self
+	}
+
+	/// Returns the handle cancelling this stream (e.g., from another task).
+	pub fn cancel_handle(&self) -> CancelHandle {
+		self.cancel_handle.clone()
+	}
+
+	/// Records the captured usage of the `StreamEnd` in the tracker.
+	pub(crate) fn with_usage_tracker(
+		mut self,
+		tracker: UsageTracker,
+		model_iden: ModelIden,
+		label: Option<String>,
+	) -> Self {
+		self.usage_recorder = Some(UsageRecorder {
+			tracker,
+			model_iden,
+			label,
+		});
+		self
 	}
 
-	pub(crate) fn from_inter_stream<T>(inter_stream: T) -> Self
+	pub(crate) fn from_inter_stream<T>(model_iden: ModelIden, inter_stream: T) -> Self
 	where
 		T: Stream<Item = crate::Result<InterStreamEvent>> + Send + 'static,
 	{
 		let boxed_stream: InterStreamType = Box::pin(inter_stream);
-		ChatStream::new(boxed_stream)
+		ChatStream::new(model_iden, boxed_stream)
+	}
+
+	/// Returns the model identifier (AdapterKind/ModelName) of this stream.
+	pub fn model_iden(&self) -> &ModelIden {
+		&self.model_iden
 	}
 }
 
@@ -34,8 +98,37 @@ impl Stream for ChatStream {
 	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
 		let this = self.get_mut();
 
+		if let Some(event) = this.pending_events.pop_front() {
+			return Poll::Ready(Some(Ok(event)));
+		}
+
+		this.metrics.on_poll();
+
+		// -- Cancellation (closes the inner stream, and ends with the partial StreamEnd)
+		this.cancel_handle.register(cx);
+		if this.cancel_handle.is_cancelled() && !this.ended {
+			this.ended = true;
+			this.inter_stream = Box::pin(futures::stream::empty());
+			let mut stream_end = this
+				.control
+				.as_mut()
+				.map(|control| control.take_partial_end())
+				.unwrap_or_default();
+			stream_end.captured_content =
+				stream_end.captured_content.map(|content| this.blocks.layout_content(content));
+			stream_end.metrics = Some(this.metrics.metrics());
+			this.pending_events.extend(this.blocks.stop());
+			this.pending_events.push_back(ChatStreamEvent::End(stream_end));
+			return Poll::Ready(this.pending_events.pop_front().map(Ok));
+		}
+
 		match Pin::new(&mut this.inter_stream).poll_next(cx) {
 			Poll::Ready(Some(Ok(event))) => {
+				if let Some(control) = &mut this.control {
+					control.on_event(&event);
+				}
+				this.metrics.on_event(&event);
+				let block_events = this.blocks.on_event(&event);
 				let chat_event = match event {
 					InterStreamEvent::Start => ChatStreamEvent::Start,
 					InterStreamEvent::Chunk(content) => ChatStreamEvent::Chunk(StreamChunk { content }),
@@ -45,16 +138,47 @@ impl Stream for ChatStream {
 					InterStreamEvent::ThoughtSignatureChunk(content) => {
 						ChatStreamEvent::ThoughtSignatureChunk(StreamChunk { content })
 					}
+					InterStreamEvent::ToolCallStart(start) => ChatStreamEvent::ToolCallStart(start),
+					InterStreamEvent::ToolCallArgsDelta(delta) => ChatStreamEvent::ToolCallArgsDelta(delta),
+					InterStreamEvent::ToolCallEnd(end) => ChatStreamEvent::ToolCallEnd(end),
 					InterStreamEvent::ToolCallChunk(tool_call) => {
 						ChatStreamEvent::ToolCallChunk(ToolChunk { tool_call })
 					}
-					InterStreamEvent::End(inter_end) => ChatStreamEvent::End(inter_end.into()),
+					InterStreamEvent::Usage(usage) => ChatStreamEvent::Usage(usage),
+					InterStreamEvent::End(inter_end) => {
+						this.ended = true;
+						let mut stream_end: StreamEnd = inter_end.into();
+						if let Some(recorder) = &this.usage_recorder
+							&& let Some(usage) = &stream_end.captured_usage
+						{
+							recorder.tracker.record(&recorder.model_iden, recorder.label.as_deref(), usage);
+						}
+						stream_end.captured_content =
+							stream_end.captured_content.map(|content| this.blocks.layout_content(content));
+						stream_end.metrics = Some(this.metrics.metrics());
+						ChatStreamEvent::End(stream_end)
+					}
 				};
-				Poll::Ready(Some(Ok(chat_event)))
+				this.pending_events.extend(block_events);
+				this.pending_events.push_back(chat_event);
+				Poll::Ready(this.pending_events.pop_front().map(Ok))
 			}
 			Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
-			Poll::Ready(None) => Poll::Ready(None),
-			Poll::Pending => Poll::Pending,
+			// The inner stream can finish without an End event, so stop the open block
+			Poll::Ready(None) => {
+				this.ended = true;
+				this.inter_stream = Box::pin(futures::stream::empty());
+				Poll::Ready(this.blocks.stop().map(Ok))
+			}
+			Poll::Pending => {
+				// -- Timeouts (closes the inner stream)
+				if let Some(err) = this.control.as_mut().and_then(|control| control.poll_timeout(cx)) {
+					this.ended = true;
+					this.inter_stream = Box::pin(futures::stream::empty());
+					return Poll::Ready(Some(Err(err)));
+				}
+				Poll::Pending
+			}
 		}
 	}
 }
@@ -78,9 +202,30 @@ pub enum ChatStreamEvent {
 	/// Thought signature content chunk.
 	ThoughtSignatureChunk(StreamChunk),
 
+	/// Start of a tool call (before its arguments deltas).
+	ToolCallStart(ToolCallStart),
+
+	/// Tool-call arguments fragment (the raw JSON text, as streamed by the provider).
+	ToolCallArgsDelta(ToolCallArgsDelta),
+
+	/// End of a tool call (all its arguments deltas were sent).
+	ToolCallEnd(ToolCallEnd),
+
+	/// Start of a content block (a run of text or reasoning chunks, or a tool call).
+	/// The following content events belong to this block, until its `BlockStop`.
+	BlockStart(StreamBlock),
+
+	/// End of a content block (sent before the next `BlockStart` or the `End`).
+	BlockStop(StreamBlock),
+
 	/// Tool-call chunk.
+	/// Note: Still sent with the fine-grained `ToolCallStart`, `ToolCallArgsDelta`, and `ToolCallEnd` events.
 	ToolCallChunk(ToolChunk),
 
+	/// The usage so far, when sent by the provider during the stream (e.g., Anthropic, Gemini).
+	/// Note: Sent regardless of `ChatOptions.capture_usage` (which captures the final usage in the `End`).
+	Usage(Usage),
+
 	/// End of stream.
 	/// May include captured usage and/or content when enabled via `ChatOptions`.
 	End(StreamEnd),
@@ -101,6 +246,57 @@ pub struct ToolChunk {
 	pub tool_call: ToolCall,
 }
 
+/// Content of `ChatStreamEvent::BlockStart` and `ChatStreamEvent::BlockStop`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamBlock {
+	/// The position of the block in the response (0-based).
+	pub index: usize,
+
+	/// The kind of content of the block.
+	pub kind: StreamBlockKind,
+}
+
+/// The kind of content of a `StreamBlock`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamBlockKind {
+	/// Text chunks (`ChatStreamEvent::Chunk`).
+	Text,
+	/// Reasoning chunks (`ChatStreamEvent::ReasoningChunk`).
+	Reasoning,
+	/// A tool call (`ChatStreamEvent::ToolCallStart` to `ChatStreamEvent::ToolCallChunk`).
+	ToolCall,
+}
+
+/// Content of `ChatStreamEvent::ToolCallStart`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ToolCallStart {
+	/// The tool call id (the `ToolCall::call_id`).
+	pub call_id: String,
+
+	/// Name of the function to invoke.
+	pub fn_name: String,
+
+	/// The position of the tool call among the tool calls of the response (0-based).
+	pub index: usize,
+}
+
+/// Content of `ChatStreamEvent::ToolCallArgsDelta`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ToolCallArgsDelta {
+	/// The tool call id (the `ToolCall::call_id`).
+	pub call_id: String,
+
+	/// The arguments JSON text fragment (the concatenation of the deltas is the arguments JSON).
+	pub delta: String,
+}
+
+/// Content of `ChatStreamEvent::ToolCallEnd`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ToolCallEnd {
+	/// The tool call id (the `ToolCall::call_id`).
+	pub call_id: String,
+}
+
 /// Terminal event data with optionally captured usage and content.
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StreamEnd {
@@ -109,12 +305,19 @@ pub struct StreamEnd {
 
 	/// Captured final content (text and tool calls) if `ChatOptions.capture_content`
 	/// or `capture_tool_calls` is enabled.
+	/// The parts are in the stream order, with a text part per text block (e.g., interleaved thinking).
 	/// Note: Since 0.4.0 this includes tool calls as well (for API symmetry with `ChatResponse`);
 	///       use `.captured_tool_calls()` or `.captured_texts()`.
 	pub captured_content: Option<MessageContent>,
 
 	/// Captured reasoning content if `ChatOptions.capture_reasoning` is enabled.
 	pub captured_reasoning_content: Option<String>,
+
+	/// Captured citations if `ChatOptions.capture_content` is enabled and the provider returns them.
+	pub captured_citations: Option<Vec<Citation>>,
+
+	/// The timings of the stream (always set by the `ChatStream`).
+	pub metrics: Option<StreamMetrics>,
 }
 
 impl From<InterStreamEnd> for StreamEnd {
@@ -171,6 +374,8 @@ impl From<InterStreamEnd> for StreamEnd {
 			captured_usage: inter_end.captured_usage,
 			captured_content,
 			captured_reasoning_content: inter_end.captured_reasoning_content,
+			captured_citations: inter_end.captured_citations,
+			metrics: None,
 		}
 	}
 }
@@ -263,4 +468,16 @@ impl StreamEnd {
 	}
 }
 
+/// Cost
+impl StreamEnd {
+	/// Returns the itemized cost from the captured usage (requires `ChatOptions.capture_usage`),
+	/// or `None` if there is no captured usage or the model has no price.
+	///
+	/// Note: The stream end does not carry the model, so pass the `ChatStreamResponse.model_iden`.
+	pub fn cost(&self, pricing: &Pricing, model_iden: &ModelIden) -> Option<Cost> {
+		let usage = self.captured_usage.as_ref()?;
+		pricing.cost(model_iden, usage)
+	}
+}
+
 // endregion: --- ChatStreamEvent
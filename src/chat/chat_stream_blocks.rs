@@ -0,0 +1,331 @@
+use crate::adapter::inter_stream::InterStreamEvent;
+use crate::chat::{ChatStreamEvent, ContentPart, MessageContent, StreamBlock, StreamBlockKind, ToolCall};
+use std::collections::VecDeque;
+
+// region:    --- BlockTracker
+
+/// Tracks the content blocks of a `ChatStream` (for the `BlockStart` / `BlockStop` events),
+/// and the order of the streamed content parts (for the `StreamEnd.captured_content`).
+///
+/// A block is a run of events of the same kind (text or reasoning), or a single tool call.
+#[derive(Debug, Default)]
+pub(crate) struct BlockTracker {
+	current: Option<StreamBlock>,
+	next_index: usize,
+	layout: Vec<LayoutItem>,
+}
+
+/// A content part, in the stream order.
+#[derive(Debug)]
+enum LayoutItem {
+	/// A text part, with the text of its chunks.
+	Text {
+		block_index: usize,
+		text: String,
+	},
+	ThoughtSignature,
+	ToolCall {
+		call_id: String,
+	},
+}
+
+impl BlockTracker {
+	/// Returns the block events to send before this event (the stop of the current block, and the start of the new one).
+	pub(crate) fn on_event(&mut self, event: &InterStreamEvent) -> Vec<ChatStreamEvent> {
+		match event {
+			InterStreamEvent::Start
+			| InterStreamEvent::ToolCallArgsDelta(_)
+			| InterStreamEvent::ToolCallEnd(_)
+			| InterStreamEvent::Usage(_) => Vec::new(),
+			InterStreamEvent::Chunk(text) => {
+				let events = self.enter(StreamBlockKind::Text, false);
+				let block_index = self.current.map(|block| block.index).unwrap_or_default();
+				match self.layout.last_mut() {
+					Some(LayoutItem::Text {
+						block_index: last_index,
+						text: block_text,
+					}) if *last_index == block_index => block_text.push_str(text),
+					_ => self.layout.push(LayoutItem::Text {
+						block_index,
+						text: text.clone(),
+					}),
+				}
+				events
+			}
+			InterStreamEvent::ReasoningChunk(_) => self.enter(StreamBlockKind::Reasoning, false),
+			// NOTE: The thought signatures belong to the current block (e.g., the Anthropic thinking block)
+			InterStreamEvent::ThoughtSignatureChunk(_) => {
+				self.layout.push(LayoutItem::ThoughtSignature);
+				Vec::new()
+			}
+			InterStreamEvent::ToolCallStart(start) => {
+				self.layout.push(LayoutItem::ToolCall {
+					call_id: start.call_id.clone(),
+				});
+				self.enter(StreamBlockKind::ToolCall, true)
+			}
+			// NOTE: The tool call chunks are within the block of their `ToolCallStart`
+			InterStreamEvent::ToolCallChunk(tool_call) => {
+				if self.has_tool_call(&tool_call.call_id) {
+					return Vec::new();
+				}
+				self.layout.push(LayoutItem::ToolCall {
+					call_id: tool_call.call_id.clone(),
+				});
+				self.enter(StreamBlockKind::ToolCall, true)
+			}
+			InterStreamEvent::End(_) => self.stop().into_iter().collect(),
+		}
+	}
+
+	/// Returns the stop event of the current block, if any.
+	pub(crate) fn stop(&mut self) -> Option<ChatStreamEvent> {
+		self.current.take().map(ChatStreamEvent::BlockStop)
+	}
+
+	/// Orders the captured content parts as streamed (with a text part per text block or segment).
+	///
+	/// The captured text is split per streamed segment only if it is the streamed text (e.g., not rewritten
+	/// by `normalize_reasoning_content`), otherwise it is a single part at the first segment position.
+	/// The parts that were not streamed as events are kept at the end, in the default order.
+	pub(crate) fn layout_content(&self, content: MessageContent) -> MessageContent {
+		let mut text: Option<String> = None;
+		let mut thought_signatures: VecDeque<String> = VecDeque::new();
+		let mut tool_calls: Vec<ToolCall> = Vec::new();
+		let mut other_parts: Vec<ContentPart> = Vec::new();
+		for part in content.into_parts() {
+			match part {
+				ContentPart::Text(part_text) => text.get_or_insert_with(String::new).push_str(&part_text),
+				ContentPart::ThoughtSignature(signature) => thought_signatures.push_back(signature),
+				ContentPart::ToolCall(tool_call) => tool_calls.push(tool_call),
+				part => other_parts.push(part),
+			}
+		}
+
+		let streamed_text: String = self
+			.layout
+			.iter()
+			.filter_map(|item| match item {
+				LayoutItem::Text { text, .. } => Some(text.as_str()),
+				_ => None,
+			})
+			.collect();
+		let use_segments = text.as_deref() == Some(streamed_text.as_str());
+		if use_segments {
+			text = None;
+		}
+
+		let mut parts: Vec<ContentPart> = Vec::new();
+		for item in self.layout.iter() {
+			match item {
+				LayoutItem::Text { text: segment, .. } => {
+					if use_segments {
+						if !segment.is_empty() {
+							parts.push(ContentPart::Text(segment.clone()));
+						}
+					} else if let Some(text) = text.take().filter(|text| !text.is_empty()) {
+						parts.push(ContentPart::Text(text));
+					}
+				}
+				LayoutItem::ThoughtSignature => {
+					if let Some(signature) = thought_signatures.pop_front() {
+						parts.push(ContentPart::ThoughtSignature(signature));
+					}
+				}
+				LayoutItem::ToolCall { call_id } => {
+					if let Some(idx) = tool_calls.iter().position(|tool_call| &tool_call.call_id == call_id) {
+						parts.push(ContentPart::ToolCall(tool_calls.remove(idx)));
+					}
+				}
+			}
+		}
+
+		parts.extend(thought_signatures.into_iter().map(ContentPart::ThoughtSignature));
+		if let Some(text) = text.filter(|text| !text.is_empty()) {
+			parts.push(ContentPart::Text(text));
+		}
+		parts.extend(tool_calls.into_iter().map(ContentPart::ToolCall));
+		parts.extend(other_parts);
+
+		MessageContent::from_parts(parts)
+	}
+}
+
+// Support
+impl BlockTracker {
+	/// Starts a block of this kind (stopping the current one), unless the current block is of this kind.
+	fn enter(&mut self, kind: StreamBlockKind, new_block: bool) -> Vec<ChatStreamEvent> {
+		if !new_block && self.current.is_some_and(|block| block.kind == kind) {
+			return Vec::new();
+		}
+
+		let mut events: Vec<ChatStreamEvent> = self.stop().into_iter().collect();
+		let block = StreamBlock {
+			index: self.next_index,
+			kind,
+		};
+		self.next_index += 1;
+		self.current = Some(block);
+		events.push(ChatStreamEvent::BlockStart(block));
+
+		events
+	}
+
+	fn has_tool_call(&self, call_id: &str) -> bool {
+		self.layout
+			.iter()
+			.any(|item| matches!(item, LayoutItem::ToolCall { call_id: id } if id == call_id))
+	}
+}
+
+// endregion: --- BlockTracker
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::adapter::{AdapterKind, MockScript, MockStreamItem};
+	use crate::chat::{ChatOptions, ChatRequest, ChatStreamResponse};
+	use crate::{Client, ModelIden};
+	use futures::StreamExt;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_chat_stream_blocks_interleaved() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		let tool_call = ToolCall {
+			call_id: "call_1".to_string(),
+			fn_name: "get_weather".to_string(),
+			fn_arguments: json!({"city": "Paris"}),
+			thought_signatures: None,
+		};
+		mock.push_stream([
+			MockStreamItem::ReasoningChunk("Let me check".to_string()),
+			MockStreamItem::ThoughtSignature("sig-1".to_string()),
+			MockStreamItem::Chunk("Checking".to_string()),
+			MockStreamItem::ToolCall(tool_call),
+			MockStreamItem::ReasoningChunk("Now answer".to_string()),
+			MockStreamItem::ThoughtSignature("sig-2".to_string()),
+			MockStreamItem::Chunk("It is ".to_string()),
+			MockStreamItem::Chunk("sunny".to_string()),
+		]);
+		let client = Client::builder().with_mock_script(mock).build();
+		let options = ChatOptions::default().with_capture_content(true).with_capture_tool_calls(true);
+
+		// -- Exec
+		let mut stream = client
+			.exec_chat_stream("mock::test-model", ChatRequest::from_user("Weather?"), Some(&options))
+			.await?
+			.stream;
+		let mut blocks: Vec<(&str, usize, StreamBlockKind)> = Vec::new();
+		let mut stream_end = None;
+		while let Some(event) = stream.next().await {
+			match event? {
+				ChatStreamEvent::BlockStart(block) => blocks.push(("start", block.index, block.kind)),
+				ChatStreamEvent::BlockStop(block) => blocks.push(("stop", block.index, block.kind)),
+				ChatStreamEvent::End(end) => stream_end = Some(end),
+				_ => (),
+			}
+		}
+
+		// -- Check - blocks
+		let kinds = [
+			StreamBlockKind::Reasoning,
+			StreamBlockKind::Text,
+			StreamBlockKind::ToolCall,
+			StreamBlockKind::Reasoning,
+			StreamBlockKind::Text,
+		];
+		let expected: Vec<(&str, usize, StreamBlockKind)> = kinds
+			.into_iter()
+			.enumerate()
+			.flat_map(|(index, kind)| [("start", index, kind), ("stop", index, kind)])
+			.collect();
+		assert_eq!(blocks, expected);
+
+		// -- Check - captured content
+		let content = stream_end
+			.and_then(|end| end.captured_content)
+			.ok_or("Should have captured content")?;
+		let parts = content.parts();
+		assert_eq!(parts.len(), 5);
+		assert!(matches!(&parts[0], ContentPart::ThoughtSignature(sig) if sig == "sig-1"));
+		assert!(matches!(&parts[1], ContentPart::Text(text) if text == "Checking"));
+		assert!(matches!(&parts[2], ContentPart::ToolCall(call) if call.call_id == "call_1"));
+		assert!(matches!(&parts[3], ContentPart::ThoughtSignature(sig) if sig == "sig-2"));
+		assert!(matches!(&parts[4], ContentPart::Text(text) if text == "It is sunny"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_chat_stream_blocks_layout_text_segments() -> Result<()> {
+		// -- Setup & Fixtures
+		let tool_call = ToolCall {
+			call_id: "call_1".to_string(),
+			fn_name: "get_weather".to_string(),
+			fn_arguments: json!({"city": "Paris"}),
+			thought_signatures: None,
+		};
+		let mut blocks = BlockTracker::default();
+		for event in [
+			InterStreamEvent::Chunk("<think>Ok</think>Café".to_string()),
+			InterStreamEvent::ToolCallChunk(tool_call.clone()),
+			InterStreamEvent::Chunk("😀 done".to_string()),
+		] {
+			blocks.on_event(&event);
+		}
+		let content_of = |text: &str| {
+			MessageContent::from_parts(vec![
+				ContentPart::Text(text.to_string()),
+				ContentPart::ToolCall(tool_call.clone()),
+			])
+		};
+
+		// -- Exec
+		let streamed = blocks.layout_content(content_of("<think>Ok</think>Café😀 done"));
+		let normalized = blocks.layout_content(content_of("Café😀 done"));
+
+		// -- Check - the streamed text is split per segment
+		let parts = streamed.parts();
+		assert_eq!(parts.len(), 3);
+		assert!(matches!(&parts[0], ContentPart::Text(text) if text == "<think>Ok</think>Café"));
+		assert!(matches!(&parts[1], ContentPart::ToolCall(_)));
+		assert!(matches!(&parts[2], ContentPart::Text(text) if text == "😀 done"));
+
+		// -- Check - a rewritten text is kept as one part
+		let parts = normalized.parts();
+		assert_eq!(parts.len(), 2);
+		assert!(matches!(&parts[0], ContentPart::Text(text) if text == "Café😀 done"));
+		assert!(matches!(&parts[1], ContentPart::ToolCall(_)));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_chat_stream_blocks_stop_without_end() -> Result<()> {
+		// -- Setup & Fixtures
+		// An OpenAI transcript without the finish reason and `[DONE]` (the inner stream has no End event)
+		let model_iden = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let transcript = r#"data: {"choices":[{"index":0,"delta":{"content":"Hello"}}]}
+
+"#;
+
+		// -- Exec
+		let stream = ChatStreamResponse::from_provider_transcript(model_iden, transcript, None)?.stream;
+		let events: Vec<ChatStreamEvent> = stream.map(|event| event.unwrap()).collect().await;
+
+		// -- Check
+		assert!(
+			matches!(events.last(), Some(ChatStreamEvent::BlockStop(block)) if block.kind == StreamBlockKind::Text)
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,289 @@
+use crate::ModelIden;
+use crate::chat::{
+	ChatResponse, ChatStream, ChatStreamEvent, ChatStreamResponse, Citation, ContentPart, MessageContent, ToolCall,
+	Usage,
+};
+use futures::StreamExt;
+use serde_json::Value;
+
+// region:    --- ChatStreamCollector
+
+/// Accumulates the events of a chat stream into a `ChatResponse`.
+///
+/// Unlike the `StreamEnd` captures, the content is accumulated from the events themselves,
+/// independently of the `ChatOptions::capture_*` flags.
+///
+/// Notes:
+/// - The content parts are in the stream order, with a text part per text block (the reasoning is the `reasoning_content`).
+/// - The tool call arguments are accumulated from the `ToolCallArgsDelta` events (or taken from the `ToolCallChunk`).
+/// - The usage is the last `Usage` event, or the captured usage (`ChatOptions::with_capture_usage(true)`).
+/// - The citations are only available when captured (`ChatOptions::with_capture_content(true)`).
+/// - The stream has no provider model name, so `provider_model_iden` is the `model_iden`.
+#[derive(Debug)]
+pub struct ChatStreamCollector {
+	model_iden: ModelIden,
+	parts: Vec<CollectedPart>,
+	/// The index of the current block (`ChatStreamEvent::BlockStart`)
+	block_index: Option<usize>,
+	reasoning_content: Option<String>,
+	usage: Option<Usage>,
+	citations: Option<Vec<Citation>>,
+}
+
+#[derive(Debug)]
+enum CollectedPart {
+	Text {
+		block_index: Option<usize>,
+		text: String,
+	},
+	ThoughtSignature(String),
+	/// A tool call, with the arguments of its `ToolCallArgsDelta` events (if started with a `ToolCallStart`)
+	ToolCall {
+		tool_call: ToolCall,
+		args_deltas: Option<String>,
+	},
+}
+
+impl ChatStreamCollector {
+	pub fn new(model_iden: ModelIden) -> Self {
+		Self {
+			model_iden,
+			parts: Vec::new(),
+			block_index: None,
+			reasoning_content: None,
+			usage: None,
+			citations: None,
+		}
+	}
+
+	/// Adds a stream event.
+	pub fn add(&mut self, event: &ChatStreamEvent) {
+		match event {
+			ChatStreamEvent::Start | ChatStreamEvent::BlockStop(_) | ChatStreamEvent::ToolCallEnd(_) => (),
+			ChatStreamEvent::BlockStart(block) => self.block_index = Some(block.index),
+			ChatStreamEvent::Chunk(chunk) => match self.parts.last_mut() {
+				Some(CollectedPart::Text { block_index, text }) if *block_index == self.block_index => {
+					text.push_str(&chunk.content)
+				}
+				_ => self.parts.push(CollectedPart::Text {
+					block_index: self.block_index,
+					text: chunk.content.clone(),
+				}),
+			},
+			ChatStreamEvent::ReasoningChunk(chunk) => {
+				self.reasoning_content.get_or_insert_with(String::new).push_str(&chunk.content)
+			}
+			ChatStreamEvent::ThoughtSignatureChunk(chunk) => {
+				self.parts.push(CollectedPart::ThoughtSignature(chunk.content.clone()))
+			}
+			ChatStreamEvent::ToolCallStart(start) => self.parts.push(CollectedPart::ToolCall {
+				tool_call: ToolCall {
+					call_id: start.call_id.clone(),
+					fn_name: start.fn_name.clone(),
+					fn_arguments: Value::Null,
+					thought_signatures: None,
+				},
+				args_deltas: Some(String::new()),
+			}),
+			ChatStreamEvent::ToolCallArgsDelta(delta) => {
+				if let Some(args_deltas) = self.tool_call_args_deltas(&delta.call_id) {
+					args_deltas.push_str(&delta.delta);
+				}
+			}
+			// NOTE: Some providers send a chunk per arguments delta, so the deltas take precedence for a started tool call
+			ChatStreamEvent::ToolCallChunk(tool_chunk) => {
+				let started = self.parts.iter().any(
+					|part| matches!(part, CollectedPart::ToolCall { tool_call, .. } if tool_call.call_id == tool_chunk.tool_call.call_id),
+				);
+				if !started {
+					self.parts.push(CollectedPart::ToolCall {
+						tool_call: tool_chunk.tool_call.clone(),
+						args_deltas: None,
+					});
+				}
+			}
+			ChatStreamEvent::Usage(usage) => self.usage = Some(usage.clone()),
+			ChatStreamEvent::End(stream_end) => {
+				if let Some(usage) = &stream_end.captured_usage {
+					self.usage = Some(usage.clone());
+				}
+				self.citations = stream_end.captured_citations.clone();
+			}
+		}
+	}
+
+	/// Returns the `ChatResponse` of the events added so far.
+	pub fn into_response(self) -> ChatResponse {
+		let parts: Vec<ContentPart> = self
+			.parts
+			.into_iter()
+			.map(|part| match part {
+				CollectedPart::Text { text, .. } => ContentPart::Text(text),
+				CollectedPart::ThoughtSignature(signature) => ContentPart::ThoughtSignature(signature),
+				CollectedPart::ToolCall {
+					mut tool_call,
+					args_deltas,
+				} => {
+					if let Some(args) = args_deltas {
+						// NOTE: Resilient, if the arguments are not valid JSON, keep the string
+						tool_call.fn_arguments = if args.is_empty() {
+							Value::Object(Default::default())
+						} else {
+							serde_json::from_str(&args).unwrap_or(Value::String(args))
+						};
+					}
+					ContentPart::ToolCall(tool_call)
+				}
+			})
+			.collect();
+
+		ChatResponse {
+			content: MessageContent::from_parts(parts),
+			reasoning_content: self.reasoning_content,
+			model_iden: self.model_iden.clone(),
+			provider_model_iden: self.model_iden,
+			usage: self.usage.unwrap_or_default(),
+			citations: self.citations,
+			captured_raw_body: None,
+		}
+	}
+}
+
+// Support
+impl ChatStreamCollector {
+	fn tool_call_args_deltas(&mut self, call_id: &str) -> Option<&mut String> {
+		self.parts.iter_mut().rev().find_map(|part| match part {
+			CollectedPart::ToolCall {
+				tool_call,
+				args_deltas: Some(args_deltas),
+			} if tool_call.call_id == call_id => Some(args_deltas),
+			_ => None,
+		})
+	}
+}
+
+// endregion: --- ChatStreamCollector
+
+// region:    --- Collect
+
+/// Collect
+impl ChatStream {
+	/// Consumes the stream, and returns the `ChatResponse` of its events (see `ChatStreamCollector`).
+	pub async fn collect_response(self) -> crate::Result<ChatResponse> {
+		self.collect_response_with(|_| ()).await
+	}
+
+	/// Consumes the stream, forwarding each event to `on_event` (e.g., to print it) while accumulating
+	/// the `ChatResponse` (see `ChatStreamCollector`).
+	pub async fn collect_response_with(
+		mut self,
+		mut on_event: impl FnMut(&ChatStreamEvent),
+	) -> crate::Result<ChatResponse> {
+		let mut collector = ChatStreamCollector::new(self.model_iden().clone());
+		while let Some(event) = self.next().await {
+			let event = event?;
+			on_event(&event);
+			collector.add(&event);
+		}
+		Ok(collector.into_response())
+	}
+}
+
+/// Collect
+impl ChatStreamResponse {
+	/// Consumes the stream, and returns its `ChatResponse` (see `ChatStream::collect_response`).
+	pub async fn into_response(self) -> crate::Result<ChatResponse> {
+		self.stream.collect_response().await
+	}
+
+	/// Consumes the stream, forwarding each event to `on_event` while accumulating its `ChatResponse`
+	/// (see `ChatStream::collect_response_with`).
+	pub async fn into_response_with(self, on_event: impl FnMut(&ChatStreamEvent)) -> crate::Result<ChatResponse> {
+		self.stream.collect_response_with(on_event).await
+	}
+}
+
+// endregion: --- Collect
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Client;
+	use crate::adapter::{AdapterKind, MockScript, MockStreamItem};
+	use crate::chat::ChatRequest;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_chat_stream_collector_into_response() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		let tool_call = ToolCall {
+			call_id: "call_1".to_string(),
+			fn_name: "get_weather".to_string(),
+			fn_arguments: json!({"city": "Paris"}),
+			thought_signatures: None,
+		};
+		mock.push_stream([
+			MockStreamItem::ReasoningChunk("Thinking".to_string()),
+			MockStreamItem::ThoughtSignature("sig-1".to_string()),
+			MockStreamItem::Chunk("Hello".to_string()),
+			MockStreamItem::Chunk(" world".to_string()),
+			MockStreamItem::ToolCall(tool_call),
+		]);
+		let client = Client::builder().with_mock_script(mock).build();
+
+		// -- Exec
+		let res = client
+			.exec_chat_stream("mock::test-model", ChatRequest::from_user("Hi"), None)
+			.await?;
+		let mut event_count = 0;
+		let chat_res = res.into_response_with(|_| event_count += 1).await?;
+
+		// -- Check
+		// Start, 3 blocks (start, stop), 4 chunks, 4 tool call events, End
+		assert_eq!(event_count, 16);
+		assert_eq!(chat_res.reasoning_content.as_deref(), Some("Thinking"));
+		assert_eq!(&*chat_res.model_iden.model_name, "mock::test-model");
+		let parts = chat_res.content.parts();
+		assert_eq!(parts.len(), 3);
+		assert!(matches!(&parts[0], ContentPart::ThoughtSignature(sig) if sig == "sig-1"));
+		assert!(matches!(&parts[1], ContentPart::Text(text) if text == "Hello world"));
+		assert!(matches!(&parts[2], ContentPart::ToolCall(call) if call.fn_name == "get_weather"));
+
+		Ok(())
+	}
+	#[tokio::test]
+	async fn test_chat_stream_collector_openai_tool_call_no_capture() -> Result<()> {
+		// -- Setup & Fixtures
+		let model_iden = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let transcript = [
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_1","function":{"name":"get_weather","arguments":""}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":"}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]}}]}"#,
+			r#"{"choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}"#,
+			"[DONE]",
+		]
+		.map(|data| format!("data: {data}\n\n"))
+		.concat();
+
+		// -- Exec
+		let chat_res = ChatStreamResponse::from_provider_transcript(model_iden, transcript, None)?
+			.into_response()
+			.await?;
+
+		// -- Check
+		let tool_calls = chat_res.into_tool_calls();
+		assert_eq!(tool_calls.len(), 1);
+		assert_eq!(tool_calls[0].call_id, "call_1");
+		assert_eq!(tool_calls[0].fn_name, "get_weather");
+		assert_eq!(tool_calls[0].fn_arguments, json!({"city": "Paris"}));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,502 @@
+use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
+use crate::chat::{ChatOptionsSet, StreamEnd, ToolCall};
+use crate::{Error, ModelIden};
+use derive_more::Display;
+use futures::task::AtomicWaker;
+use serde_json::Value;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{Instant, Sleep};
+
+// region:    --- TimeoutPhase
+
+/// The phase of a request that timed out (see `Error::Timeout`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum TimeoutPhase {
+	/// The whole request (`ChatOptions::timeout`).
+	#[display("total")]
+	Total,
+	/// The wait for the first stream content event (`ChatOptions::first_token_timeout`).
+	#[display("first token")]
+	FirstToken,
+	/// The wait between two stream events (`ChatOptions::idle_timeout`).
+	#[display("idle")]
+	Idle,
+}
+
+// endregion: --- TimeoutPhase
+
+// region:    --- CancelHandle
+
+/// Cancels a chat stream (see `ChatStreamResponse::cancel_handle()`).
+///
+/// On cancel, the stream closes the underlying web stream and yields a last `ChatStreamEvent::End`
+/// with the content received so far (per the `capture_*` options, without the usage).
+/// An in-progress tool call is captured with its arguments so far (kept as a string if not valid JSON).
+///
+/// The handle is cheap to clone and can be sent to another task.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+	inner: Arc<CancelInner>,
+}
+
+#[derive(Debug, Default)]
+struct CancelInner {
+	cancelled: AtomicBool,
+	waker: AtomicWaker,
+}
+
+impl CancelHandle {
+	pub fn cancel(&self) {
+		self.inner.cancelled.store(true, Ordering::SeqCst);
+		self.inner.waker.wake();
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.inner.cancelled.load(Ordering::SeqCst)
+	}
+
+	pub(crate) fn register(&self, cx: &Context<'_>) {
+		self.inner.waker.register(cx.waker());
+	}
+}
+
+// endregion: --- CancelHandle
+
+// region:    --- StreamControl
+
+/// The timeouts of a `ChatStream`, with the content captured for the partial `StreamEnd` of a cancelled stream.
+pub(crate) struct StreamControl {
+	model_iden: ModelIden,
+	timeouts: StreamTimeouts,
+	capture: PartialCapture,
+	start: Instant,
+	first_token_at: Option<Instant>,
+	last_event_at: Instant,
+	timer: Option<Pin<Box<Sleep>>>,
+}
+
+struct StreamTimeouts {
+	total: Option<Duration>,
+	first_token: Option<Duration>,
+	idle: Option<Duration>,
+}
+
+/// The captured content, in the same way as the adapter streamers, for a cancelled stream.
+#[derive(Default)]
+struct PartialCapture {
+	content: bool,
+	reasoning_content: bool,
+	tool_calls: bool,
+	text: Option<String>,
+	reasoning: Option<String>,
+	thought_signatures: Vec<String>,
+	tool_call_list: Vec<ToolCall>,
+	/// The tool calls started (`ToolCallStart`), with their arguments deltas, until their `ToolCallChunk`
+	started_tool_calls: Vec<(ToolCall, String)>,
+}
+
+impl StreamControl {
+	pub(crate) fn new(model_iden: ModelIden, options_set: &ChatOptionsSet<'_, '_>) -> Self {
+		let now = Instant::now();
+		Self {
+			model_iden,
+			timeouts: StreamTimeouts {
+				total: options_set.timeout(),
+				first_token: options_set.first_token_timeout(),
+				idle: options_set.idle_timeout(),
+			},
+			capture: PartialCapture {
+				content: options_set.capture_content().unwrap_or(false),
+				reasoning_content: options_set.capture_reasoning_content().unwrap_or(false),
+				tool_calls: options_set.capture_tool_calls().unwrap_or(false),
+				..Default::default()
+			},
+			start: now,
+			first_token_at: None,
+			last_event_at: now,
+			timer: None,
+		}
+	}
+
+	/// The stream end with the content received so far (for a cancelled stream).
+	pub(crate) fn take_partial_end(&mut self) -> StreamEnd {
+		self.capture.take_end().into()
+	}
+
+	/// Tracks an event of the inner stream (timings and partial capture).
+	pub(crate) fn on_event(&mut self, event: &InterStreamEvent) {
+		let now = Instant::now();
+		self.last_event_at = now;
+		match event {
+			InterStreamEvent::Start | InterStreamEvent::Usage(_) | InterStreamEvent::End(_) => (),
+			event => {
+				self.first_token_at.get_or_insert(now);
+				self.capture.add(event);
+			}
+		}
+	}
+
+	/// Returns the timeout error if a deadline passed while the inner stream is pending.
+	pub(crate) fn poll_timeout(&mut self, cx: &mut Context<'_>) -> Option<Error> {
+		let (deadline, phase) = self.next_deadline()?;
+
+		let timer = self.timer.get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+		if timer.deadline() != deadline {
+			timer.as_mut().reset(deadline);
+		}
+
+		match timer.as_mut().poll(cx) {
+			Poll::Ready(()) => Some(Error::Timeout {
+				model_iden: self.model_iden.clone(),
+				phase,
+			}),
+			Poll::Pending => None,
+		}
+	}
+
+	/// The earliest deadline (the first token one until the first content event, then the idle one).
+	fn next_deadline(&self) -> Option<(Instant, TimeoutPhase)> {
+		let total = self.timeouts.total.map(|timeout| (self.start + timeout, TimeoutPhase::Total));
+		let phase = match self.first_token_at {
+			None => self
+				.timeouts
+				.first_token
+				.map(|timeout| (self.start + timeout, TimeoutPhase::FirstToken)),
+			Some(_) => self
+				.timeouts
+				.idle
+				.map(|timeout| (self.last_event_at + timeout, TimeoutPhase::Idle)),
+		};
+
+		match (total, phase) {
+			(Some(total), Some(phase)) => Some(if phase.0 < total.0 { phase } else { total }),
+			(total, phase) => total.or(phase),
+		}
+	}
+}
+
+impl PartialCapture {
+	fn add(&mut self, event: &InterStreamEvent) {
+		match event {
+			InterStreamEvent::Chunk(text) if self.content => {
+				self.text.get_or_insert_with(String::new).push_str(text);
+			}
+			InterStreamEvent::ReasoningChunk(text) if self.reasoning_content => {
+				self.reasoning.get_or_insert_with(String::new).push_str(text);
+			}
+			InterStreamEvent::ThoughtSignatureChunk(signature) if self.content || self.tool_calls => {
+				self.thought_signatures.push(signature.clone());
+			}
+			InterStreamEvent::ToolCallStart(start) if self.tool_calls => {
+				let tool_call = ToolCall {
+					call_id: start.call_id.clone(),
+					fn_name: start.fn_name.clone(),
+					fn_arguments: Value::Null,
+					thought_signatures: None,
+				};
+				self.started_tool_calls.push((tool_call, String::new()));
+			}
+			InterStreamEvent::ToolCallArgsDelta(delta) if self.tool_calls => {
+				if let Some((_, args)) = self
+					.started_tool_calls
+					.iter_mut()
+					.rev()
+					.find(|(tool_call, _)| tool_call.call_id == delta.call_id)
+				{
+					args.push_str(&delta.delta);
+				}
+			}
+			// NOTE: Some streamers send a chunk per arguments delta (with the arguments so far), so the last one replaces the others
+			InterStreamEvent::ToolCallChunk(tool_call) if self.tool_calls => {
+				self.started_tool_calls
+					.retain(|(started, _)| started.call_id != tool_call.call_id);
+				match self
+					.tool_call_list
+					.iter_mut()
+					.find(|captured| captured.call_id == tool_call.call_id)
+				{
+					Some(captured) => *captured = tool_call.clone(),
+					None => self.tool_call_list.push(tool_call.clone()),
+				}
+			}
+			_ => (),
+		}
+	}
+
+	fn take_end(&mut self) -> InterStreamEnd {
+		let thought_signatures = std::mem::take(&mut self.thought_signatures);
+		let started_tool_calls = self.started_tool_calls.drain(..).map(|(mut tool_call, args)| {
+			tool_call.fn_arguments = Value::String(args);
+			tool_call
+		});
+		let tool_calls: Vec<ToolCall> = std::mem::take(&mut self.tool_call_list)
+			.into_iter()
+			.chain(started_tool_calls)
+			.map(|mut tool_call| {
+				// NOTE: Resilient, the arguments of an in-progress tool call are usually not valid JSON yet, so keep the string
+				if let Value::String(args) = tool_call.fn_arguments {
+					tool_call.fn_arguments = if args.is_empty() {
+						Value::Object(Default::default())
+					} else {
+						serde_json::from_str(&args).unwrap_or(Value::String(args))
+					};
+				}
+				tool_call
+			})
+			.collect();
+		InterStreamEnd {
+			captured_usage: None,
+			captured_text_content: self.text.take(),
+			captured_reasoning_content: self.reasoning.take(),
+			captured_tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+			captured_thought_signatures: (!thought_signatures.is_empty()).then_some(thought_signatures),
+			captured_citations: None,
+		}
+	}
+}
+
+// endregion: --- StreamControl
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Client;
+	use crate::adapter::{MockScript, MockStreamItem};
+	use crate::chat::{ChatOptions, ChatRequest, ChatStreamEvent, ToolCallArgsDelta, ToolCallStart};
+	use crate::resolver::AuthData;
+	use crate::webc::{HttpRequest, HttpResponse, HttpStreamResponse, HttpTransport, TransportFuture};
+	use bytes::Bytes;
+	use futures::StreamExt;
+	use reqwest::StatusCode;
+	use reqwest::header::HeaderMap;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	const MODEL: &str = "mock::test-model";
+
+	/// A transport streaming the OpenAI SSE chunks, and then waiting (the stream never ends).
+	#[derive(Debug)]
+	struct PendingSseTransport {
+		chunks: Vec<&'static str>,
+	}
+
+	impl HttpTransport for PendingSseTransport {
+		fn send(&self, _request: HttpRequest) -> TransportFuture<'_, HttpResponse> {
+			unimplemented!("only streaming")
+		}
+
+		fn send_stream(&self, _request: HttpRequest) -> TransportFuture<'_, HttpStreamResponse> {
+			let chunks = self.chunks.iter().map(|chunk| Ok(Bytes::from(format!("data: {chunk}\n\n"))));
+			let body = futures::stream::iter(chunks.collect::<Vec<_>>()).chain(futures::stream::pending());
+			Box::pin(async move {
+				Ok(HttpStreamResponse {
+					status: StatusCode::OK,
+					headers: HeaderMap::new(),
+					body: Box::pin(body),
+				})
+			})
+		}
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_stream_control_timeouts() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		let chunk = |text: &str| MockStreamItem::Chunk(text.to_string());
+		let delay = |millis| MockStreamItem::Delay(Duration::from_millis(millis));
+		mock.push_stream([delay(500), chunk("late")])
+			.push_stream([chunk("Hello"), delay(500), chunk("late")])
+			.push_stream([delay(500), chunk("late")]);
+		let client = Client::builder().with_mock_script(mock).build();
+		let options = ChatOptions::default()
+			.with_first_token_timeout(Duration::from_millis(100))
+			.with_idle_timeout(Duration::from_millis(200))
+			.with_timeout(Duration::from_millis(300));
+		let chat_req = ChatRequest::from_user("Hi");
+
+		// -- Exec & Check - first token
+		let mut stream = client.exec_chat_stream(MODEL, chat_req.clone(), Some(&options)).await?.stream;
+		assert!(matches!(stream.next().await, Some(Ok(ChatStreamEvent::Start))));
+		let res = stream.next().await;
+		assert!(matches!(
+			res,
+			Some(Err(Error::Timeout {
+				phase: TimeoutPhase::FirstToken,
+				..
+			}))
+		));
+		assert!(stream.next().await.is_none());
+
+		// -- Exec & Check - idle
+		let mut stream = client.exec_chat_stream(MODEL, chat_req.clone(), Some(&options)).await?.stream;
+		assert!(matches!(stream.next().await, Some(Ok(ChatStreamEvent::Start))));
+		assert!(matches!(stream.next().await, Some(Ok(ChatStreamEvent::BlockStart(_)))));
+		assert!(matches!(stream.next().await, Some(Ok(ChatStreamEvent::Chunk(_)))));
+		let res = stream.next().await;
+		assert!(matches!(
+			res,
+			Some(Err(Error::Timeout {
+				phase: TimeoutPhase::Idle,
+				..
+			}))
+		));
+
+		// -- Exec & Check - total (not streamed)
+		let res = client.exec_chat(MODEL, chat_req, Some(&options)).await;
+		assert!(matches!(
+			res,
+			Err(Error::Timeout {
+				phase: TimeoutPhase::Total,
+				..
+			})
+		));
+
+		Ok(())
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_stream_control_cancel_partial_end() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		mock.push_stream([
+			MockStreamItem::Chunk("Hello".to_string()),
+			MockStreamItem::Delay(Duration::from_secs(60)),
+			MockStreamItem::Chunk(" world".to_string()),
+		]);
+		let client = Client::builder().with_mock_script(mock).build();
+		let options = ChatOptions::default().with_capture_content(true);
+
+		// -- Exec
+		let res = client
+			.exec_chat_stream(MODEL, ChatRequest::from_user("Hi"), Some(&options))
+			.await?;
+		let cancel_handle = res.cancel_handle();
+		let mut stream = res.stream;
+		assert!(matches!(stream.next().await, Some(Ok(ChatStreamEvent::Start))));
+		assert!(matches!(stream.next().await, Some(Ok(ChatStreamEvent::BlockStart(_)))));
+		assert!(matches!(stream.next().await, Some(Ok(ChatStreamEvent::Chunk(_)))));
+		tokio::spawn(async move {
+			tokio::time::sleep(Duration::from_secs(1)).await;
+			cancel_handle.cancel();
+		});
+
+		// -- Check
+		assert!(matches!(stream.next().await, Some(Ok(ChatStreamEvent::BlockStop(_)))));
+		let Some(Ok(ChatStreamEvent::End(stream_end))) = stream.next().await else {
+			return Err("Should have a partial stream end".into());
+		};
+		assert_eq!(stream_end.captured_first_text(), Some("Hello"));
+		assert!(stream.next().await.is_none());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_stream_control_partial_capture_tool_calls() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut capture = PartialCapture {
+			tool_calls: true,
+			..Default::default()
+		};
+		let start = |call_id: &str, fn_name: &str| {
+			InterStreamEvent::ToolCallStart(ToolCallStart {
+				call_id: call_id.to_string(),
+				fn_name: fn_name.to_string(),
+				index: 0,
+			})
+		};
+		let delta = |call_id: &str, delta: &str| {
+			InterStreamEvent::ToolCallArgsDelta(ToolCallArgsDelta {
+				call_id: call_id.to_string(),
+				delta: delta.to_string(),
+			})
+		};
+		let done_call = ToolCall {
+			call_id: "call_1".to_string(),
+			fn_name: "get_weather".to_string(),
+			fn_arguments: json!({"city": "Paris"}),
+			thought_signatures: None,
+		};
+
+		// -- Exec
+		capture.add(&start("call_1", "get_weather"));
+		capture.add(&delta("call_1", r#"{"city": "Paris"}"#));
+		capture.add(&InterStreamEvent::ToolCallChunk(done_call));
+		capture.add(&start("call_2", "get_time"));
+		capture.add(&delta("call_2", r#"{"zone": "Eu"#));
+		let end = capture.take_end();
+
+		// -- Check
+		let tool_calls = end.captured_tool_calls.ok_or("Should have captured tool calls")?;
+		assert_eq!(tool_calls.len(), 2);
+		assert_eq!(tool_calls[0].fn_arguments, json!({"city": "Paris"}));
+		assert_eq!(tool_calls[1].call_id, "call_2");
+		assert_eq!(tool_calls[1].fn_name, "get_time");
+		assert_eq!(tool_calls[1].fn_arguments, json!(r#"{"zone": "Eu"#));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_stream_control_cancel_openai_tool_call() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport = PendingSseTransport {
+			chunks: vec![
+				r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_1","function":{"name":"get_weather","arguments":""}}]}}]}"#,
+				r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":"}}]}}]}"#,
+				r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]}}]}"#,
+			],
+		};
+		let client = Client::builder()
+			.with_auth_resolver_fn(|_| Ok(Some(AuthData::from_single("sk-test"))))
+			.with_transport(transport)
+			.build();
+		let options = ChatOptions::default().with_capture_tool_calls(true);
+
+		// -- Exec
+		let res = client
+			.exec_chat_stream(
+				"gpt-4o-mini",
+				ChatRequest::from_user("Weather in Paris?"),
+				Some(&options),
+			)
+			.await?;
+		let cancel_handle = res.cancel_handle();
+		let mut stream = res.stream;
+		let mut chunk_count = 0;
+		let mut stream_end = None;
+		while let Some(event) = stream.next().await {
+			match event? {
+				// Cancelled before the end of the tool call (the stream does not finish the choice)
+				ChatStreamEvent::ToolCallChunk(_) => {
+					chunk_count += 1;
+					if chunk_count == 3 {
+						cancel_handle.cancel();
+					}
+				}
+				ChatStreamEvent::End(end) => stream_end = Some(end),
+				_ => (),
+			}
+		}
+
+		// -- Check
+		let tool_calls = stream_end
+			.and_then(|end| end.captured_into_tool_calls())
+			.ok_or("Should have captured tool calls")?;
+		assert_eq!(tool_calls.len(), 1);
+		assert_eq!(tool_calls[0].call_id, "call_1");
+		assert_eq!(tool_calls[0].fn_name, "get_weather");
+		assert_eq!(tool_calls[0].fn_arguments, json!({"city": "Paris"}));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,149 @@
+use crate::adapter::inter_stream::InterStreamEvent;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::time::Instant;
+
+// region:    --- StreamMetrics
+
+/// The timings of a chat stream (in `StreamEnd.metrics`).
+///
+/// The durations are from the first poll of the stream (when the request is sent).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreamMetrics {
+	/// The time to the first stream event (e.g., the response headers for SSE streams).
+	pub time_to_first_byte: Option<Duration>,
+
+	/// The time to the first content event (text, reasoning, or tool call).
+	pub time_to_first_token: Option<Duration>,
+
+	/// The time to the stream end.
+	pub total_duration: Duration,
+
+	/// The output tokens per second, from the first token to the stream end.
+	/// Requires the output tokens (`Usage` events, or `ChatOptions.capture_usage`).
+	pub output_tokens_per_sec: Option<f64>,
+}
+
+// endregion: --- StreamMetrics
+
+// region:    --- MetricsRecorder
+
+/// Records the timings of a `ChatStream` for its `StreamMetrics`.
+#[derive(Debug, Default)]
+pub(crate) struct MetricsRecorder {
+	start: Option<Instant>,
+	first_byte_at: Option<Instant>,
+	first_token_at: Option<Instant>,
+	completion_tokens: Option<i32>,
+}
+
+impl MetricsRecorder {
+	/// Starts the timings on the first poll.
+	pub(crate) fn on_poll(&mut self) {
+		self.start.get_or_insert_with(Instant::now);
+	}
+
+	pub(crate) fn on_event(&mut self, event: &InterStreamEvent) {
+		let now = Instant::now();
+		self.first_byte_at.get_or_insert(now);
+
+		match event {
+			InterStreamEvent::Start => (),
+			InterStreamEvent::Usage(usage) => {
+				self.completion_tokens = usage.completion_tokens.or(self.completion_tokens);
+			}
+			InterStreamEvent::End(end) => {
+				if let Some(usage) = &end.captured_usage {
+					self.completion_tokens = usage.completion_tokens.or(self.completion_tokens);
+				}
+			}
+			_ => {
+				self.first_token_at.get_or_insert(now);
+			}
+		}
+	}
+
+	/// Returns the metrics of the stream so far (at the stream end).
+	pub(crate) fn metrics(&self) -> StreamMetrics {
+		let now = Instant::now();
+		let start = self.start.unwrap_or(now);
+		let since_start = |at: Option<Instant>| at.map(|at| at.duration_since(start));
+
+		let output_tokens_per_sec = match (self.first_token_at, self.completion_tokens) {
+			(Some(first_token_at), Some(tokens)) => {
+				let generation_secs = now.duration_since(first_token_at).as_secs_f64();
+				(generation_secs > 0.0).then(|| tokens as f64 / generation_secs)
+			}
+			_ => None,
+		};
+
+		StreamMetrics {
+			time_to_first_byte: since_start(self.first_byte_at),
+			time_to_first_token: since_start(self.first_token_at),
+			total_duration: now.duration_since(start),
+			output_tokens_per_sec,
+		}
+	}
+}
+
+// endregion: --- MetricsRecorder
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use crate::Client;
+	use crate::adapter::{MockScript, MockStreamItem};
+	use crate::chat::{ChatRequest, ChatStreamEvent, Usage};
+	use futures::StreamExt;
+	use std::time::Duration;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test(start_paused = true)]
+	async fn test_chat_stream_metrics_with_usage_events() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		let usage = Usage {
+			completion_tokens: Some(10),
+			..Default::default()
+		};
+		mock.push_stream([
+			MockStreamItem::Delay(Duration::from_millis(100)),
+			MockStreamItem::Chunk("Hello".to_string()),
+			MockStreamItem::Usage(usage),
+			MockStreamItem::Delay(Duration::from_millis(900)),
+			MockStreamItem::Chunk(" world".to_string()),
+		]);
+		let client = Client::builder().with_mock_script(mock).build();
+
+		// -- Exec
+		let mut stream = client
+			.exec_chat_stream("mock::test-model", ChatRequest::from_user("Hi"), None)
+			.await?
+			.stream;
+		let mut usage_events: Vec<Usage> = Vec::new();
+		let mut stream_end = None;
+		while let Some(event) = stream.next().await {
+			match event? {
+				ChatStreamEvent::Usage(usage) => usage_events.push(usage),
+				ChatStreamEvent::End(end) => stream_end = Some(end),
+				_ => (),
+			}
+		}
+
+		// -- Check
+		assert_eq!(usage_events.len(), 1);
+		assert_eq!(usage_events[0].completion_tokens, Some(10));
+		let metrics = stream_end.and_then(|end| end.metrics).ok_or("Should have metrics")?;
+		assert_eq!(metrics.time_to_first_byte, Some(Duration::ZERO));
+		assert_eq!(metrics.time_to_first_token, Some(Duration::from_millis(100)));
+		assert_eq!(metrics.total_duration, Duration::from_millis(1000));
+		let tokens_per_sec = metrics.output_tokens_per_sec.ok_or("Should have output tokens per sec")?;
+		assert!((tokens_per_sec - 10.0 / 0.9).abs() < 0.01);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
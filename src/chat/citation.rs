@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// A citation of the response text, with the sources that support it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Citation {
+	/// Start offset (in characters) of the cited span in the response text, when provided.
+	pub start: Option<usize>,
+
+	/// End offset (in characters, exclusive) of the cited span in the response text, when provided.
+	pub end: Option<usize>,
+
+	/// The cited span of the response text, when provided.
+	pub text: Option<String>,
+
+	/// The sources supporting this citation.
+	pub sources: Vec<CitationSource>,
+}
+
+/// A source of a [`Citation`] (e.g., a grounding document, a tool result, or a web page).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CitationSource {
+	/// Source id (e.g., the `ChatDocument` id or the tool call id).
+	pub id: Option<String>,
+
+	/// Source url, when the source is a web page.
+	pub url: Option<String>,
+
+	/// Source title, when available.
+	pub title: Option<String>,
+
+	/// Relevant text of the source, when available.
+	pub snippet: Option<String>,
+}
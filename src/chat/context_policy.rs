@@ -0,0 +1,429 @@
+//! Context-window management: trims the `ChatRequest.messages` before sending the request.
+//!
+//! Set it with `ChatOptions::with_context_policy(...)` (per call, or as the client default),
+//! or apply it manually with `ContextPolicy::apply(...)`.
+
+use crate::ModelSpec;
+use crate::chat::token_count::{estimate_input_tokens, estimate_message_tokens};
+use crate::chat::{ChatMessage, ChatRequest, ChatRole, ContentPart};
+use std::sync::Arc;
+
+const DEFAULT_SUMMARY_INSTRUCTION: &str = "Summarize the following conversation excerpt concisely. \
+Keep the facts, decisions, open questions, and tool results that may be needed to continue the conversation.";
+
+// region:    --- ContextPolicy
+
+/// The trimming rules of the chat request messages.
+///
+/// Rules:
+/// - The system messages (and `ChatRequest.system`) and the pinned messages (`ChatMessage::with_pinned`) are always kept.
+/// - The last turn (from its user message to the last message, e.g., the tool calls of an agent loop) is always kept.
+/// - An assistant tool call message and its tool response messages are kept or dropped together.
+/// - `max_turns` keeps the last N turns (a turn starts at a user message).
+/// - `max_tokens` then drops the oldest messages until the request estimate is under the budget
+///   (best effort: the kept messages above can still be over it).
+/// - The kept messages (other than the system and pinned ones) start at a user message.
+///
+/// The dropped messages can be summarized by a secondary model (see `with_summarizer`),
+/// the summary being inserted as a system message in place of the dropped ones.
+#[derive(Debug, Clone, Default)]
+pub struct ContextPolicy {
+	/// Keep the last N turns.
+	pub max_turns: Option<usize>,
+
+	/// The input token budget (system, tools, and messages).
+	pub max_tokens: Option<u32>,
+
+	/// The token counter of the messages (default: the local estimate, from `ChatMessage::size()`).
+	pub token_counter: Option<MessageTokenCounter>,
+
+	/// The summarizer of the dropped messages (only applied by the `Client`).
+	pub summarizer: Option<ContextSummarizer>,
+}
+
+/// Chainable Setters
+impl ContextPolicy {
+	pub fn with_max_turns(mut self, value: usize) -> Self {
+		self.max_turns = Some(value);
+		self
+	}
+
+	pub fn with_max_tokens(mut self, value: u32) -> Self {
+		self.max_tokens = Some(value);
+		self
+	}
+
+	/// Sets a custom message token counter (e.g., a real tokenizer).
+	pub fn with_token_counter_fn(mut self, counter_fn: impl Fn(&ChatMessage) -> u32 + Send + Sync + 'static) -> Self {
+		self.token_counter = Some(MessageTokenCounter(Arc::new(counter_fn)));
+		self
+	}
+
+	pub fn with_summarizer(mut self, summarizer: ContextSummarizer) -> Self {
+		self.summarizer = Some(summarizer);
+		self
+	}
+}
+
+/// The result of `ContextPolicy::apply(...)`.
+#[derive(Debug, Clone)]
+pub struct ContextTrim {
+	/// The chat request with the kept messages.
+	pub chat_req: ChatRequest,
+
+	/// The dropped messages, in their original order.
+	pub dropped: Vec<ChatMessage>,
+
+	/// The index (in the kept messages) where the dropped messages were, to insert a summary.
+	pub dropped_index: usize,
+}
+
+impl ContextPolicy {
+	/// Trims the messages of the chat request (see `ContextPolicy` for the rules).
+	pub fn apply(&self, chat_req: ChatRequest) -> ContextTrim {
+		let ChatRequest {
+			system,
+			messages,
+			tools,
+			documents,
+		} = chat_req;
+
+		let groups = group_messages(&messages);
+		let mut keep: Vec<bool> = vec![true; groups.len()];
+
+		// -- Protected groups
+		let last_turn_start = groups.iter().rposition(|g| g.is_turn_start(&messages));
+		let protected: Vec<bool> = groups
+			.iter()
+			.enumerate()
+			.map(|(idx, group)| {
+				group.is_kept_always(&messages)
+					|| last_turn_start.is_some_and(|start| idx >= start)
+					|| idx + 1 == groups.len()
+			})
+			.collect();
+
+		// -- Max turns
+		if let Some(max_turns) = self.max_turns {
+			let turn_starts: Vec<usize> =
+				(0..groups.len()).filter(|&idx| groups[idx].is_turn_start(&messages)).collect();
+			if turn_starts.len() > max_turns {
+				// When max_turns is 0, the last turn is still kept (protected)
+				let first_kept = turn_starts[turn_starts.len() - max_turns.max(1)];
+				for idx in 0..first_kept {
+					if !protected[idx] {
+						keep[idx] = false;
+					}
+				}
+			}
+		}
+
+		// -- Max tokens
+		if let Some(max_tokens) = self.max_tokens {
+			let fixed_req = ChatRequest {
+				system: system.clone(),
+				messages: Vec::new(),
+				tools: tools.clone(),
+				documents: documents.clone(),
+			};
+			let fixed_tokens = estimate_input_tokens(&fixed_req) as usize;
+			let group_tokens: Vec<usize> = groups
+				.iter()
+				.map(|group| group.range().map(|idx| self.count_message_tokens(&messages[idx])).sum())
+				.collect();
+
+			let mut total: usize =
+				fixed_tokens + (0..groups.len()).filter(|&i| keep[i]).map(|i| group_tokens[i]).sum::<usize>();
+
+			for idx in 0..groups.len() {
+				if total <= max_tokens as usize {
+					break;
+				}
+				if keep[idx] && !protected[idx] {
+					keep[idx] = false;
+					total -= group_tokens[idx];
+				}
+			}
+		}
+
+		// -- Start the kept conversation at a user message (drop the leading orphan groups)
+		if keep.iter().zip(protected.iter()).any(|(k, p)| !k && !p) {
+			for idx in 0..groups.len() {
+				if !keep[idx] {
+					continue;
+				}
+				let is_turn_start = groups[idx].is_turn_start(&messages);
+				// Pinned user messages do not count as the start of the kept conversation
+				if is_turn_start && (!protected[idx] || Some(idx) == last_turn_start) {
+					break;
+				}
+				if !protected[idx] {
+					keep[idx] = false;
+				}
+			}
+		}
+
+		// -- Build the result
+		let mut kept = Vec::new();
+		let mut dropped = Vec::new();
+		let mut dropped_index = None;
+		let mut messages: Vec<Option<ChatMessage>> = messages.into_iter().map(Some).collect();
+		for (idx, group) in groups.iter().enumerate() {
+			for msg_idx in group.range() {
+				let Some(msg) = messages[msg_idx].take() else { continue };
+				if keep[idx] {
+					kept.push(msg);
+				} else {
+					dropped_index.get_or_insert(kept.len());
+					dropped.push(msg);
+				}
+			}
+		}
+
+		ContextTrim {
+			dropped_index: dropped_index.unwrap_or(0),
+			chat_req: ChatRequest {
+				system,
+				messages: kept,
+				tools,
+				documents,
+			},
+			dropped,
+		}
+	}
+
+	fn count_message_tokens(&self, msg: &ChatMessage) -> usize {
+		match &self.token_counter {
+			Some(counter) => (counter.0)(msg) as usize,
+			None => estimate_message_tokens(msg),
+		}
+	}
+}
+
+// endregion: --- ContextPolicy
+
+// region:    --- MessageTokenCounter
+
+/// A custom message token counter for a `ContextPolicy`.
+#[derive(Clone)]
+pub struct MessageTokenCounter(Arc<dyn Fn(&ChatMessage) -> u32 + Send + Sync>);
+
+impl std::fmt::Debug for MessageTokenCounter {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("MessageTokenCounter")
+	}
+}
+
+// endregion: --- MessageTokenCounter
+
+// region:    --- ContextSummarizer
+
+/// Summarizes the messages dropped by a `ContextPolicy` with a secondary (usually smaller) model.
+#[derive(Debug, Clone)]
+pub struct ContextSummarizer {
+	pub model: ModelSpec,
+	pub instruction: Option<String>,
+}
+
+impl ContextSummarizer {
+	pub fn new(model: impl Into<ModelSpec>) -> Self {
+		Self {
+			model: model.into(),
+			instruction: None,
+		}
+	}
+
+	/// Sets the summary instruction (system prompt).
+	pub fn with_instruction(mut self, instruction: impl Into<String>) -> Self {
+		self.instruction = Some(instruction.into());
+		self
+	}
+}
+
+impl ContextSummarizer {
+	/// The chat request to summarize the dropped messages (rendered as a transcript).
+	pub(crate) fn to_summary_request(&self, dropped: &[ChatMessage]) -> ChatRequest {
+		let mut transcript = String::new();
+		for msg in dropped {
+			for part in msg.content.parts() {
+				let line = match part {
+					ContentPart::Text(text) => text.to_string(),
+					ContentPart::ToolCall(tool_call) => {
+						format!("[tool call: {}({})]", tool_call.fn_name, tool_call.fn_arguments)
+					}
+					ContentPart::ToolResponse(tool_response) => format!("[tool response: {}]", tool_response.content),
+					ContentPart::Binary(binary) => format!("[attachment: {}]", binary.content_type),
+					ContentPart::ThoughtSignature(_) | ContentPart::Custom(_) => continue,
+				};
+				transcript.push_str(&format!("{}: {line}\n", msg.role));
+			}
+		}
+
+		let instruction = self.instruction.as_deref().unwrap_or(DEFAULT_SUMMARY_INSTRUCTION);
+		ChatRequest::from_system(instruction).append_message(ChatMessage::user(transcript))
+	}
+
+	/// The message replacing the dropped messages.
+	pub(crate) fn to_summary_message(summary: &str) -> ChatMessage {
+		ChatMessage::system(format!("Summary of the earlier conversation:\n{summary}"))
+	}
+}
+
+// endregion: --- ContextSummarizer
+
+// region:    --- Support
+
+/// A message, or an assistant tool call message with its tool response messages.
+struct MessageGroup {
+	start: usize,
+	end: usize,
+}
+
+impl MessageGroup {
+	fn range(&self) -> std::ops::Range<usize> {
+		self.start..self.end
+	}
+
+	fn is_turn_start(&self, messages: &[ChatMessage]) -> bool {
+		let msg = &messages[self.start];
+		msg.role == ChatRole::User && msg.content.tool_responses().is_empty()
+	}
+
+	fn is_kept_always(&self, messages: &[ChatMessage]) -> bool {
+		messages[self.range()]
+			.iter()
+			.any(|msg| msg.role == ChatRole::System || msg.is_pinned())
+	}
+}
+
+fn group_messages(messages: &[ChatMessage]) -> Vec<MessageGroup> {
+	let is_tool_response = |msg: &ChatMessage| msg.role == ChatRole::Tool || !msg.content.tool_responses().is_empty();
+
+	let mut groups: Vec<MessageGroup> = Vec::new();
+	for (idx, msg) in messages.iter().enumerate() {
+		match groups.last_mut() {
+			// Attach the tool responses to the previous group (the tool calls)
+			Some(last) if is_tool_response(msg) => last.end = idx + 1,
+			_ => groups.push(MessageGroup {
+				start: idx,
+				end: idx + 1,
+			}),
+		}
+	}
+	groups
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chat::{ToolCall, ToolResponse};
+	use serde_json::json;
+
+	fn tool_call_messages(call_id: &str) -> Vec<ChatMessage> {
+		let tool_call = ToolCall {
+			call_id: call_id.to_string(),
+			fn_name: "get_weather".to_string(),
+			fn_arguments: json!({"city": "Paris"}),
+			thought_signatures: None,
+		};
+		vec![
+			ChatMessage::from(vec![tool_call]),
+			ChatMessage::from(ToolResponse::new(call_id, "x".repeat(400))),
+		]
+	}
+
+	fn seed_messages() -> Vec<ChatMessage> {
+		let mut messages = vec![
+			ChatMessage::system("Be concise"),
+			ChatMessage::user("turn 1").with_pinned(true),
+			ChatMessage::assistant("answer 1"),
+			ChatMessage::user("turn 2"),
+		];
+		messages.extend(tool_call_messages("call_a"));
+		messages.push(ChatMessage::assistant("answer 2"));
+		messages.push(ChatMessage::user("turn 3"));
+		messages.extend(tool_call_messages("call_b"));
+		messages.extend(tool_call_messages("call_c"));
+		messages
+	}
+
+	#[test]
+	fn test_context_policy_max_turns() {
+		// -- Exec
+		let trim = ContextPolicy::default()
+			.with_max_turns(1)
+			.apply(ChatRequest::new(seed_messages()));
+
+		// -- Check (system and pinned kept, turn 2 dropped with its tool call and response)
+		let kept = &trim.chat_req.messages;
+		assert_eq!(kept.len(), 2 + 5);
+		assert_eq!(kept[0].role, ChatRole::System);
+		assert!(kept[1].is_pinned());
+		assert_eq!(kept[2].content.first_text(), Some("turn 3"));
+		assert_eq!(trim.dropped.len(), 5);
+		assert_eq!(trim.dropped_index, 2);
+	}
+
+	#[test]
+	fn test_context_policy_max_tokens_keeps_tool_pairs() {
+		// -- Exec
+		let trim = ContextPolicy::default()
+			.with_max_tokens(150)
+			.apply(ChatRequest::new(seed_messages()));
+
+		// -- Check (each tool response is kept with its tool call)
+		let kept = &trim.chat_req.messages;
+		for (idx, msg) in kept.iter().enumerate() {
+			if !msg.content.tool_responses().is_empty() {
+				assert!(
+					!kept[idx - 1].content.tool_calls().is_empty(),
+					"tool response without its tool call"
+				);
+			}
+		}
+		// turn 3 (the last turn) is kept with its tool call pairs, call_a (turn 2) is dropped
+		assert!(kept.iter().any(|m| m.content.first_text() == Some("turn 3")));
+		let kept_call_ids: Vec<&str> = kept
+			.iter()
+			.flat_map(|m| m.content.tool_calls())
+			.map(|tc| tc.call_id.as_str())
+			.collect();
+		assert_eq!(kept_call_ids, vec!["call_b", "call_c"]);
+	}
+
+	#[test]
+	fn test_context_policy_max_tokens_keeps_last_turn_tool_round_trips() {
+		// -- Setup & Fixtures
+		let mut messages = vec![ChatMessage::user("turn 1"), ChatMessage::assistant("answer 1")];
+		messages.push(ChatMessage::user("turn 2"));
+		messages.extend(tool_call_messages("call_a"));
+		messages.extend(tool_call_messages("call_b"));
+		messages.push(ChatMessage::assistant("almost done"));
+
+		// -- Exec (the last turn alone is over the budget)
+		let trim = ContextPolicy::default().with_max_tokens(10).apply(ChatRequest::new(messages));
+
+		// -- Check (only the previous turn is dropped)
+		let kept = &trim.chat_req.messages;
+		assert_eq!(kept.len(), 6);
+		assert_eq!(kept[0].content.first_text(), Some("turn 2"));
+		let kept_call_ids: Vec<&str> = kept
+			.iter()
+			.flat_map(|m| m.content.tool_calls())
+			.map(|tc| tc.call_id.as_str())
+			.collect();
+		assert_eq!(kept_call_ids, vec!["call_a", "call_b"]);
+		assert_eq!(
+			kept.iter().filter(|m| !m.content.tool_responses().is_empty()).count(),
+			2
+		);
+		assert_eq!(trim.dropped.len(), 2);
+		assert_eq!(trim.dropped_index, 0);
+	}
+}
+
+// endregion: --- Tests
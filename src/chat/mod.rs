@@ -4,27 +4,45 @@
 // region:    --- Modules
 
 mod binary;
+mod chat_document;
 mod chat_message;
 mod chat_options;
 mod chat_req_response_format;
 mod chat_request;
 mod chat_response;
 mod chat_stream;
+mod chat_stream_blocks;
+mod chat_stream_collector;
+mod chat_stream_control;
+mod chat_stream_metrics;
+mod citation;
 mod content_part;
+mod context_policy;
 mod message_content;
+mod partial_json;
+mod token_count;
 mod tool;
 mod usage;
 
 // -- Flatten
 pub use binary::*;
+pub use chat_document::*;
 pub use chat_message::*;
 pub use chat_options::*;
 pub use chat_req_response_format::*;
 pub use chat_request::*;
 pub use chat_response::*;
 pub use chat_stream::*;
+pub use chat_stream_collector::*;
+pub(crate) use chat_stream_control::StreamControl;
+pub use chat_stream_control::{CancelHandle, TimeoutPhase};
+pub use chat_stream_metrics::StreamMetrics;
+pub use citation::*;
 pub use content_part::*;
+pub use context_policy::*;
 pub use message_content::*;
+pub use partial_json::*;
+pub use token_count::*;
 pub use tool::*;
 pub use usage::*;
 
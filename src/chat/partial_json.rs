@@ -0,0 +1,664 @@
+//! Partial JSON support for streamed structured output (e.g., `ChatResponseFormat::JsonSpec` with `exec_chat_stream`).
+
+use crate::chat::{ChatStream, ChatStreamEvent};
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+// region:    --- Partial JSON Parser
+
+/// Parses a possibly incomplete JSON text (e.g., the text streamed so far), and returns the value
+/// of its complete part, or `None` if nothing can be parsed yet (or if the text is not JSON).
+///
+/// The unclosed objects, arrays, and strings are closed, and the incomplete keys, literals, and
+/// number suffixes are dropped. For example, `{"name": "Pa` gives `{"name": "Pa"}`,
+/// and `{"tags": ["a", tr` gives `{"tags": ["a"]}`.
+///
+/// A leading Markdown code fence line (e.g., "```json") is skipped, and the text after the JSON value is ignored.
+pub fn parse_partial_json(text: &str) -> Option<Value> {
+	let start = find_json_start(text)?;
+	let mut parser = PartialJsonParser::default();
+	parser.push(&text[start..]);
+	parser.value()
+}
+
+/// Returns the byte offset of the JSON value, after the leading whitespace and Markdown code fence line.
+/// Returns `None` while not known yet (e.g., only whitespace, or a fence line without its new line).
+fn find_json_start(text: &str) -> Option<usize> {
+	let trimmed = text.trim_start();
+	if trimmed.is_empty() || (trimmed.len() < 3 && "```".starts_with(trimmed)) {
+		return None;
+	}
+	if !trimmed.starts_with("```") {
+		return Some(text.len() - trimmed.len());
+	}
+
+	// -- Fence line (e.g., "```json\n")
+	let (_, after_fence) = trimmed.split_once('\n')?;
+	let json = after_fence.trim_start();
+	if json.is_empty() {
+		return None;
+	}
+	Some(text.len() - json.len())
+}
+
+/// Error for an invalid JSON text (not just incomplete).
+struct InvalidJson;
+
+type ParseResult<T> = core::result::Result<T, InvalidJson>;
+
+/// An incremental parser of a possibly incomplete JSON text, with each pushed char parsed once
+/// (the open containers keep their complete members).
+#[derive(Default)]
+struct PartialJsonParser {
+	/// The open objects and arrays, from the root.
+	stack: Vec<Container>,
+	/// The scalar (or object key) being parsed.
+	token: Option<Token>,
+	/// The root value, once complete (the text after it is ignored).
+	root: Option<Value>,
+	invalid: bool,
+}
+
+enum Container {
+	Object {
+		map: Map<String, Value>,
+		/// The complete key of the value being parsed.
+		key: Option<String>,
+		expect: ObjectExpect,
+	},
+	Array(Vec<Value>),
+}
+
+/// What is expected next in an object.
+#[derive(Clone, Copy)]
+enum ObjectExpect {
+	Key,
+	Colon,
+	Value,
+	Comma,
+}
+
+enum Token {
+	String {
+		text: String,
+		escape: Escape,
+		is_key: bool,
+	},
+	/// The rest of the literal (e.g., `rue` after the `t` of `true`).
+	Literal {
+		rest: &'static str,
+		value: Value,
+	},
+	Number(String),
+}
+
+/// The escape sequence being parsed in a string.
+#[derive(Clone, Copy)]
+enum Escape {
+	None,
+	Backslash,
+	/// The hex digits of a `\u` escape (with the high surrogate for the low surrogate escape).
+	Hex {
+		code: u32,
+		digits: u8,
+		high: Option<u32>,
+	},
+	/// The `\` of the low surrogate escape, after a high surrogate.
+	LowBackslash(u32),
+	/// The `u` of the low surrogate escape.
+	LowU(u32),
+}
+
+impl PartialJsonParser {
+	fn push(&mut self, text: &str) {
+		for c in text.chars() {
+			if self.invalid || self.root.is_some() {
+				return;
+			}
+			if self.push_char(c).is_err() {
+				self.invalid = true;
+			}
+		}
+	}
+
+	/// Returns the value of the text pushed so far (see `parse_partial_json`).
+	fn value(&self) -> Option<Value> {
+		if self.invalid {
+			return None;
+		}
+		if let Some(root) = &self.root {
+			return Some(root.clone());
+		}
+
+		// -- The partial scalar (the incomplete keys and literals are dropped)
+		let mut value = match &self.token {
+			Some(Token::String {
+				text, is_key: false, ..
+			}) => Some(Value::String(text.clone())),
+			Some(Token::Number(number)) => {
+				serde_json::from_str(number.trim_end_matches(['-', '+', '.', 'e', 'E'])).ok()
+			}
+			_ => None,
+		};
+
+		// -- Close the open containers (from the innermost)
+		for container in self.stack.iter().rev() {
+			value = Some(match container {
+				Container::Object { map, key, .. } => {
+					let mut map = map.clone();
+					if let (Some(key), Some(value)) = (key, value) {
+						map.insert(key.clone(), value);
+					}
+					Value::Object(map)
+				}
+				Container::Array(items) => {
+					let mut items = items.clone();
+					items.extend(value);
+					Value::Array(items)
+				}
+			});
+		}
+
+		value
+	}
+
+	fn push_char(&mut self, c: char) -> ParseResult<()> {
+		match self.token.take() {
+			None => self.push_structural(c),
+			Some(Token::String { text, escape, is_key }) => self.push_string_char(text, escape, is_key, c),
+			Some(Token::Literal { rest, value }) => {
+				let rest = rest.strip_prefix(c).ok_or(InvalidJson)?;
+				if rest.is_empty() {
+					self.complete_value(value);
+				} else {
+					self.token = Some(Token::Literal { rest, value });
+				}
+				Ok(())
+			}
+			Some(Token::Number(mut number)) => {
+				if matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E') {
+					number.push(c);
+					self.token = Some(Token::Number(number));
+					return Ok(());
+				}
+				let value = serde_json::from_str(&number).map_err(|_| InvalidJson)?;
+				self.complete_value(value);
+				if self.root.is_some() {
+					return Ok(());
+				}
+				self.push_structural(c)
+			}
+		}
+	}
+
+	/// Pushes a char outside of a scalar (whitespace, punctuation, or the start of a value).
+	fn push_structural(&mut self, c: char) -> ParseResult<()> {
+		if c.is_whitespace() {
+			return Ok(());
+		}
+
+		match self.stack.last_mut() {
+			None => self.start_value(c),
+			Some(Container::Array(_)) => match c {
+				']' => {
+					self.close_container();
+					Ok(())
+				}
+				',' => Ok(()),
+				c => self.start_value(c),
+			},
+			Some(Container::Object { expect, .. }) => match (*expect, c) {
+				(ObjectExpect::Key | ObjectExpect::Comma, '}') => {
+					self.close_container();
+					Ok(())
+				}
+				(ObjectExpect::Key | ObjectExpect::Comma, ',') => {
+					*expect = ObjectExpect::Key;
+					Ok(())
+				}
+				(ObjectExpect::Key | ObjectExpect::Comma, '"') => {
+					self.token = Some(Token::String {
+						text: String::new(),
+						escape: Escape::None,
+						is_key: true,
+					});
+					Ok(())
+				}
+				(ObjectExpect::Colon, ':') => {
+					*expect = ObjectExpect::Value;
+					Ok(())
+				}
+				(ObjectExpect::Value, c) => self.start_value(c),
+				_ => Err(InvalidJson),
+			},
+		}
+	}
+
+	fn start_value(&mut self, c: char) -> ParseResult<()> {
+		let token = match c {
+			'{' => {
+				self.stack.push(Container::Object {
+					map: Map::new(),
+					key: None,
+					expect: ObjectExpect::Key,
+				});
+				return Ok(());
+			}
+			'[' => {
+				self.stack.push(Container::Array(Vec::new()));
+				return Ok(());
+			}
+			'"' => Token::String {
+				text: String::new(),
+				escape: Escape::None,
+				is_key: false,
+			},
+			't' => Token::Literal {
+				rest: "rue",
+				value: Value::Bool(true),
+			},
+			'f' => Token::Literal {
+				rest: "alse",
+				value: Value::Bool(false),
+			},
+			'n' => Token::Literal {
+				rest: "ull",
+				value: Value::Null,
+			},
+			'-' | '0'..='9' => Token::Number(c.to_string()),
+			_ => return Err(InvalidJson),
+		};
+		self.token = Some(token);
+		Ok(())
+	}
+
+	fn push_string_char(&mut self, mut text: String, escape: Escape, is_key: bool, c: char) -> ParseResult<()> {
+		let escape = match (escape, c) {
+			(Escape::None, '"') => {
+				self.complete_string(text, is_key);
+				return Ok(());
+			}
+			(Escape::None, '\\') => Escape::Backslash,
+			(Escape::None, c) => {
+				text.push(c);
+				Escape::None
+			}
+			(Escape::Backslash, 'u') => Escape::Hex {
+				code: 0,
+				digits: 0,
+				high: None,
+			},
+			(Escape::Backslash, c) => {
+				text.push(unescape_char(c)?);
+				Escape::None
+			}
+			(Escape::Hex { code, digits, high }, c) => {
+				let code = code * 16 + c.to_digit(16).ok_or(InvalidJson)?;
+				match (digits, high) {
+					(0..3, _) => Escape::Hex {
+						code,
+						digits: digits + 1,
+						high,
+					},
+					// -- Surrogate pair
+					(_, None) if (0xD800..0xDC00).contains(&code) => Escape::LowBackslash(code),
+					(_, None) => {
+						text.push(char::from_u32(code).ok_or(InvalidJson)?);
+						Escape::None
+					}
+					(_, Some(high)) => {
+						if !(0xDC00..0xE000).contains(&code) {
+							return Err(InvalidJson);
+						}
+						let code = 0x10000 + ((high - 0xD800) << 10) + (code - 0xDC00);
+						text.push(char::from_u32(code).ok_or(InvalidJson)?);
+						Escape::None
+					}
+				}
+			}
+			(Escape::LowBackslash(high), '\\') => Escape::LowU(high),
+			(Escape::LowU(high), 'u') => Escape::Hex {
+				code: 0,
+				digits: 0,
+				high: Some(high),
+			},
+			(Escape::LowBackslash(_) | Escape::LowU(_), _) => return Err(InvalidJson),
+		};
+
+		// The string is not closed yet (an incomplete escape is not in the partial value)
+		self.token = Some(Token::String { text, escape, is_key });
+		Ok(())
+	}
+
+	fn complete_string(&mut self, text: String, is_key: bool) {
+		if !is_key {
+			self.complete_value(Value::String(text));
+		} else if let Some(Container::Object { key, expect, .. }) = self.stack.last_mut() {
+			*key = Some(text);
+			*expect = ObjectExpect::Colon;
+		}
+	}
+
+	fn close_container(&mut self) {
+		let value = match self.stack.pop() {
+			Some(Container::Object { map, .. }) => Value::Object(map),
+			Some(Container::Array(items)) => Value::Array(items),
+			None => return,
+		};
+		self.complete_value(value);
+	}
+
+	/// Adds a complete value to its container (or sets the root value).
+	fn complete_value(&mut self, value: Value) {
+		match self.stack.last_mut() {
+			None => self.root = Some(value),
+			Some(Container::Array(items)) => items.push(value),
+			Some(Container::Object { map, key, expect }) => {
+				if let Some(key) = key.take() {
+					map.insert(key, value);
+				}
+				*expect = ObjectExpect::Comma;
+			}
+		}
+	}
+}
+
+fn unescape_char(c: char) -> ParseResult<char> {
+	match c {
+		'"' => Ok('"'),
+		'\\' => Ok('\\'),
+		'/' => Ok('/'),
+		'b' => Ok('\u{08}'),
+		'f' => Ok('\u{0C}'),
+		'n' => Ok('\n'),
+		'r' => Ok('\r'),
+		't' => Ok('\t'),
+		_ => Err(InvalidJson),
+	}
+}
+
+// endregion: --- Partial JSON Parser
+
+// region:    --- PartialJsonStream
+
+/// A stream of progressively more complete JSON values, parsed from the text chunks of a `ChatStream`
+/// (see `ChatStream::into_partial_json()`).
+///
+/// A value is returned each time a chunk changes the parsed value (see `parse_partial_json`).
+/// The text is parsed incrementally, so each chunk is only parsed once.
+/// The other stream events (reasoning, tool calls, end, ...) are skipped.
+pub struct PartialJsonStream {
+	stream: ChatStream,
+	text: String,
+	/// The byte offset of the text parsed so far (once the JSON start is known).
+	parsed_len: Option<usize>,
+	parser: PartialJsonParser,
+	last_value: Option<Value>,
+}
+
+impl PartialJsonStream {
+	pub fn new(stream: ChatStream) -> Self {
+		Self {
+			stream,
+			text: String::new(),
+			parsed_len: None,
+			parser: PartialJsonParser::default(),
+			last_value: None,
+		}
+	}
+
+	/// Returns a stream of typed partial values (see `PartialTypedStream`).
+	pub fn into_typed<T: DeserializeOwned>(self) -> PartialTypedStream<T> {
+		PartialTypedStream {
+			inner: self,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Returns the text received so far.
+	pub fn text(&self) -> &str {
+		&self.text
+	}
+
+	/// Returns the last value returned, if any.
+	pub fn last_value(&self) -> Option<&Value> {
+		self.last_value.as_ref()
+	}
+}
+
+impl Stream for PartialJsonStream {
+	type Item = crate::Result<Value>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+
+		loop {
+			match Pin::new(&mut this.stream).poll_next(cx) {
+				Poll::Ready(Some(Ok(ChatStreamEvent::Chunk(chunk)))) => {
+					this.text.push_str(&chunk.content);
+					let Some(parsed_len) = this.parsed_len.or_else(|| find_json_start(&this.text)) else {
+						continue;
+					};
+					this.parser.push(&this.text[parsed_len..]);
+					this.parsed_len = Some(this.text.len());
+					if let Some(value) = this.parser.value()
+						&& this.last_value.as_ref() != Some(&value)
+					{
+						this.last_value = Some(value.clone());
+						return Poll::Ready(Some(Ok(value)));
+					}
+				}
+				Poll::Ready(Some(Ok(_))) => (),
+				Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+				Poll::Ready(None) => return Poll::Ready(None),
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+	}
+}
+
+/// A stream of typed partial values, deserialized from the `PartialJsonStream` values.
+///
+/// The values that do not deserialize yet are skipped, so the partial type should be tolerant
+/// (e.g., `Option` fields, or `#[serde(default)]`).
+pub struct PartialTypedStream<T> {
+	inner: PartialJsonStream,
+	_marker: PhantomData<fn() -> T>,
+}
+
+impl<T> PartialTypedStream<T> {
+	/// Returns the text received so far.
+	pub fn text(&self) -> &str {
+		self.inner.text()
+	}
+}
+
+impl<T: DeserializeOwned> Stream for PartialTypedStream<T> {
+	type Item = crate::Result<T>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+
+		loop {
+			match Pin::new(&mut this.inner).poll_next(cx) {
+				Poll::Ready(Some(Ok(value))) => {
+					if let Ok(typed) = serde_json::from_value(value) {
+						return Poll::Ready(Some(Ok(typed)));
+					}
+				}
+				Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+				Poll::Ready(None) => return Poll::Ready(None),
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+	}
+}
+
+/// Partial JSON
+impl ChatStream {
+	/// Returns the stream of the progressively more complete JSON values of the text chunks
+	/// (e.g., for `ChatResponseFormat::JsonSpec`).
+	pub fn into_partial_json(self) -> PartialJsonStream {
+		PartialJsonStream::new(self)
+	}
+}
+
+// endregion: --- PartialJsonStream
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Client;
+	use crate::adapter::{MockScript, MockStreamItem};
+	use crate::chat::ChatRequest;
+	use futures::StreamExt;
+	use serde::Deserialize;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_partial_json_parse() -> Result<()> {
+		// -- Setup & Fixtures
+		let fx_cases = [
+			("", None),
+			("  ", None),
+			("{", Some(json!({}))),
+			(r#"{"na"#, Some(json!({}))),
+			(r#"{"name""#, Some(json!({}))),
+			(r#"{"name": "Pa"#, Some(json!({"name": "Pa"}))),
+			(
+				r#"{"name": "Paris", "tags": ["a", tr"#,
+				Some(json!({"name": "Paris", "tags": ["a"]})),
+			),
+			(r#"{"pop": 2.1e"#, Some(json!({"pop": 2.1}))),
+			(r#"{"pop": -"#, Some(json!({}))),
+			(r#"{"text": "line\"#, Some(json!({"text": "line"}))),
+			(r#"{"text": "caf\u00e"#, Some(json!({"text": "caf"}))),
+			(r#"{"text": "café 😀"}"#, Some(json!({"text": "café 😀"}))),
+			(
+				r#"[{"a": null}, {"b": [1, 2"#,
+				Some(json!([{"a": null}, {"b": [1, 2]}])),
+			),
+			(r#"{"ok": true} trailing"#, Some(json!({"ok": true}))),
+			("Hello", None),
+			(r#"{"a" 1}"#, None),
+			// -- Markdown code fence
+			("```", None),
+			("```json", None),
+			("```json\n", None),
+			("```json\n{\"a\": [1", Some(json!({"a": [1]}))),
+			("\n```\n[true]\n```", Some(json!([true]))),
+			("``{}", None),
+		];
+
+		// -- Exec & Check
+		for (text, expected) in fx_cases {
+			assert_eq!(parse_partial_json(text), expected, "text: {text}");
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_partial_json_parse_by_char() -> Result<()> {
+		// -- Setup & Fixtures
+		let text = r#"{"name": "caf\u00e9 \ud83d\ude00", "pop": -2.1e3, "tags": [true, null, "a\"b"]} trailing"#;
+		let expected = json!({"name": "café 😀", "pop": -2.1e3, "tags": [true, null, "a\"b"]});
+
+		// -- Exec
+		let mut parser = PartialJsonParser::default();
+		let mut values = Vec::new();
+		for (idx, c) in text.char_indices() {
+			parser.push(&text[idx..idx + c.len_utf8()]);
+			// -- Check - same value as the whole text so far
+			let value = parser.value();
+			assert_eq!(value, parse_partial_json(&text[..idx + c.len_utf8()]), "at: {idx}");
+			values.push(value);
+		}
+
+		// -- Check
+		assert_eq!(values.last().cloned().flatten(), Some(expected));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_partial_json_stream_typed() -> Result<()> {
+		// -- Setup & Fixtures
+		#[derive(Debug, Deserialize, PartialEq)]
+		struct City {
+			name: Option<String>,
+			#[serde(default)]
+			tags: Vec<String>,
+		}
+		let mock = MockScript::new();
+		let chunk = |text: &str| MockStreamItem::Chunk(text.to_string());
+		mock.push_stream([
+			chunk(r#"{"na"#),
+			chunk(r#"me": "Pa"#),
+			chunk(r#"ris", "#),
+			chunk(r#""tags": ["capital""#),
+			chunk(r#"]}"#),
+		]);
+		let client = Client::builder().with_mock_script(mock).build();
+
+		// -- Exec
+		let stream = client
+			.exec_chat_stream("mock::test-model", ChatRequest::from_user("City?"), None)
+			.await?
+			.stream;
+		let mut typed_stream = stream.into_partial_json().into_typed::<City>();
+		let mut cities = Vec::new();
+		while let Some(city) = typed_stream.next().await {
+			cities.push(city?);
+		}
+
+		// -- Check
+		// The last chunk (closing the array and object) does not change the value.
+		let names: Vec<Option<&str>> = cities.iter().map(|city| city.name.as_deref()).collect();
+		assert_eq!(names, [None, Some("Pa"), Some("Paris"), Some("Paris")]);
+		assert_eq!(
+			cities.last().map(|city| city.tags.clone()),
+			Some(vec!["capital".to_string()])
+		);
+		assert_eq!(typed_stream.text(), r#"{"name": "Paris", "tags": ["capital"]}"#);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_partial_json_stream_fenced() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		let chunk = |text: &str| MockStreamItem::Chunk(text.to_string());
+		mock.push_stream([
+			chunk("``"),
+			chunk("`json\n"),
+			chunk(r#"{"name": "Pa"#),
+			chunk(r#"ris"}"#),
+			chunk("\n```"),
+		]);
+		let client = Client::builder().with_mock_script(mock).build();
+
+		// -- Exec
+		let stream = client
+			.exec_chat_stream("mock::test-model", ChatRequest::from_user("City?"), None)
+			.await?
+			.stream;
+		let values: Vec<Value> = stream.into_partial_json().map(|value| value.unwrap()).collect().await;
+
+		// -- Check
+		assert_eq!(values, [json!({"name": "Pa"}), json!({"name": "Paris"})]);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
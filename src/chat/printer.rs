@@ -139,6 +139,14 @@ async fn print_chat_stream_inner(
 						}
 					}
 
+					// The blocks and interim usage are not printed, and the tool calls are printed from the complete `ToolCallChunk`
+					ChatStreamEvent::BlockStart(_)
+					| ChatStreamEvent::Usage(_)
+					| ChatStreamEvent::BlockStop(_)
+					| ChatStreamEvent::ToolCallStart(_)
+					| ChatStreamEvent::ToolCallArgsDelta(_)
+					| ChatStreamEvent::ToolCallEnd(_) => (None, None, false),
+
 					ChatStreamEvent::End(end_event) => {
 						if print_events {
 							// TODO: Might implement pretty JSON formatting
@@ -0,0 +1,230 @@
+//! Input token count of a `ChatRequest`, returned by `Client::count_tokens(...)`.
+
+use crate::ModelIden;
+use crate::chat::{Binary, BinarySource, ChatMessage, ChatRequest, ContentPart};
+use serde::{Deserialize, Serialize};
+
+/// Rough average of characters per token for the local estimate.
+const CHARS_PER_TOKEN: usize = 4;
+/// Per-message overhead (role and separators) for the local estimate.
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+/// An image is priced by dimensions, which are not decoded here (~1 megapixel image).
+const IMAGE_TOKENS: usize = 1_000;
+/// A PDF page is text plus an image for the vision models.
+const PDF_PAGE_TOKENS: usize = 1_500;
+const PDF_BYTES_PER_PAGE: usize = 100_000;
+/// Compressed audio at ~16KB per second, and ~25 tokens per second.
+const AUDIO_BYTES_PER_SECOND: usize = 16_000;
+const AUDIO_TOKENS_PER_SECOND: usize = 25;
+
+/// The input token count of a chat request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenCount {
+	/// The resolved model identifier the tokens were counted for.
+	pub model_iden: ModelIden,
+
+	/// The input tokens (system, messages, tool definitions, and binary parts).
+	pub input_tokens: u32,
+
+	/// True when estimated locally (the provider has no token counting API),
+	/// false when counted by the provider.
+	pub estimated: bool,
+}
+
+/// Constructors
+impl TokenCount {
+	pub(crate) fn from_provider(model_iden: ModelIden, input_tokens: u32) -> Self {
+		Self {
+			model_iden,
+			input_tokens,
+			estimated: false,
+		}
+	}
+
+	/// Estimates the input tokens locally, without a tokenizer.
+	pub fn estimate(model_iden: ModelIden, chat_req: &ChatRequest) -> Self {
+		Self {
+			model_iden,
+			input_tokens: estimate_input_tokens(chat_req),
+			estimated: true,
+		}
+	}
+}
+
+// region:    --- Estimator
+
+/// Rough estimate of the input tokens of a chat request.
+///
+/// The text (including the tool calls, tool responses, tool definitions, and documents) is ~4 characters per token.
+/// The binary parts use flat estimates (images), or their size (PDF pages, audio seconds).
+pub(crate) fn estimate_input_tokens(chat_req: &ChatRequest) -> u32 {
+	let mut char_count = chat_req.system.as_ref().map(|s| s.len()).unwrap_or_default();
+	let mut tokens = 0;
+
+	for msg in chat_req.messages.iter() {
+		add_message_counts(msg, &mut char_count, &mut tokens);
+	}
+
+	for tool in chat_req.tools.iter().flatten() {
+		char_count += tool.size();
+	}
+
+	for doc in chat_req.documents.iter().flatten() {
+		char_count += doc.text.len() + doc.title.as_ref().map(|t| t.len()).unwrap_or_default();
+	}
+
+	tokens += char_count.div_ceil(CHARS_PER_TOKEN);
+
+	tokens.try_into().unwrap_or(u32::MAX)
+}
+
+/// Rough estimate of the tokens of a single message (see `estimate_input_tokens`).
+pub(crate) fn estimate_message_tokens(msg: &ChatMessage) -> usize {
+	let mut char_count = 0;
+	let mut tokens = 0;
+	add_message_counts(msg, &mut char_count, &mut tokens);
+
+	tokens + char_count.div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Adds the characters (text parts) and tokens (overhead and binary parts) of a message.
+fn add_message_counts(msg: &ChatMessage, char_count: &mut usize, tokens: &mut usize) {
+	*tokens += MESSAGE_OVERHEAD_TOKENS;
+	for part in msg.content.parts() {
+		match part {
+			ContentPart::Text(text) => *char_count += text.len(),
+			ContentPart::ToolCall(tool_call) => *char_count += tool_call.size(),
+			ContentPart::ToolResponse(tool_response) => *char_count += tool_response.size(),
+			ContentPart::Binary(binary) => *tokens += estimate_binary_tokens(binary),
+			// Thought signatures are opaque, and not counted as input by the providers.
+			ContentPart::ThoughtSignature(_) => (),
+			ContentPart::Custom(_) => *char_count += part.size(),
+		}
+	}
+}
+
+fn estimate_binary_tokens(binary: &Binary) -> usize {
+	// Decoded byte length (URL content is unknown, so counted as a single page/second)
+	let byte_len = match &binary.source {
+		BinarySource::Base64(data) => data.len() * 3 / 4,
+		BinarySource::Url(_) => 0,
+	};
+
+	if binary.is_image() {
+		IMAGE_TOKENS
+	} else if binary.is_pdf() {
+		byte_len.div_ceil(PDF_BYTES_PER_PAGE).max(1) * PDF_PAGE_TOKENS
+	} else if binary.is_audio() {
+		byte_len.div_ceil(AUDIO_BYTES_PER_SECOND).max(1) * AUDIO_TOKENS_PER_SECOND
+	} else {
+		// Other documents (e.g., text files) are sent as text.
+		byte_len.div_ceil(CHARS_PER_TOKEN)
+	}
+}
+
+// endregion: --- Estimator
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chat::Tool;
+	use crate::resolver::AuthData;
+	use crate::webc::{HttpRequest, HttpResponse, HttpStreamResponse, HttpTransport, TransportFuture};
+	use crate::{Client, Headers};
+	use bytes::Bytes;
+	use reqwest::StatusCode;
+	use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
+	use serde_json::json;
+	use std::sync::{Arc, Mutex};
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_token_count_estimate_tools_and_binary() {
+		// -- Setup & Fixtures
+		let text = "x".repeat(400);
+		let chat_req = ChatRequest::default()
+			.with_system("y".repeat(40))
+			.append_message(ChatMessage::user(text));
+		let with_tool = chat_req.clone().append_tool(
+			Tool::new("get_weather")
+				.with_description("z".repeat(100))
+				.with_schema(json!({"type": "object"})),
+		);
+		let with_image = chat_req
+			.clone()
+			.append_message(ChatMessage::user(vec![ContentPart::from_binary_url(
+				"image/png",
+				"https://example.com/image.png",
+				None,
+			)]));
+
+		// -- Exec
+		let base = estimate_input_tokens(&chat_req);
+		let with_tool = estimate_input_tokens(&with_tool);
+		let with_image = estimate_input_tokens(&with_image);
+
+		// -- Check
+		assert_eq!(base, 4 + 440 / 4);
+		assert!(with_tool > base + 25, "tool definitions should be counted");
+		assert_eq!(with_image, base + 4 + IMAGE_TOKENS as u32);
+	}
+
+	/// An in-memory transport answering the Anthropic token count.
+	#[derive(Debug, Default, Clone)]
+	struct CountTransport {
+		requests: Arc<Mutex<Vec<HttpRequest>>>,
+	}
+
+	impl HttpTransport for CountTransport {
+		fn send(&self, request: HttpRequest) -> TransportFuture<'_, HttpResponse> {
+			self.requests.lock().unwrap().push(request);
+			let mut headers = HeaderMap::new();
+			headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+			Box::pin(async move {
+				Ok(HttpResponse {
+					status: StatusCode::OK,
+					headers,
+					body: Bytes::from(json!({"input_tokens": 12}).to_string()),
+				})
+			})
+		}
+
+		fn send_stream(&self, _request: HttpRequest) -> TransportFuture<'_, HttpStreamResponse> {
+			Box::pin(async { Err(crate::webc::Error::Transport("not supported".into())) })
+		}
+	}
+
+	#[tokio::test]
+	async fn test_count_tokens_request_override() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport = CountTransport::default();
+		let client = Client::builder()
+			.with_auth_resolver_fn(|_| {
+				Ok(Some(AuthData::RequestOverride {
+					url: "https://gateway.example.com/count".to_string(),
+					headers: Headers::from(("x-gateway-key".to_string(), "gw-key".to_string())),
+				}))
+			})
+			.with_transport(transport.clone())
+			.build();
+		let chat_req = ChatRequest::new(vec![ChatMessage::user("Hi")]);
+
+		// -- Exec
+		let token_count = client.count_tokens("claude-haiku-4-5", &chat_req, None).await?;
+
+		// -- Check
+		assert_eq!(token_count.input_tokens, 12);
+		let requests = transport.requests.lock().unwrap().clone();
+		assert_eq!(requests.len(), 1);
+		assert_eq!(requests[0].url, "https://gateway.example.com/count");
+		assert!(requests[0].headers.iter().any(|(k, v)| k == "x-gateway-key" && v == "gw-key"));
+		assert!(!requests[0].headers.iter().any(|(k, _)| k == "x-api-key"));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -244,6 +244,8 @@ mod tests {
 			max_uses: Some(5),
 			allowed_domains: None,
 			blocked_domains: None,
+			recency: None,
+			return_images: None,
 		});
 		let json = serde_json::to_string(&original).unwrap();
 		// Built-in configs are qualified as {"WebSearch": config}.
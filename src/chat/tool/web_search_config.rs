@@ -14,6 +14,14 @@ pub struct WebSearchConfig {
 	/// URLs from these domains will not be fetched.
 	/// Cannot be used together with `allowed_domains`.
 	pub blocked_domains: Option<Vec<String>>,
+
+	/// Only search content published within this time window.
+	/// NOTE: Currently only supported by Perplexity (`search_recency_filter`).
+	pub recency: Option<WebSearchRecency>,
+
+	/// Return the images found by the search.
+	/// NOTE: Currently only supported by Perplexity (`return_images`).
+	pub return_images: Option<bool>,
 }
 
 impl WebSearchConfig {
@@ -43,4 +51,43 @@ impl WebSearchConfig {
 		self.blocked_domains = Some(blocked_domains.into_iter().map(Into::into).collect());
 		self
 	}
+
+	/// Set the recency window of the searched content.
+	pub fn with_recency(mut self, recency: WebSearchRecency) -> Self {
+		self.recency = Some(recency);
+		self
+	}
+
+	/// Set whether the images found by the search should be returned.
+	pub fn with_return_images(mut self, return_images: bool) -> Self {
+		self.return_images = Some(return_images);
+		self
+	}
 }
+
+// region:    --- WebSearchRecency
+
+/// Time window of the searched content.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WebSearchRecency {
+	Hour,
+	Day,
+	Week,
+	Month,
+	Year,
+}
+
+impl WebSearchRecency {
+	/// Returns the keyword for API usage.
+	pub fn as_keyword(&self) -> &'static str {
+		match self {
+			WebSearchRecency::Hour => "hour",
+			WebSearchRecency::Day => "day",
+			WebSearchRecency::Week => "week",
+			WebSearchRecency::Month => "month",
+			WebSearchRecency::Year => "year",
+		}
+	}
+}
+
+// endregion: --- WebSearchRecency
@@ -28,6 +28,11 @@ pub struct Usage {
 }
 
 impl Usage {
+	/// True if no token count is set (e.g., a provider usage without counts).
+	pub fn is_empty(&self) -> bool {
+		self.prompt_tokens.is_none() && self.completion_tokens.is_none() && self.total_tokens.is_none()
+	}
+
 	/// Remove detail objects that contain only `None` fields.
 	pub fn compact_details(&mut self) {
 		if self.prompt_tokens_details.as_ref().is_some_and(|d| d.is_empty()) {
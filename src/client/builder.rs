@@ -1,11 +1,14 @@
+use crate::adapter::{CompatProfile, MockScript};
 use crate::chat::ChatOptions;
 use crate::resolver::{
 	AuthResolver, IntoAuthResolverFn, IntoModelMapperFn, IntoServiceTargetResolverFn, ModelMapper,
 	ServiceTargetResolver,
 };
-use crate::webc::WebClient;
-use crate::{Client, ClientConfig, WebConfig};
-use std::sync::Arc;
+#[cfg(feature = "cassette")]
+use crate::webc::Cassette;
+use crate::webc::{HttpTransport, WebClient};
+use crate::{Client, ClientConfig, UsageTracker, WebConfig, WebConfigTarget};
+use std::sync::{Arc, OnceLock};
 
 /// Builder for `Client`.
 ///
@@ -16,6 +19,8 @@ use std::sync::Arc;
 pub struct ClientBuilder {
 	web_client: Option<WebClient>,
 	config: Option<ClientConfig>,
+	#[cfg(feature = "cassette")]
+	cassette: Option<Cassette>,
 }
 
 /// Builder methods
@@ -26,6 +31,14 @@ impl ClientBuilder {
 		self
 	}
 
+	/// Use a custom `HttpTransport` for all the web calls (the default is `ReqwestTransport`).
+	///
+	/// Note: The `WebConfig` is not applied, as it configures the default `reqwest::Client`.
+	pub fn with_transport(mut self, transport: impl HttpTransport + 'static) -> Self {
+		self.web_client = Some(WebClient::from_transport(Arc::new(transport)));
+		self
+	}
+
 	/// Set a `ClientConfig`.
 	pub fn with_config(mut self, config: ClientConfig) -> Self {
 		self.config = Some(config);
@@ -38,6 +51,13 @@ impl ClientBuilder {
 		client_config.web_config = Some(req_options);
 		self
 	}
+
+	/// Records or replays the web calls with a `Cassette` (requires the `cassette` feature).
+	#[cfg(feature = "cassette")]
+	pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+		self.cassette = Some(cassette);
+		self
+	}
 }
 
 /// Builder ClientConfig passthrough convenient setters.
@@ -87,6 +107,13 @@ impl ClientBuilder {
 		self
 	}
 
+	/// Add a `WebConfig` override on `ClientConfig` for an adapter kind or endpoint host (creates it if absent).
+	pub fn with_web_config_override(mut self, target: impl Into<WebConfigTarget>, web_config: WebConfig) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.web_config_overrides.push((target.into(), web_config));
+		self
+	}
+
 	/// Set `ModelMapper` from a mapper function (creates `ClientConfig` if absent).
 	pub fn with_model_mapper_fn(mut self, model_mapper_fn: impl IntoModelMapperFn) -> Self {
 		let client_config = self.config.get_or_insert_with(ClientConfig::default);
@@ -94,6 +121,29 @@ impl ClientBuilder {
 		client_config.model_mapper = Some(model_mapper);
 		self
 	}
+
+	/// Set `UsageTracker` on `ClientConfig` (creates it if absent).
+	pub fn with_usage_tracker(mut self, usage_tracker: UsageTracker) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.usage_tracker = Some(usage_tracker);
+		self
+	}
+
+	/// Set `MockScript` on `ClientConfig` (creates it if absent), serving the `mock::` models.
+	pub fn with_mock_script(mut self, mock_script: MockScript) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.mock_script = Some(mock_script);
+		self
+	}
+
+	/// Add a `CompatProfile` on `ClientConfig` (creates it if absent), serving the models of its namespace.
+	pub fn with_compat_profile(mut self, compat_profile: CompatProfile) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config
+			.compat_profiles
+			.insert(compat_profile.name.clone(), compat_profile);
+		self
+	}
 }
 
 impl ClientBuilder {
@@ -101,22 +151,35 @@ impl ClientBuilder {
 	pub fn build(self) -> Client {
 		let config = self.config.unwrap_or_default();
 
+		// The WebConfig override web clients are built on first use (not with a custom reqwest client or transport)
+		let override_web_clients = match self.web_client {
+			Some(_) => Vec::new(),
+			None => config.web_config_overrides().iter().map(|_| OnceLock::new()).collect(),
+		};
+
 		// Create WebClient based on configuration
 		let web_client = if let Some(web_client) = self.web_client {
 			// Use explicitly provided WebClient
 			web_client
 		} else if let Some(req_config) = config.web_config() {
 			// Create WebClient with reqwest configuration
-			let mut builder = reqwest::Client::builder();
-			builder = req_config.apply_to_builder(builder);
-			let reqwest_client = builder.build().expect("Failed to build reqwest client");
-			WebClient::from_reqwest_client(reqwest_client)
+			WebClient::from_reqwest_client(req_config.new_reqwest_client())
 		} else {
 			// Use default WebClient
 			WebClient::default()
 		};
 
-		let inner = super::ClientInner { web_client, config };
+		#[cfg(feature = "cassette")]
+		let web_client = match self.cassette {
+			Some(cassette) => web_client.with_cassette(cassette),
+			None => web_client,
+		};
+
+		let inner = super::ClientInner {
+			web_client,
+			override_web_clients,
+			config,
+		};
 		Client { inner: Arc::new(inner) }
 	}
 }
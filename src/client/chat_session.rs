@@ -0,0 +1,466 @@
+use crate::chat::{
+	CancelHandle, ChatMessage, ChatOptions, ChatRequest, ChatResponse, ChatRole, ChatStream, ChatStreamEvent,
+	MessageContent, StreamEnd, Tool, ToolResponse,
+};
+use crate::{Client, Error, ModelIden, Result};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The current version of the `ChatSession` JSON format.
+pub const CHAT_SESSION_VERSION: u32 = 1;
+
+// region:    --- ChatSession
+
+/// A conversation with a model, owning the system prompt, tools, messages, and default options.
+///
+/// Each `send...` call appends the new messages (user or tool responses) and the assistant turn
+/// (content, tool calls, and thought signatures) to the session, only once the call succeeds.
+/// The reasoning content of the assistant turns is kept by the session (it is not sent back to the model).
+///
+/// The session can be saved and loaded as versioned JSON (`to_json` / `from_json`, `save` / `load`).
+/// The `Client` is not persisted, and the options not serializable (e.g., `usage_tracker`, `context_policy`) are skipped.
+#[derive(Debug, Clone)]
+pub struct ChatSession {
+	client: Client,
+	data: ChatSessionData,
+}
+
+/// The persisted data of a `ChatSession`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChatSessionData {
+	version: u32,
+	model: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	system: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	tools: Option<Vec<Tool>>,
+	#[serde(default)]
+	messages: Vec<ChatMessage>,
+	/// The reasoning content of the assistant messages, by message index.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	reasoning_contents: BTreeMap<usize, String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	options: Option<ChatOptions>,
+}
+
+/// Constructors & Setters
+impl ChatSession {
+	pub fn new(client: Client, model: impl Into<String>) -> Self {
+		Self {
+			client,
+			data: ChatSessionData {
+				version: CHAT_SESSION_VERSION,
+				model: model.into(),
+				system: None,
+				tools: None,
+				messages: Vec::new(),
+				reasoning_contents: BTreeMap::new(),
+				options: None,
+			},
+		}
+	}
+
+	/// Sets the model for the next calls.
+	pub fn with_model(mut self, model: impl Into<String>) -> Self {
+		self.data.model = model.into();
+		self
+	}
+
+	pub fn with_system(mut self, system: impl Into<String>) -> Self {
+		self.data.system = Some(system.into());
+		self
+	}
+
+	pub fn with_tools<I>(mut self, tools: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<Tool>,
+	{
+		self.data.tools = Some(tools.into_iter().map(Into::into).collect());
+		self
+	}
+
+	pub fn append_tool(mut self, tool: impl Into<Tool>) -> Self {
+		self.data.tools.get_or_insert_with(Vec::new).push(tool.into());
+		self
+	}
+
+	/// Sets the default options of the `send...` calls.
+	pub fn with_options(mut self, options: ChatOptions) -> Self {
+		self.data.options = Some(options);
+		self
+	}
+}
+
+/// Getters & Mutators
+impl ChatSession {
+	pub fn client(&self) -> &Client {
+		&self.client
+	}
+
+	pub fn model(&self) -> &str {
+		&self.data.model
+	}
+
+	pub fn system(&self) -> Option<&str> {
+		self.data.system.as_deref()
+	}
+
+	pub fn tools(&self) -> Option<&[Tool]> {
+		self.data.tools.as_deref()
+	}
+
+	pub fn options(&self) -> Option<&ChatOptions> {
+		self.data.options.as_ref()
+	}
+
+	pub fn messages(&self) -> &[ChatMessage] {
+		&self.data.messages
+	}
+
+	/// Returns the reasoning content of the assistant message at `message_index`, if any.
+	pub fn reasoning_content(&self, message_index: usize) -> Option<&str> {
+		self.data.reasoning_contents.get(&message_index).map(String::as_str)
+	}
+
+	/// Returns the last assistant message, if any.
+	pub fn last_assistant_message(&self) -> Option<&ChatMessage> {
+		self.data
+			.messages
+			.iter()
+			.rev()
+			.find(|msg| matches!(msg.role, ChatRole::Assistant))
+	}
+
+	/// Appends a message without calling the model (e.g., a tool response sent later with `send_messages`).
+	pub fn append_message(&mut self, message: impl Into<ChatMessage>) {
+		self.data.messages.push(message.into());
+	}
+
+	/// Removes all the messages (the system prompt, tools, and options are kept).
+	pub fn clear_messages(&mut self) {
+		self.data.messages.clear();
+		self.data.reasoning_contents.clear();
+	}
+
+	/// Returns the `ChatRequest` of the current conversation.
+	pub fn chat_request(&self) -> ChatRequest {
+		self.to_chat_request(Vec::new())
+	}
+}
+
+/// Send
+impl ChatSession {
+	/// Sends a user message, and appends it with the assistant response to the session.
+	pub async fn send(&mut self, content: impl Into<MessageContent>) -> Result<ChatResponse> {
+		self.send_messages(vec![ChatMessage::user(content)]).await
+	}
+
+	/// Sends the tool responses (for the tool calls of the last assistant turn),
+	/// and appends them with the assistant response to the session.
+	pub async fn send_tool_responses<I>(&mut self, tool_responses: I) -> Result<ChatResponse>
+	where
+		I: IntoIterator<Item = ToolResponse>,
+	{
+		self.send_messages(tool_responses.into_iter().map(ChatMessage::from).collect())
+			.await
+	}
+
+	/// Sends new messages, and appends them with the assistant response to the session.
+	pub async fn send_messages(&mut self, messages: Vec<ChatMessage>) -> Result<ChatResponse> {
+		let chat_req = self.to_chat_request(messages.clone());
+
+		let chat_res = self
+			.client
+			.exec_chat(self.data.model.as_str(), chat_req, self.data.options.as_ref())
+			.await?;
+
+		self.commit_turn(messages, chat_res.content.clone(), chat_res.reasoning_content.clone());
+
+		Ok(chat_res)
+	}
+
+	/// Streams the response to a user message.
+	///
+	/// The user message and the assistant turn (content, tool calls, thought signatures, and reasoning)
+	/// are appended to the session at the `ChatStreamEvent::End`; nothing is appended if the stream
+	/// is cancelled (see `ChatSessionStream::cancel_handle`), dropped, or fails before the end.
+	///
+	/// Note: The content, reasoning, and tool calls capture options are forced on for this call.
+	pub async fn send_stream(&mut self, content: impl Into<MessageContent>) -> Result<ChatSessionStream<'_>> {
+		self.send_messages_stream(vec![ChatMessage::user(content)]).await
+	}
+
+	/// Streams the response to the tool responses (see `send_stream`).
+	pub async fn send_tool_responses_stream<I>(&mut self, tool_responses: I) -> Result<ChatSessionStream<'_>>
+	where
+		I: IntoIterator<Item = ToolResponse>,
+	{
+		self.send_messages_stream(tool_responses.into_iter().map(ChatMessage::from).collect())
+			.await
+	}
+
+	/// Streams the response to new messages (see `send_stream`).
+	pub async fn send_messages_stream(&mut self, messages: Vec<ChatMessage>) -> Result<ChatSessionStream<'_>> {
+		let chat_req = self.to_chat_request(messages.clone());
+		let options = self
+			.data
+			.options
+			.clone()
+			.unwrap_or_default()
+			.with_capture_content(true)
+			.with_capture_reasoning_content(true)
+			.with_capture_tool_calls(true);
+
+		let stream_res = self
+			.client
+			.exec_chat_stream(self.data.model.as_str(), chat_req, Some(&options))
+			.await?;
+
+		Ok(ChatSessionStream {
+			session: self,
+			stream: stream_res.stream,
+			model_iden: stream_res.model_iden,
+			pending_messages: Some(messages),
+		})
+	}
+}
+
+/// Persistence
+impl ChatSession {
+	/// Serializes the session as versioned JSON.
+	pub fn to_json(&self) -> Result<String> {
+		Ok(serde_json::to_string_pretty(&self.data)?)
+	}
+
+	/// Restores a session from its JSON, with the client to use for the next calls.
+	///
+	/// Returns an `Error::ChatSessionVersionNotSupported` if the JSON is from a newer format version.
+	pub fn from_json(client: Client, json: &str) -> Result<Self> {
+		let value: serde_json::Value = serde_json::from_str(json)?;
+		let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or_default();
+		if version == 0 || version > CHAT_SESSION_VERSION as u64 {
+			return Err(Error::ChatSessionVersionNotSupported {
+				version,
+				max_version: CHAT_SESSION_VERSION,
+			});
+		}
+
+		let mut data: ChatSessionData = serde_json::from_value(value)?;
+		data.version = CHAT_SESSION_VERSION;
+
+		Ok(Self { client, data })
+	}
+
+	/// Saves the session JSON to a file.
+	pub fn save(&self, file_path: impl AsRef<Path>) -> Result<()> {
+		let file_path = file_path.as_ref();
+		std::fs::write(file_path, self.to_json()?)
+			.map_err(|e| Error::Internal(format!("Failed to write file '{}': {}", file_path.display(), e)))
+	}
+
+	/// Loads a session from a file saved with `save`.
+	pub fn load(client: Client, file_path: impl AsRef<Path>) -> Result<Self> {
+		let file_path = file_path.as_ref();
+		let json = std::fs::read_to_string(file_path)
+			.map_err(|e| Error::Internal(format!("Failed to read file '{}': {}", file_path.display(), e)))?;
+		Self::from_json(client, &json)
+	}
+}
+
+// Private
+impl ChatSession {
+	fn to_chat_request(&self, new_messages: Vec<ChatMessage>) -> ChatRequest {
+		let mut messages = self.data.messages.clone();
+		messages.extend(new_messages);
+
+		ChatRequest {
+			system: self.data.system.clone(),
+			messages,
+			tools: self.data.tools.clone(),
+			documents: None,
+		}
+	}
+
+	fn commit_turn(
+		&mut self,
+		new_messages: Vec<ChatMessage>,
+		content: MessageContent,
+		reasoning_content: Option<String>,
+	) {
+		// Some providers reject empty assistant messages (e.g., a response with only reasoning),
+		// and appending only the new messages would give two consecutive user turns, so the turn is skipped.
+		if content.is_empty() {
+			return;
+		}
+		self.data.messages.extend(new_messages);
+		if let Some(reasoning_content) = reasoning_content.filter(|r| !r.is_empty()) {
+			self.data.reasoning_contents.insert(self.data.messages.len(), reasoning_content);
+		}
+		self.data.messages.push(ChatMessage::assistant(content));
+	}
+}
+
+// endregion: --- ChatSession
+
+// region:    --- ChatSessionStream
+
+/// The stream returned by `ChatSession::send_stream(...)`.
+///
+/// Yields the `ChatStreamEvent` of the underlying `ChatStream`, and appends the turn to the session at the end.
+pub struct ChatSessionStream<'a> {
+	session: &'a mut ChatSession,
+	stream: ChatStream,
+	/// The resolved model identifier of the request.
+	pub model_iden: ModelIden,
+	/// The messages sent, appended at the stream end (taken once appended).
+	pending_messages: Option<Vec<ChatMessage>>,
+}
+
+impl ChatSessionStream<'_> {
+	/// Returns the handle cancelling this stream (the truncated turn is not appended to the session).
+	pub fn cancel_handle(&self) -> CancelHandle {
+		self.stream.cancel_handle()
+	}
+
+	fn commit(&mut self, stream_end: &StreamEnd) {
+		// The partial end of a cancelled stream is a truncated turn.
+		if self.stream.cancel_handle().is_cancelled() {
+			self.pending_messages = None;
+			return;
+		}
+		if let Some(messages) = self.pending_messages.take() {
+			let content = stream_end.captured_content.clone().unwrap_or_default();
+			let reasoning_content = stream_end.captured_reasoning_content.clone();
+			self.session.commit_turn(messages, content, reasoning_content);
+		}
+	}
+}
+
+impl Stream for ChatSessionStream<'_> {
+	type Item = Result<ChatStreamEvent>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+
+		let poll = Pin::new(&mut this.stream).poll_next(cx);
+		if let Poll::Ready(Some(Ok(ChatStreamEvent::End(stream_end)))) = &poll {
+			this.commit(stream_end);
+		}
+		poll
+	}
+}
+
+// endregion: --- ChatSessionStream
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::adapter::{MockScript, MockStreamItem};
+	use crate::chat::{ContentPart, ToolCall};
+	use futures::StreamExt;
+	use serde_json::json;
+	use std::time::Duration;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[test]
+	fn test_chat_session_json_round_trip() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut session = ChatSession::new(Client::default(), "gpt-4o-mini")
+			.with_system("Be concise")
+			.append_tool(Tool::new("get_weather").with_schema(json!({"type": "object"})))
+			.with_options(ChatOptions::default().with_temperature(0.2));
+		let tool_call = ToolCall {
+			call_id: "call_1".to_string(),
+			fn_name: "get_weather".to_string(),
+			fn_arguments: json!({"city": "Paris"}),
+			thought_signatures: None,
+		};
+		session.commit_turn(
+			vec![ChatMessage::user("Weather in Paris?")],
+			MessageContent::from_parts(vec![
+				ContentPart::ThoughtSignature("sig-1".to_string()),
+				ContentPart::ToolCall(tool_call),
+			]),
+			Some("Need the weather tool".to_string()),
+		);
+		session.append_message(ToolResponse::new("call_1", "sunny"));
+
+		// -- Exec
+		let json = session.to_json()?;
+		let loaded = ChatSession::from_json(Client::default(), &json)?;
+
+		// -- Check
+		assert_eq!(loaded.model(), "gpt-4o-mini");
+		assert_eq!(loaded.system(), Some("Be concise"));
+		assert_eq!(loaded.tools().map(|t| t.len()), Some(1));
+		assert_eq!(loaded.options().and_then(|o| o.temperature), Some(0.2));
+		assert_eq!(loaded.messages().len(), 3);
+		assert!(matches!(loaded.messages()[1].role, ChatRole::Assistant));
+		assert_eq!(loaded.messages()[1].content.parts().len(), 2);
+		assert_eq!(loaded.reasoning_content(1), Some("Need the weather tool"));
+
+		// -- Check unsupported version
+		let mut value: serde_json::Value = serde_json::from_str(&json)?;
+		value["version"] = json!(CHAT_SESSION_VERSION + 1);
+		let res = ChatSession::from_json(Client::default(), &value.to_string());
+		assert!(matches!(res, Err(Error::ChatSessionVersionNotSupported { .. })));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_chat_session_commit_turn_empty_content_skipped() -> Result<()> {
+		// -- Setup & Fixtures
+		let mut session = ChatSession::new(Client::default(), "gpt-4o-mini");
+
+		// -- Exec
+		session.commit_turn(
+			vec![ChatMessage::user("Hi")],
+			MessageContent::default(),
+			Some("Only reasoning".to_string()),
+		);
+
+		// -- Check
+		assert!(session.messages().is_empty());
+		assert_eq!(session.reasoning_content(1), None);
+
+		Ok(())
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_chat_session_stream_cancel_not_committed() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		mock.push_stream([
+			MockStreamItem::Chunk("Hello".to_string()),
+			MockStreamItem::Delay(Duration::from_secs(60)),
+			MockStreamItem::Chunk(" world".to_string()),
+		]);
+		let client = Client::builder().with_mock_script(mock).build();
+		let mut session = ChatSession::new(client, "mock::test-model");
+
+		// -- Exec
+		let mut stream = session.send_stream("Hi").await?;
+		let cancel_handle = stream.cancel_handle();
+		while let Some(event) = stream.next().await {
+			if let ChatStreamEvent::Chunk(_) = event? {
+				cancel_handle.cancel();
+			}
+		}
+
+		// -- Check
+		assert!(session.messages().is_empty());
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
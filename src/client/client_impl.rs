@@ -1,9 +1,13 @@
-use crate::adapter::{AdapterDispatcher, AdapterKind, ServiceType, WebRequestData};
-use crate::chat::{ChatOptions, ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
-use crate::client::ModelSpec;
+use crate::adapter::{AdapterDispatcher, AdapterKind, MockScript, ServiceType, WebRequestData};
+use crate::chat::{
+	ChatOptions, ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse, ContextSummarizer, ContextTrim,
+	StreamControl, TimeoutPhase, TokenCount,
+};
+use crate::client::{ListModelsSpec, ModelSpec};
 use crate::embed::{EmbedOptions, EmbedOptionsSet, EmbedRequest, EmbedResponse};
 use crate::resolver::AuthData;
-use crate::{Client, Error, ModelIden, Result, ServiceTarget};
+use crate::{Client, Error, ModelCapabilities, ModelIden, ModelInfo, Result, ServiceTarget, UsageTracker};
+use std::time::Duration;
 
 /// High-level client APIs.
 impl Client {
@@ -21,15 +25,34 @@ impl Client {
 	///
 	/// - Adapters should filter non-chat models until more skills are supported.
 	///   Future: `model_names(adapter_kind, Option<&[Skill]>)`.
+	///
+	/// - See [`Client::list_models`] for the resolver-aware listing with [`ModelInfo`].
 	pub async fn all_model_names(&self, adapter_kind: AdapterKind) -> Result<Vec<String>> {
 		let models = AdapterDispatcher::all_model_names(adapter_kind).await?;
 		Ok(models)
 	}
 
+	/// Lists the models, with their [`ModelInfo`], from the provider models endpoint.
+	///
+	/// Unlike [`Client::all_model_names`], the endpoint and auth are resolved through the client config
+	/// (auth resolver, service target resolver), so custom hosts (e.g., a remote Ollama) and keys are honored.
+	///
+	/// Accepts any type that implements `Into<ListModelsSpec>`:
+	/// - `AdapterKind`: Resolves with an empty model name
+	/// - `&str`, `String`, `ModelIden`, `ServiceTarget`: Resolves like the [`ModelSpec`] of the chat requests
+	///
+	/// Note: Providers without a models endpoint (e.g., Perplexity) return their static list (ids only).
+	pub async fn list_models(&self, spec: impl Into<ListModelsSpec>) -> Result<Vec<ModelInfo>> {
+		let target = self.config().resolve_list_models_spec(spec.into()).await?;
+		let web_client = self.web_client_for(&target.model, target.endpoint.base_url())?;
+		let compat_profile = self.config().compat_profile_for(&target.model);
+		AdapterDispatcher::list_models(target, web_client, compat_profile).await
+	}
+
 	/// Builds a ModelIden by inferring AdapterKind from the model name.
 	pub fn default_model(&self, model_name: &str) -> Result<ModelIden> {
 		// -- First get the default ModelInfo
-		let adapter_kind = AdapterKind::from_model(model_name)?;
+		let adapter_kind = self.config().adapter_kind_for_model(model_name)?;
 		let model_iden = ModelIden::new(adapter_kind, model_name);
 		Ok(model_iden)
 	}
@@ -69,36 +92,50 @@ impl Client {
 			.with_client_options(self.config().chat_options());
 
 		let target = self.config().resolve_model_spec(model.into()).await?;
+		let options_set = options_set.with_compat_profile(self.config().compat_profile_for(&target.model));
 		let model = target.model.clone();
-		let auth_data = target.auth.clone();
 
-		let WebRequestData {
-			mut url,
-			mut headers,
-			payload,
-		} = AdapterDispatcher::to_web_request_data(target, ServiceType::Chat, chat_req, options_set.clone())?;
+		// -- The checks not depending on the trimmed messages fail before the context policy summarizer call
+		let capabilities = self.capabilities_to_check(&model, &options_set);
+		if let Some(capabilities) = capabilities {
+			capabilities.check_chat_options(&model, &chat_req, &options_set)?;
+		}
 
-		if let Some(extra_headers) = options.and_then(|o| o.extra_headers.as_ref()) {
-			headers.merge_with(extra_headers);
+		let usage_tracker = self.resolve_usage_tracker(options_set.usage_tracker());
+		let usage_label = options_set.usage_label().map(|label| label.to_string());
+		if let Some(usage_tracker) = &usage_tracker {
+			usage_tracker.check_budget(usage_label.as_deref())?;
 		}
 
-		if let AuthData::RequestOverride {
-			url: override_url,
-			headers: override_headers,
-		} = auth_data
-		{
-			url = override_url;
-			headers = override_headers;
-		};
+		let chat_req = self.apply_context_policy(chat_req, &options_set).await?;
 
-		let web_res = self
-			.web_client()
-			.do_post(&url, &headers, &payload)
-			.await
-			.map_err(|webc_error| Error::WebModelCall {
-				model_iden: model.clone(),
-				webc_error,
-			})?;
+		if let Some(capabilities) = capabilities {
+			capabilities.check_chat_messages(&model, &chat_req)?;
+		}
+
+		// -- The mock models are served in-process by the MockScript
+		if model.adapter_kind == AdapterKind::Mock {
+			let mock_fut = self.mock_script(&model)?.exec_chat(model.clone(), chat_req, &options_set);
+			let chat_res = Self::with_timeout(options_set.timeout(), &model, mock_fut).await?;
+			if let Some(usage_tracker) = &usage_tracker {
+				usage_tracker.record(&model, usage_label.as_deref(), &chat_res.usage);
+			}
+			return Ok(chat_res);
+		}
+
+		let WebRequestData { url, headers, payload } =
+			Self::to_chat_web_request_data(target, ServiceType::Chat, chat_req, options, options_set.clone())?;
+
+		let web_fut = async {
+			self.web_client_for(&model, &url)?
+				.do_post(&url, &headers, &payload)
+				.await
+				.map_err(|webc_error| Error::WebModelCall {
+					model_iden: model.clone(),
+					webc_error,
+				})
+		};
+		let web_res = Self::with_timeout(options_set.timeout(), &model, web_fut).await?;
 
 		// Note: here we capture/clone the raw body if set in the options_set
 		let captured_raw_body = options_set.capture_raw_body().unwrap_or_default().then(|| web_res.body.clone());
@@ -106,6 +143,9 @@ impl Client {
 		match AdapterDispatcher::to_chat_response(model.clone(), web_res, options_set) {
 			Ok(mut chat_res) => {
 				chat_res.captured_raw_body = captured_raw_body;
+				if let Some(usage_tracker) = &usage_tracker {
+					usage_tracker.record(&model, usage_label.as_deref(), &chat_res.usage);
+				}
 				Ok(chat_res)
 			}
 			Err(err) => {
@@ -140,42 +180,145 @@ impl Client {
 			.with_client_options(self.config().chat_options());
 
 		let target = self.config().resolve_model_spec(model.into()).await?;
+		let options_set = options_set.with_compat_profile(self.config().compat_profile_for(&target.model));
 		let model = target.model.clone();
-		let auth_data = target.auth.clone();
 
-		let WebRequestData {
-			mut url,
-			mut headers,
-			payload,
-		} = AdapterDispatcher::to_web_request_data(target, ServiceType::ChatStream, chat_req, options_set.clone())?;
+		// -- The checks not depending on the trimmed messages fail before the context policy summarizer call
+		let capabilities = self.capabilities_to_check(&model, &options_set);
+		if let Some(capabilities) = capabilities {
+			capabilities.check_chat_options(&model, &chat_req, &options_set)?;
+		}
 
-		if let Some(extra_headers) = options.and_then(|o| o.extra_headers.as_ref()) {
-			headers.merge_with(extra_headers);
+		let usage_tracker = self.resolve_usage_tracker(options_set.usage_tracker());
+		let usage_label = options_set.usage_label().map(|label| label.to_string());
+		if let Some(usage_tracker) = &usage_tracker {
+			usage_tracker.check_budget(usage_label.as_deref())?;
 		}
 
-		// TODO: Need to check this.
-		//       This was part of the 429c5cee2241dbef9f33699b9c91202233c22816 commit
-		//       But now it is missing in the the exec_chat(..) above, which is probably an issue.
-		if let AuthData::RequestOverride {
-			url: override_url,
-			headers: override_headers,
-		} = auth_data
-		{
-			url = override_url;
-			headers = override_headers;
+		let chat_req = self.apply_context_policy(chat_req, &options_set).await?;
+
+		if let Some(capabilities) = capabilities {
+			capabilities.check_chat_messages(&model, &chat_req)?;
+		}
+
+		// -- Capture the usage when tracked (recorded from the `StreamEnd.captured_usage`)
+		let tracked_options: ChatOptions;
+		let (options, options_set) = if usage_tracker.is_some() && options_set.capture_usage() != Some(true) {
+			tracked_options = options.cloned().unwrap_or_default().with_capture_usage(true);
+			let options_set = ChatOptionsSet::default()
+				.with_chat_options(Some(&tracked_options))
+				.with_client_options(self.config().chat_options())
+				.with_compat_profile(options_set.compat_profile());
+			(Some(&tracked_options), options_set)
+		} else {
+			(options, options_set)
 		};
 
-		let reqwest_builder = self
-			.web_client()
-			.new_req_builder(&url, &headers, &payload)
+		let control = StreamControl::new(model.clone(), &options_set);
+
+		let mut res = if model.adapter_kind == AdapterKind::Mock {
+			// -- The mock models are served in-process by the MockScript
+			self.mock_script(&model)?
+				.exec_chat_stream(model.clone(), chat_req, &options_set)?
+		} else {
+			let WebRequestData { url, headers, payload } = Self::to_chat_web_request_data(
+				target,
+				ServiceType::ChatStream,
+				chat_req,
+				options,
+				options_set.clone(),
+			)?;
+
+			let stream_source = self
+				.web_client_for(&model, &url)?
+				.new_stream_source(&url, &headers, &payload)
+				.map_err(|webc_error| Error::WebModelCall {
+					model_iden: model.clone(),
+					webc_error,
+				})?;
+
+			AdapterDispatcher::to_chat_stream(model.clone(), stream_source, options_set)?
+		};
+
+		res.stream = res.stream.with_control(control);
+		if let Some(usage_tracker) = usage_tracker {
+			res.stream = res.stream.with_usage_tracker(usage_tracker, model, usage_label);
+		}
+
+		Ok(res)
+	}
+
+	/// Builds the provider request of a chat call (URL, redacted headers, and JSON payload), without sending it.
+	///
+	/// Uses the same model resolution, adapter, `extra_headers`, and `AuthData::RequestOverride` as `exec_chat(...)`.
+	///
+	/// Note: The `ChatOptions::context_policy` trims the messages, but its summarizer is not called.
+	pub async fn build_chat_request(
+		&self,
+		model: impl Into<ModelSpec>,
+		chat_req: ChatRequest,
+		options: Option<&ChatOptions>,
+	) -> Result<WebRequestData> {
+		let options_set = ChatOptionsSet::default()
+			.with_chat_options(options)
+			.with_client_options(self.config().chat_options());
+
+		let target = self.config().resolve_model_spec(model.into()).await?;
+		let options_set = options_set.with_compat_profile(self.config().compat_profile_for(&target.model));
+
+		let chat_req = match options_set.context_policy() {
+			Some(policy) => policy.apply(chat_req).chat_req,
+			None => chat_req,
+		};
+
+		let mut web_request_data =
+			Self::to_chat_web_request_data(target, ServiceType::Chat, chat_req, options, options_set)?;
+		web_request_data.headers = web_request_data.headers.redacted();
+
+		Ok(web_request_data)
+	}
+
+	/// Counts the input tokens of a chat request (system, messages, tool definitions, and binary parts), without sending it.
+	///
+	/// - Anthropic (`messages/count_tokens`), Gemini (`:countTokens`), and Ollama (`prompt_eval_count`)
+	///   are counted by the provider.
+	/// - The other adapters (e.g., OpenAI and the OpenAI-compatible ones) are estimated locally
+	///   (`TokenCount::estimated` is true).
+	///
+	/// The `extra_headers` and the `AuthData::RequestOverride` (URL and headers) are applied as in `exec_chat(...)`.
+	pub async fn count_tokens(
+		&self,
+		model: impl Into<ModelSpec>,
+		chat_req: &ChatRequest,
+		options: Option<&ChatOptions>,
+	) -> Result<TokenCount> {
+		let options_set = ChatOptionsSet::default()
+			.with_chat_options(options)
+			.with_client_options(self.config().chat_options());
+
+		let target = self.config().resolve_model_spec(model.into()).await?;
+		let options_set = options_set.with_compat_profile(self.config().compat_profile_for(&target.model));
+		let model = target.model.clone();
+		let auth_data = target.auth.clone();
+
+		let Some(web_request_data) =
+			AdapterDispatcher::to_count_tokens_request_data(target, chat_req.clone(), options_set)?
+		else {
+			return Ok(TokenCount::estimate(model, chat_req));
+		};
+		let WebRequestData { url, headers, payload } =
+			Self::apply_request_options(web_request_data, auth_data, options);
+
+		let web_res = self
+			.web_client_for(&model, &url)?
+			.do_post(&url, &headers, &payload)
+			.await
 			.map_err(|webc_error| Error::WebModelCall {
 				model_iden: model.clone(),
 				webc_error,
 			})?;
 
-		let res = AdapterDispatcher::to_chat_stream(model, reqwest_builder, options_set)?;
-
-		Ok(res)
+		AdapterDispatcher::to_token_count(model, web_res)
 	}
 
 	/// Creates embeddings for a single input string.
@@ -204,6 +347,28 @@ impl Client {
 		self.exec_embed(model, embed_req, options).await
 	}
 
+	/// Builds the provider request of an embedding call (URL, redacted headers, and JSON payload), without sending it.
+	///
+	/// Uses the same model resolution and adapter as `exec_embed(...)`.
+	pub async fn build_embed_request(
+		&self,
+		model: impl Into<ModelSpec>,
+		embed_req: EmbedRequest,
+		options: Option<&EmbedOptions>,
+	) -> Result<WebRequestData> {
+		let options_set = EmbedOptionsSet::new()
+			.with_request_options(options)
+			.with_client_options(self.config().embed_options());
+
+		let target = self.config().resolve_model_spec(model.into()).await?;
+		let options_set = options_set.with_compat_profile(self.config().compat_profile_for(&target.model));
+
+		let mut web_request_data = AdapterDispatcher::to_embed_request_data(target, embed_req, options_set)?;
+		web_request_data.headers = web_request_data.headers.redacted();
+
+		Ok(web_request_data)
+	}
+
 	/// Sends an embedding request and returns the response.
 	///
 	/// Accepts any type that implements `Into<ModelSpec>`:
@@ -221,13 +386,20 @@ impl Client {
 			.with_client_options(self.config().embed_options());
 
 		let target = self.config().resolve_model_spec(model.into()).await?;
+		let options_set = options_set.with_compat_profile(self.config().compat_profile_for(&target.model));
 		let model = target.model.clone();
 
+		let usage_tracker = self.resolve_usage_tracker(options_set.usage_tracker());
+		let usage_label = options_set.usage_label().map(|label| label.to_string());
+		if let Some(usage_tracker) = &usage_tracker {
+			usage_tracker.check_budget(usage_label.as_deref())?;
+		}
+
 		let WebRequestData { headers, payload, url } =
 			AdapterDispatcher::to_embed_request_data(target, embed_req, options_set.clone())?;
 
 		let web_res = self
-			.web_client()
+			.web_client_for(&model, &url)?
 			.do_post(&url, &headers, &payload)
 			.await
 			.map_err(|webc_error| Error::WebModelCall {
@@ -235,8 +407,141 @@ impl Client {
 				webc_error,
 			})?;
 
-		let res = AdapterDispatcher::to_embed_response(model, web_res, options_set)?;
+		let res = AdapterDispatcher::to_embed_response(model.clone(), web_res, options_set)?;
+
+		if let Some(usage_tracker) = &usage_tracker {
+			usage_tracker.record(&model, usage_label.as_deref(), &res.usage);
+		}
 
 		Ok(res)
 	}
 }
+
+// region:    --- Support
+
+impl Client {
+	/// The capabilities of the opt-in pre-flight check (`ChatOptions::capability_check`),
+	/// for the models in the capabilities registry.
+	fn capabilities_to_check(
+		&self,
+		model: &ModelIden,
+		options_set: &ChatOptionsSet<'_, '_>,
+	) -> Option<&ModelCapabilities> {
+		if !options_set.capability_check().unwrap_or_default() {
+			return None;
+		}
+
+		self.config().model_capabilities().get(model)
+	}
+}
+
+impl Client {
+	/// Trims the request messages with the `ChatOptions::context_policy`, if any,
+	/// and summarizes the dropped messages when the policy has a summarizer.
+	async fn apply_context_policy(
+		&self,
+		chat_req: ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+	) -> Result<ChatRequest> {
+		let Some(policy) = options_set.context_policy() else {
+			return Ok(chat_req);
+		};
+
+		let ContextTrim {
+			mut chat_req,
+			dropped,
+			dropped_index,
+		} = policy.apply(chat_req);
+
+		if let Some(summarizer) = policy.summarizer.as_ref()
+			&& !dropped.is_empty()
+		{
+			let summary_req = summarizer.to_summary_request(&dropped);
+			// Boxed, as exec_chat is the caller of this function.
+			let summary_res = Box::pin(self.exec_chat(summarizer.model.clone(), summary_req, None)).await?;
+			if let Some(summary) = summary_res.first_text() {
+				let summary_msg = ContextSummarizer::to_summary_message(summary);
+				chat_req.messages.insert(dropped_index, summary_msg);
+			}
+		}
+
+		Ok(chat_req)
+	}
+}
+
+impl Client {
+	/// The adapter web request data of a chat call, with the `extra_headers` of the options,
+	/// and the `AuthData::RequestOverride` of the target applied.
+	fn to_chat_web_request_data(
+		target: ServiceTarget,
+		service_type: ServiceType,
+		chat_req: ChatRequest,
+		options: Option<&ChatOptions>,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		let auth_data = target.auth.clone();
+
+		let web_request_data = AdapterDispatcher::to_web_request_data(target, service_type, chat_req, options_set)?;
+
+		Ok(Self::apply_request_options(web_request_data, auth_data, options))
+	}
+
+	/// Applies the `extra_headers` of the options, then the `AuthData::RequestOverride` (if any).
+	fn apply_request_options(
+		web_request_data: WebRequestData,
+		auth_data: AuthData,
+		options: Option<&ChatOptions>,
+	) -> WebRequestData {
+		let WebRequestData {
+			mut url,
+			mut headers,
+			payload,
+		} = web_request_data;
+
+		if let Some(extra_headers) = options.and_then(|o| o.extra_headers.as_ref()) {
+			headers.merge_with(extra_headers);
+		}
+
+		if let AuthData::RequestOverride {
+			url: override_url,
+			headers: override_headers,
+		} = auth_data
+		{
+			url = override_url;
+			headers = override_headers;
+		};
+
+		WebRequestData { url, headers, payload }
+	}
+}
+
+impl Client {
+	/// The per-call `UsageTracker` (from the options), or the client one.
+	fn resolve_usage_tracker(&self, options_tracker: Option<&UsageTracker>) -> Option<UsageTracker> {
+		options_tracker.or_else(|| self.config().usage_tracker()).cloned()
+	}
+
+	/// Runs the call with the `ChatOptions::timeout` (if any).
+	async fn with_timeout<T>(
+		timeout: Option<Duration>,
+		model: &ModelIden,
+		fut: impl Future<Output = Result<T>>,
+	) -> Result<T> {
+		match timeout {
+			Some(timeout) => tokio::time::timeout(timeout, fut).await.map_err(|_| Error::Timeout {
+				model_iden: model.clone(),
+				phase: TimeoutPhase::Total,
+			})?,
+			None => fut.await,
+		}
+	}
+
+	/// The client MockScript serving the `AdapterKind::Mock` models.
+	fn mock_script(&self, model: &ModelIden) -> Result<&MockScript> {
+		self.config().mock_script().ok_or_else(|| Error::MockScriptNotSet {
+			model_iden: model.clone(),
+		})
+	}
+}
+
+// endregion: --- Support
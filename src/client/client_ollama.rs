@@ -0,0 +1,86 @@
+use crate::adapter::{AdapterKind, OllamaAdapter, OllamaModelInfo, OllamaPullStream, WebRequestData};
+use crate::client::ModelSpec;
+use crate::{Client, Error, Result, ServiceTarget};
+
+/// Ollama management APIs (only for `AdapterKind::Ollama` models).
+///
+/// The endpoint is resolved like for the chat requests, so a custom Ollama host
+/// set with a `ServiceTargetResolver` (or a `ServiceTarget`) is honored.
+impl Client {
+	/// Pulls a model, returning a stream of `OllamaPullProgress` events (the last one has the `"success"` status).
+	pub async fn ollama_pull(&self, model: impl Into<ModelSpec>) -> Result<OllamaPullStream> {
+		let target = self.resolve_ollama_target(model.into(), "ollama_pull").await?;
+		let model = target.model.clone();
+
+		let WebRequestData { url, headers, payload } = OllamaAdapter::to_pull_request_data(target)?;
+
+		let stream_source = self
+			.web_client_for(&model, &url)?
+			.new_stream_source(&url, &headers, &payload)
+			.map_err(|webc_error| Error::WebModelCall {
+				model_iden: model.clone(),
+				webc_error,
+			})?;
+
+		Ok(OllamaAdapter::to_pull_stream(model, stream_source))
+	}
+
+	/// Returns the model details (context length, capabilities, quantization, ...).
+	pub async fn ollama_show(&self, model: impl Into<ModelSpec>) -> Result<OllamaModelInfo> {
+		let target = self.resolve_ollama_target(model.into(), "ollama_show").await?;
+		let model = target.model.clone();
+
+		let WebRequestData { url, headers, payload } = OllamaAdapter::to_show_request_data(target)?;
+
+		let web_res = self
+			.web_client_for(&model, &url)?
+			.do_post(&url, &headers, &payload)
+			.await
+			.map_err(|webc_error| Error::WebModelCall {
+				model_iden: model.clone(),
+				webc_error,
+			})?;
+
+		OllamaAdapter::to_model_info(&model, web_res)
+	}
+
+	/// Unloads the model from memory (`keep_alive: 0`).
+	///
+	/// Note: To control how long a model stays loaded after a chat, use `OllamaOptions::keep_alive`.
+	pub async fn ollama_unload(&self, model: impl Into<ModelSpec>) -> Result<()> {
+		let target = self.resolve_ollama_target(model.into(), "ollama_unload").await?;
+		let model = target.model.clone();
+
+		let WebRequestData { url, headers, payload } = OllamaAdapter::to_unload_request_data(target)?;
+
+		self.web_client_for(&model, &url)?
+			.do_post(&url, &headers, &payload)
+			.await
+			.map_err(|webc_error| Error::WebModelCall {
+				model_iden: model,
+				webc_error,
+			})?;
+
+		Ok(())
+	}
+}
+
+// region:    --- Support
+
+impl Client {
+	async fn resolve_ollama_target(&self, model: ModelSpec, feature: &str) -> Result<ServiceTarget> {
+		let target = self.config().resolve_model_spec(model).await?;
+
+		let adapter_kind = target.model.adapter_kind;
+		if adapter_kind != AdapterKind::Ollama {
+			return Err(Error::AdapterNotSupported {
+				adapter_kind,
+				feature: feature.to_string(),
+			});
+		}
+
+		Ok(target)
+	}
+}
+
+// endregion: --- Support
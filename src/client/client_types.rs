@@ -1,6 +1,6 @@
-use crate::webc::WebClient;
-use crate::{ClientBuilder, ClientConfig};
-use std::sync::Arc;
+use crate::webc::{ReqwestTransport, WebClient};
+use crate::{ClientBuilder, ClientConfig, Error, ModelIden, Result, webc};
+use std::sync::{Arc, OnceLock};
 
 /// Client for sending AI requests to supported providers.
 ///
@@ -42,6 +42,30 @@ impl Client {
 		&self.inner.web_client
 	}
 
+	/// The web client for a call of this model to this URL (the WebConfig override one, if any).
+	///
+	/// The override web client is built on first use, so an invalid override WebConfig fails the call.
+	pub(crate) fn web_client_for(&self, model: &ModelIden, url: &str) -> Result<&WebClient> {
+		let Some((idx, override_web_client)) = self
+			.config()
+			.resolve_web_config_override(model.adapter_kind, url)
+			.and_then(|idx| Some((idx, self.inner.override_web_clients.get(idx)?)))
+		else {
+			return Ok(self.web_client());
+		};
+		if let Some(web_client) = override_web_client.get() {
+			return Ok(web_client);
+		}
+
+		let (_, web_config) = &self.config().web_config_overrides()[idx];
+		let reqwest_client = web_config.try_new_reqwest_client().map_err(|err| Error::WebModelCall {
+			model_iden: model.clone(),
+			webc_error: webc::Error::Reqwest(err),
+		})?;
+		let transport = Arc::new(ReqwestTransport::new(reqwest_client));
+		Ok(override_web_client.get_or_init(|| self.web_client().with_transport(transport)))
+	}
+
 	pub(crate) fn config(&self) -> &ClientConfig {
 		&self.inner.config
 	}
@@ -55,6 +79,9 @@ impl Client {
 pub(super) struct ClientInner {
 	pub(super) web_client: WebClient,
 
+	/// The web clients of the `ClientConfig` WebConfig overrides (same order), built on first use.
+	pub(super) override_web_clients: Vec<OnceLock<WebClient>>,
+
 	pub(super) config: ClientConfig,
 }
 
@@ -1,9 +1,10 @@
-use crate::adapter::{AdapterDispatcher, AdapterKind};
+use crate::adapter::{AdapterDispatcher, AdapterKind, CompatProfile, MockScript};
 use crate::chat::ChatOptions;
-use crate::client::{ModelSpec, ServiceTarget};
+use crate::client::{ListModelsSpec, ModelSpec, ServiceTarget};
 use crate::embed::EmbedOptions;
-use crate::resolver::{AuthData, AuthResolver, ModelMapper, ServiceTargetResolver};
-use crate::{Error, ModelIden, Result, WebConfig};
+use crate::resolver::{AuthData, AuthResolver, Endpoint, ModelMapper, ServiceTargetResolver};
+use crate::{Error, ModelCapabilitiesRegistry, ModelIden, ModelName, Result, UsageTracker, WebConfig, WebConfigTarget};
+use std::collections::HashMap;
 
 /// Configuration for building and customizing a `Client`.
 #[derive(Debug, Default, Clone)]
@@ -12,8 +13,13 @@ pub struct ClientConfig {
 	pub(super) service_target_resolver: Option<ServiceTargetResolver>,
 	pub(super) model_mapper: Option<ModelMapper>,
 	pub(super) web_config: Option<WebConfig>,
+	pub(super) web_config_overrides: Vec<(WebConfigTarget, WebConfig)>,
 	pub(super) chat_options: Option<ChatOptions>,
 	pub(super) embed_options: Option<EmbedOptions>,
+	pub(super) model_capabilities: Option<ModelCapabilitiesRegistry>,
+	pub(super) usage_tracker: Option<UsageTracker>,
+	pub(super) mock_script: Option<MockScript>,
+	pub(super) compat_profiles: HashMap<String, CompatProfile>,
 }
 
 /// Chainable setters related to the ClientConfig.
@@ -54,6 +60,34 @@ impl ClientConfig {
 		self
 	}
 
+	/// Sets the model capabilities registry used by the pre-flight check (see `ChatOptions::with_capability_check`).
+	///
+	/// Start from `ModelCapabilitiesRegistry::default()` to keep the built-in entries.
+	pub fn with_model_capabilities(mut self, registry: ModelCapabilitiesRegistry) -> Self {
+		self.model_capabilities = Some(registry);
+		self
+	}
+
+	/// Sets the UsageTracker recording the usage of all chat and embed requests
+	/// (unless overridden per call with `ChatOptions::with_usage_tracker` / `EmbedOptions::with_usage_tracker`).
+	pub fn with_usage_tracker(mut self, usage_tracker: UsageTracker) -> Self {
+		self.usage_tracker = Some(usage_tracker);
+		self
+	}
+
+	/// Sets the MockScript serving the `AdapterKind::Mock` models (e.g., `mock::my-model`).
+	pub fn with_mock_script(mut self, mock_script: MockScript) -> Self {
+		self.mock_script = Some(mock_script);
+		self
+	}
+
+	/// Adds a CompatProfile, serving the models of its namespace with `AdapterKind::OpenAICompat`
+	/// (e.g., `vllm::Qwen/Qwen3-8B`). Replaces the previous profile of the same namespace.
+	pub fn with_compat_profile(mut self, compat_profile: CompatProfile) -> Self {
+		self.compat_profiles.insert(compat_profile.name.clone(), compat_profile);
+		self
+	}
+
 	/// Sets the HTTP client configuration (reqwest).
 	pub fn with_web_config(mut self, web_config: WebConfig) -> Self {
 		self.web_config = Some(web_config);
@@ -64,6 +98,24 @@ impl ClientConfig {
 	pub fn web_config(&self) -> Option<&WebConfig> {
 		self.web_config.as_ref()
 	}
+
+	/// Adds a WebConfig for the calls of an adapter kind or endpoint host (e.g., a proxy for Gemini only,
+	/// or longer timeouts for a reasoning provider).
+	///
+	/// Notes:
+	/// - The override replaces the client WebConfig for these calls (it is not merged, so it can drop the proxy).
+	/// - A host override takes precedence over an adapter kind override, then the first added wins.
+	/// - Each override has its own HTTP client, built on first use (a build error fails the call).
+	/// - Not applied when the client has a custom reqwest client or transport.
+	pub fn with_web_config_override(mut self, target: impl Into<WebConfigTarget>, web_config: WebConfig) -> Self {
+		self.web_config_overrides.push((target.into(), web_config));
+		self
+	}
+
+	/// Returns the WebConfig overrides, in the order added.
+	pub fn web_config_overrides(&self) -> &[(WebConfigTarget, WebConfig)] {
+		&self.web_config_overrides
+	}
 }
 
 /// Getters for the fields of ClientConfig (as references).
@@ -92,10 +144,52 @@ impl ClientConfig {
 	pub fn embed_options(&self) -> Option<&EmbedOptions> {
 		self.embed_options.as_ref()
 	}
+
+	/// Returns the model capabilities registry (the built-in one if not set).
+	pub fn model_capabilities(&self) -> &ModelCapabilitiesRegistry {
+		self.model_capabilities
+			.as_ref()
+			.unwrap_or_else(|| ModelCapabilitiesRegistry::builtin())
+	}
+
+	/// Returns the UsageTracker, if set.
+	pub fn usage_tracker(&self) -> Option<&UsageTracker> {
+		self.usage_tracker.as_ref()
+	}
+
+	/// Returns the MockScript, if set.
+	pub fn mock_script(&self) -> Option<&MockScript> {
+		self.mock_script.as_ref()
+	}
+
+	/// Returns the CompatProfile of this namespace, if set.
+	pub fn compat_profile(&self, namespace: &str) -> Option<&CompatProfile> {
+		self.compat_profiles.get(namespace)
+	}
 }
 
 /// Resolvers
 impl ClientConfig {
+	/// Returns the index of the WebConfig override for a call to this URL, if any.
+	pub(crate) fn resolve_web_config_override(&self, adapter_kind: AdapterKind, url: &str) -> Option<usize> {
+		let host = reqwest::Url::parse(url)
+			.ok()
+			.and_then(|url| url.host_str().map(|host| host.to_string()));
+
+		let by_host = host.and_then(|host| {
+			self.web_config_overrides.iter().position(|(target, _)| match target {
+				WebConfigTarget::Host(target_host) => target_host.eq_ignore_ascii_case(&host),
+				WebConfigTarget::AdapterKind(_) => false,
+			})
+		});
+
+		by_host.or_else(|| {
+			self.web_config_overrides
+				.iter()
+				.position(|(target, _)| *target == WebConfigTarget::AdapterKind(adapter_kind))
+		})
+	}
+
 	/// Resolves a ServiceTarget for the given model.
 	///
 	/// Applies the ModelMapper (if any), resolves auth (via AuthResolver or adapter default),
@@ -111,7 +205,7 @@ impl ClientConfig {
 
 		// -- Get the default endpoint
 		// For now, just get the default endpoint; the `resolve_target` will allow overriding it.
-		let endpoint = AdapterDispatcher::default_endpoint(model.adapter_kind);
+		let endpoint = self.default_endpoint_for_model(&model);
 
 		// -- Create the default service target
 		let service_target = ServiceTarget {
@@ -150,11 +244,30 @@ impl ClientConfig {
 						resolver_error: err,
 					})?
 					// default the resolver resolves to nothing
-					.unwrap_or_else(|| AdapterDispatcher::default_auth(model.adapter_kind));
+					.unwrap_or_else(|| self.default_auth_for_model(&model));
 
 				Ok(auth_data)
 			}
-			None => Ok(AdapterDispatcher::default_auth(model.adapter_kind)),
+			None => Ok(self.default_auth_for_model(&model)),
+		}
+	}
+
+	/// The adapter default endpoint, or the CompatProfile one for `AdapterKind::OpenAICompat`.
+	fn default_endpoint_for_model(&self, model: &ModelIden) -> Endpoint {
+		match model.adapter_kind {
+			AdapterKind::OpenAICompat => self.compat_profile_for(model).cloned().unwrap_or_default().endpoint,
+			kind => AdapterDispatcher::default_endpoint(kind),
+		}
+	}
+
+	/// The adapter default auth, or the CompatProfile one for `AdapterKind::OpenAICompat`.
+	fn default_auth_for_model(&self, model: &ModelIden) -> AuthData {
+		match model.adapter_kind {
+			AdapterKind::OpenAICompat => match self.compat_profile_for(model) {
+				Some(profile) => profile.default_auth(),
+				None => CompatProfile::default().default_auth(),
+			},
+			kind => AdapterDispatcher::default_auth(kind),
 		}
 	}
 
@@ -189,7 +302,7 @@ impl ClientConfig {
 	pub async fn resolve_model_spec(&self, spec: ModelSpec) -> Result<ServiceTarget> {
 		match spec {
 			ModelSpec::Name(name) => {
-				let adapter_kind = AdapterKind::from_model(&name)?;
+				let adapter_kind = self.adapter_kind_for_model(&name)?;
 				let model = ModelIden::new(adapter_kind, name);
 				self.resolve_service_target(model).await
 			}
@@ -197,4 +310,41 @@ impl ClientConfig {
 			ModelSpec::Target(target) => self.run_service_target_resolver(target).await,
 		}
 	}
+
+	/// Infers the AdapterKind from the model name (see `AdapterKind::from_model`),
+	/// with the namespaces of the CompatProfiles as `AdapterKind::OpenAICompat`.
+	///
+	/// NOTE: The built-in adapter namespaces (e.g., `openai::`, `groq::`) take precedence over the CompatProfiles.
+	pub(crate) fn adapter_kind_for_model(&self, model_name: &str) -> Result<AdapterKind> {
+		if let Some(adapter_kind) = AdapterKind::from_model_namespace(model_name) {
+			return Ok(adapter_kind);
+		}
+
+		let (namespace, _) = ModelName::split_as_namespace_and_name(model_name);
+		match namespace {
+			Some(namespace) if self.compat_profiles.contains_key(namespace) => Ok(AdapterKind::OpenAICompat),
+			_ => AdapterKind::from_model(model_name),
+		}
+	}
+
+	/// The CompatProfile of the model namespace, for an `AdapterKind::OpenAICompat` model.
+	pub(crate) fn compat_profile_for(&self, model: &ModelIden) -> Option<&CompatProfile> {
+		if model.adapter_kind != AdapterKind::OpenAICompat {
+			return None;
+		}
+		model
+			.model_name
+			.namespace()
+			.and_then(|namespace| self.compat_profile(namespace))
+	}
+
+	/// Resolves a [`ListModelsSpec`] to a [`ServiceTarget`] (the model name is empty for [`ListModelsSpec::Adapter`]).
+	pub async fn resolve_list_models_spec(&self, spec: ListModelsSpec) -> Result<ServiceTarget> {
+		match spec {
+			ListModelsSpec::Adapter(adapter_kind) => {
+				self.resolve_service_target(ModelIden::new(adapter_kind, "")).await
+			}
+			ListModelsSpec::Model(spec) => self.resolve_model_spec(spec).await,
+		}
+	}
 }
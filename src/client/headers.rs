@@ -47,6 +47,45 @@ impl Headers {
 	}
 }
 
+// region:    --- Redaction
+
+/// The value replacing the secret header values in `Headers::redacted()`.
+const REDACTED: &str = "[REDACTED]";
+
+impl Headers {
+	/// Returns a copy with the secret values (e.g., `Authorization`, `x-api-key`, `x-goog-api-key`, tokens, cookies)
+	/// replaced by `[REDACTED]`, for logging and inspection.
+	/// The auth scheme of an `Authorization` value is kept (e.g., `Bearer [REDACTED]`).
+	pub fn redacted(&self) -> Headers {
+		let inner = self
+			.inner
+			.iter()
+			.map(|(name, value)| {
+				if !is_secret_header(name) {
+					return (name.clone(), value.clone());
+				}
+				let value = match value.split_once(' ') {
+					Some((scheme, _)) if name.to_ascii_lowercase().ends_with("authorization") => {
+						format!("{scheme} {REDACTED}")
+					}
+					_ => REDACTED.to_string(),
+				};
+				(name.clone(), value)
+			})
+			.collect();
+		Headers { inner }
+	}
+}
+
+fn is_secret_header(name: &str) -> bool {
+	let name = name.to_ascii_lowercase();
+	name.ends_with("authorization")
+		|| name == "cookie"
+		|| ["key", "token", "secret", "password"].iter().any(|part| name.contains(part))
+}
+
+// endregion: --- Redaction
+
 // region:    --- Froms
 impl<K, V> From<(K, V)> for Headers
 where
@@ -0,0 +1,73 @@
+use crate::adapter::AdapterKind;
+use crate::{ModelIden, ModelName, ModelSpec, ServiceTarget};
+
+/// Specifies which provider (endpoint and auth) to list the models from, for `Client::list_models(...)`.
+///
+/// - [`ListModelsSpec::Adapter`]: The adapter kind, resolved through the client config
+///   (auth resolver, service target resolver) with an empty model name.
+///
+/// - [`ListModelsSpec::Model`]: A [`ModelSpec`], resolved like for the chat requests.
+///   Useful when the resolvers depend on the model (e.g., a `CompatProfile` namespace, or a custom Ollama host).
+#[derive(Debug, Clone)]
+pub enum ListModelsSpec {
+	Adapter(AdapterKind),
+	Model(ModelSpec),
+}
+
+// region:    --- From Implementations
+
+impl From<AdapterKind> for ListModelsSpec {
+	fn from(adapter_kind: AdapterKind) -> Self {
+		ListModelsSpec::Adapter(adapter_kind)
+	}
+}
+
+impl From<ModelSpec> for ListModelsSpec {
+	fn from(spec: ModelSpec) -> Self {
+		ListModelsSpec::Model(spec)
+	}
+}
+
+impl From<&str> for ListModelsSpec {
+	fn from(name: &str) -> Self {
+		ListModelsSpec::Model(name.into())
+	}
+}
+
+impl From<String> for ListModelsSpec {
+	fn from(name: String) -> Self {
+		ListModelsSpec::Model(name.into())
+	}
+}
+
+impl From<&String> for ListModelsSpec {
+	fn from(name: &String) -> Self {
+		ListModelsSpec::Model(name.into())
+	}
+}
+
+impl From<ModelName> for ListModelsSpec {
+	fn from(model: ModelName) -> Self {
+		ListModelsSpec::Model(model.into())
+	}
+}
+
+impl From<ModelIden> for ListModelsSpec {
+	fn from(model: ModelIden) -> Self {
+		ListModelsSpec::Model(model.into())
+	}
+}
+
+impl From<&ModelIden> for ListModelsSpec {
+	fn from(model: &ModelIden) -> Self {
+		ListModelsSpec::Model(model.into())
+	}
+}
+
+impl From<ServiceTarget> for ListModelsSpec {
+	fn from(target: ServiceTarget) -> Self {
+		ListModelsSpec::Model(target.into())
+	}
+}
+
+// endregion: --- From Implementations
@@ -6,20 +6,27 @@
 // region:    --- Modules
 
 mod builder;
+mod chat_session;
 mod client_impl;
+mod client_ollama;
 mod client_types;
 mod config;
 mod headers;
+mod list_models_spec;
 mod model_spec;
 mod service_target;
+mod usage_tracker;
 mod web_config;
 
 pub use builder::*;
+pub use chat_session::*;
 pub use client_types::*;
 pub use config::*;
 pub use headers::*;
+pub use list_models_spec::*;
 pub use model_spec::*;
 pub use service_target::*;
+pub use usage_tracker::*;
 pub use web_config::*;
 
 // endregion: --- Modules
@@ -0,0 +1,379 @@
+use crate::chat::Usage;
+use crate::{Cost, Error, ModelIden, Pricing, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+// region:    --- UsageTracker
+
+/// Accumulates the `Usage` (and cost, when a `Pricing` is set) of the requests, in total, by model, and by label.
+///
+/// Attach it to the client with `ClientBuilder::with_usage_tracker(...)` (or `ClientConfig::with_usage_tracker(...)`),
+/// or per call with `ChatOptions::with_usage_tracker(...)` / `EmbedOptions::with_usage_tracker(...)`.
+/// The label (e.g., a tenant or feature name) is set with `ChatOptions::with_usage_label(...)` / `EmbedOptions::with_usage_label(...)`.
+///
+/// The tracker is cheap to clone (all clones share the same state).
+///
+/// Notes:
+/// - Streams are recorded at the `ChatStreamEvent::End` (the usage capture is turned on for the tracked streams,
+///   so their `StreamEnd.captured_usage` is set).
+/// - Budgets are checked before each call, so a call started under the budget can go over it.
+#[derive(Debug, Clone, Default)]
+pub struct UsageTracker {
+	inner: Arc<RwLock<UsageTrackerInner>>,
+}
+
+#[derive(Debug, Default)]
+struct UsageTrackerInner {
+	pricing: Option<Pricing>,
+	budget: Option<UsageBudget>,
+	label_budgets: HashMap<String, UsageBudget>,
+	snapshot: UsageSnapshot,
+}
+
+/// Constructors & Setters
+impl UsageTracker {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the pricing table used to accumulate the cost.
+	pub fn with_pricing(self, pricing: Pricing) -> Self {
+		self.write().pricing = Some(pricing);
+		self
+	}
+
+	/// Sets the budget for the total usage (all labels).
+	pub fn with_budget(self, budget: UsageBudget) -> Self {
+		self.write().budget = Some(budget);
+		self
+	}
+
+	/// Sets the budget for the usage of a label.
+	pub fn with_label_budget(self, label: impl Into<String>, budget: UsageBudget) -> Self {
+		self.write().label_budgets.insert(label.into(), budget);
+		self
+	}
+}
+
+/// Recording & Budgets
+impl UsageTracker {
+	/// Records the usage of one request.
+	///
+	/// Note: Called by the client; only needed for the usage of requests made outside of it.
+	pub fn record(&self, model: &ModelIden, label: Option<&str>, usage: &Usage) {
+		let mut inner = self.write();
+
+		let cost = inner.pricing.as_ref().and_then(|pricing| pricing.cost(model, usage));
+		let model_key = format!("{}::{}", model.adapter_kind.as_lower_str(), model.model_name);
+
+		let snapshot = &mut inner.snapshot;
+		snapshot.total.add(usage, cost);
+		snapshot.by_model.entry(model_key).or_default().add(usage, cost);
+		if let Some(label) = label {
+			snapshot.by_label.entry(label.to_string()).or_default().add(usage, cost);
+		}
+	}
+
+	/// Returns an `Error::UsageBudgetExceeded` if the total budget, or the budget of the label, is reached.
+	pub fn check_budget(&self, label: Option<&str>) -> Result<()> {
+		let inner = self.read();
+
+		if let Some(budget) = inner.budget.as_ref()
+			&& let Some(reason) = budget.exceeded_reason(&inner.snapshot.total)
+		{
+			return Err(Error::UsageBudgetExceeded { label: None, reason });
+		}
+
+		if let Some(label) = label
+			&& let Some(budget) = inner.label_budgets.get(label)
+		{
+			let totals = inner.snapshot.by_label.get(label).cloned().unwrap_or_default();
+			if let Some(reason) = budget.exceeded_reason(&totals) {
+				return Err(Error::UsageBudgetExceeded {
+					label: Some(label.to_string()),
+					reason,
+				});
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Snapshot & Reset
+impl UsageTracker {
+	/// Returns a copy of the accumulated usage.
+	pub fn snapshot(&self) -> UsageSnapshot {
+		self.read().snapshot.clone()
+	}
+
+	/// Returns the accumulated usage of a label, if any.
+	pub fn label_totals(&self, label: &str) -> Option<UsageTotals> {
+		self.read().snapshot.by_label.get(label).cloned()
+	}
+
+	/// Resets the accumulated usage (the pricing and budgets are kept).
+	pub fn reset(&self) {
+		self.write().snapshot = UsageSnapshot::default();
+	}
+
+	/// Returns the accumulated usage, and resets it.
+	pub fn take_snapshot(&self) -> UsageSnapshot {
+		std::mem::take(&mut self.write().snapshot)
+	}
+}
+
+// Private
+impl UsageTracker {
+	fn read(&self) -> std::sync::RwLockReadGuard<'_, UsageTrackerInner> {
+		self.inner.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+
+	fn write(&self) -> std::sync::RwLockWriteGuard<'_, UsageTrackerInner> {
+		self.inner.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+// endregion: --- UsageTracker
+
+// region:    --- UsageBudget
+
+/// The hard limits of a `UsageTracker` (total, or per label).
+///
+/// Note: `max_cost` requires the tracker pricing (`UsageTracker::with_pricing`); unpriced models are not counted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageBudget {
+	pub max_total_tokens: Option<u64>,
+	pub max_requests: Option<u64>,
+	/// Maximum cost (USD).
+	pub max_cost: Option<f64>,
+}
+
+/// Chainable Setters
+impl UsageBudget {
+	pub fn with_max_total_tokens(mut self, value: u64) -> Self {
+		self.max_total_tokens = Some(value);
+		self
+	}
+
+	pub fn with_max_requests(mut self, value: u64) -> Self {
+		self.max_requests = Some(value);
+		self
+	}
+
+	pub fn with_max_cost(mut self, value: f64) -> Self {
+		self.max_cost = Some(value);
+		self
+	}
+}
+
+impl UsageBudget {
+	fn exceeded_reason(&self, totals: &UsageTotals) -> Option<String> {
+		if let Some(max) = self.max_total_tokens
+			&& totals.total_tokens >= max
+		{
+			return Some(format!("total tokens {} reached the max of {max}", totals.total_tokens));
+		}
+		if let Some(max) = self.max_requests
+			&& totals.requests >= max
+		{
+			return Some(format!("requests {} reached the max of {max}", totals.requests));
+		}
+		if let Some(max) = self.max_cost
+			&& totals.cost.total >= max
+		{
+			return Some(format!("cost ${:.6} reached the max of ${max}", totals.cost.total));
+		}
+		None
+	}
+}
+
+// endregion: --- UsageBudget
+
+// region:    --- UsageSnapshot
+
+/// The accumulated usage of a `UsageTracker`.
+///
+/// The `by_model` keys are `<adapter kind lower name>::<model name>` (e.g., `openai::gpt-4o-mini`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageSnapshot {
+	pub total: UsageTotals,
+	pub by_model: BTreeMap<String, UsageTotals>,
+	pub by_label: BTreeMap<String, UsageTotals>,
+}
+
+/// The accumulated token counts and cost of a set of requests.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageTotals {
+	pub requests: u64,
+
+	pub prompt_tokens: u64,
+	pub cached_tokens: u64,
+	pub cache_creation_tokens: u64,
+
+	pub completion_tokens: u64,
+	pub reasoning_tokens: u64,
+
+	pub total_tokens: u64,
+
+	/// The accumulated cost of the priced requests.
+	pub cost: Cost,
+	/// The number of requests without a price (not in the tracker pricing, or no pricing set).
+	pub unpriced_requests: u64,
+}
+
+impl UsageTotals {
+	fn add(&mut self, usage: &Usage, cost: Option<Cost>) {
+		let to_u64 = |value: Option<i32>| value.map(|v| v.max(0) as u64).unwrap_or(0);
+
+		let prompt_details = usage.prompt_tokens_details.as_ref();
+		let completion_details = usage.completion_tokens_details.as_ref();
+
+		let prompt_tokens = to_u64(usage.prompt_tokens);
+		let completion_tokens = to_u64(usage.completion_tokens);
+
+		self.requests += 1;
+		self.prompt_tokens += prompt_tokens;
+		self.cached_tokens += to_u64(prompt_details.and_then(|d| d.cached_tokens));
+		self.cache_creation_tokens += to_u64(prompt_details.and_then(|d| d.cache_creation_tokens));
+		self.completion_tokens += completion_tokens;
+		self.reasoning_tokens += to_u64(completion_details.and_then(|d| d.reasoning_tokens));
+		self.total_tokens += usage
+			.total_tokens
+			.map(|v| v.max(0) as u64)
+			.unwrap_or(prompt_tokens + completion_tokens);
+
+		match cost {
+			Some(cost) => self.cost += cost,
+			None => self.unpriced_requests += 1,
+		}
+	}
+}
+
+// endregion: --- UsageSnapshot
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::adapter::{AdapterKind, MockScript, MockStreamItem};
+	use crate::chat::{ChatMessage, ChatOptions, ChatRequest, ContextPolicy, ContextSummarizer};
+	use crate::{Client, PriceRates};
+	use futures::StreamExt;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	fn usage(prompt_tokens: i32, completion_tokens: i32) -> Usage {
+		Usage {
+			prompt_tokens: Some(prompt_tokens),
+			completion_tokens: Some(completion_tokens),
+			total_tokens: Some(prompt_tokens + completion_tokens),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_usage_tracker_record_and_budget() -> Result<()> {
+		// -- Setup & Fixtures
+		let tracker = UsageTracker::new()
+			.with_pricing(Pricing::default().with("gpt-4o-mini*", PriceRates::new(0.15, 0.6)))
+			.with_label_budget("tenant-a", UsageBudget::default().with_max_total_tokens(1_500));
+		let mini = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let other = ModelIden::new(AdapterKind::Anthropic, "claude-haiku-4-5");
+
+		// -- Exec
+		tracker.check_budget(Some("tenant-a"))?;
+		tracker.record(&mini, Some("tenant-a"), &usage(1_000, 500));
+		tracker.record(&other, Some("tenant-b"), &usage(100, 10));
+
+		// -- Check
+		let snapshot = tracker.snapshot();
+		assert_eq!(snapshot.total.requests, 2);
+		assert_eq!(snapshot.total.total_tokens, 1_610);
+		assert_eq!(snapshot.total.unpriced_requests, 1);
+		assert_eq!(snapshot.by_model.len(), 2);
+		let tenant_a = tracker.label_totals("tenant-a").ok_or("Should have tenant-a")?;
+		assert!((tenant_a.cost.total - (0.001 * 0.15 + 0.0005 * 0.6)).abs() < 1e-12);
+
+		assert!(matches!(
+			tracker.check_budget(Some("tenant-a")),
+			Err(Error::UsageBudgetExceeded { label: Some(_), .. })
+		));
+		tracker.check_budget(Some("tenant-b"))?;
+
+		// -- Check reset
+		tracker.reset();
+		assert_eq!(tracker.snapshot().total.requests, 0);
+		tracker.check_budget(Some("tenant-a"))?;
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_usage_tracker_records_stream_without_capture_usage() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		mock.push_stream([MockStreamItem::Chunk("Hello".to_string()), MockStreamItem::Usage(usage(10, 5))]);
+		let tracker = UsageTracker::new().with_budget(UsageBudget::default().with_max_total_tokens(10));
+		let client = Client::builder()
+			.with_mock_script(mock)
+			.with_usage_tracker(tracker.clone())
+			.build();
+
+		// -- Exec
+		let mut stream = client
+			.exec_chat_stream("mock::test-model", ChatRequest::from_user("Hi"), None)
+			.await?
+			.stream;
+		while let Some(event) = stream.next().await {
+			event?;
+		}
+
+		// -- Check
+		let snapshot = tracker.snapshot();
+		assert_eq!(snapshot.total.requests, 1);
+		assert_eq!(snapshot.total.total_tokens, 15);
+		assert!(matches!(
+			tracker.check_budget(None),
+			Err(Error::UsageBudgetExceeded { .. })
+		));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_usage_tracker_budget_before_summarizer() -> Result<()> {
+		// -- Setup & Fixtures
+		let mock = MockScript::new();
+		mock.push_text("The summary");
+		mock.push_text("The answer");
+		let tracker = UsageTracker::new().with_budget(UsageBudget::default().with_max_total_tokens(10));
+		tracker.record(&ModelIden::new(AdapterKind::Mock, "test-model"), None, &usage(10, 5));
+		let client = Client::builder()
+			.with_mock_script(mock.clone())
+			.with_usage_tracker(tracker)
+			.build();
+		let chat_req = ChatRequest::new(vec![
+			ChatMessage::user("First question"),
+			ChatMessage::assistant("First answer"),
+			ChatMessage::user("Second question"),
+		]);
+		let policy = ContextPolicy::default()
+			.with_max_turns(1)
+			.with_summarizer(ContextSummarizer::new("mock::summary-model"));
+		let options = ChatOptions::default().with_context_policy(policy);
+
+		// -- Exec
+		let res = client.exec_chat("mock::test-model", chat_req, Some(&options)).await;
+
+		// -- Check
+		assert!(matches!(res, Err(Error::UsageBudgetExceeded { .. })));
+		assert_eq!(mock.call_count(), 0, "the summarizer should not be called");
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,3 +1,6 @@
+use crate::adapter::AdapterKind;
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// Reqwest client configuration.
@@ -8,6 +11,24 @@ pub struct WebConfig {
 	pub read_timeout: Option<Duration>,
 	pub default_headers: Option<reqwest::header::HeaderMap>,
 	pub proxy: Option<reqwest::Proxy>,
+	pub user_agent: Option<String>,
+
+	// -- TLS
+	/// Root certificates added to the built-in ones (e.g., an internal CA).
+	pub root_certificates: Vec<reqwest::Certificate>,
+	/// The client certificate and key, for mTLS.
+	pub identity: Option<reqwest::Identity>,
+	/// DANGER: Accepts invalid certificates (for development only).
+	pub danger_accept_invalid_certs: Option<bool>,
+
+	// -- Connection
+	pub http2_prior_knowledge: Option<bool>,
+	pub pool_idle_timeout: Option<Duration>,
+	pub pool_max_idle_per_host: Option<usize>,
+	pub tcp_keepalive: Option<Duration>,
+	pub local_address: Option<IpAddr>,
+	/// Sends all the requests through this Unix domain socket (Unix only; the TCP and proxy options are ignored).
+	pub unix_socket: Option<PathBuf>,
 }
 
 impl WebConfig {
@@ -23,12 +44,24 @@ impl WebConfig {
 		self
 	}
 
+	/// Sets the read timeout (between two reads of the response).
+	pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+		self.read_timeout = Some(timeout);
+		self
+	}
+
 	/// Sets default headers.
 	pub fn with_default_headers(mut self, headers: reqwest::header::HeaderMap) -> Self {
 		self.default_headers = Some(headers);
 		self
 	}
 
+	/// Sets the `User-Agent` header.
+	pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+		self.user_agent = Some(user_agent.into());
+		self
+	}
+
 	/// Sets the proxy.
 	pub fn with_proxy(mut self, proxy: reqwest::Proxy) -> Self {
 		self.proxy = Some(proxy);
@@ -55,6 +88,96 @@ impl WebConfig {
 		self.proxy = Some(proxy);
 		Ok(self)
 	}
+}
+
+/// TLS setters
+impl WebConfig {
+	/// Adds a root certificate (trusted in addition to the built-in ones).
+	pub fn with_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+		self.root_certificates.push(certificate);
+		self
+	}
+
+	/// Adds the root certificates of a PEM bundle (one or more certificates).
+	pub fn with_root_certificates_pem(mut self, pem_bundle: &[u8]) -> Result<Self, reqwest::Error> {
+		let certificates = reqwest::Certificate::from_pem_bundle(pem_bundle)?;
+		self.root_certificates.extend(certificates);
+		Ok(self)
+	}
+
+	/// Sets the client certificate identity (mTLS).
+	pub fn with_identity(mut self, identity: reqwest::Identity) -> Self {
+		self.identity = Some(identity);
+		self
+	}
+
+	/// Sets the client certificate identity (mTLS) from a PEM buffer with the certificate chain and the private key.
+	pub fn with_identity_pem(mut self, pem: &[u8]) -> Result<Self, reqwest::Error> {
+		let identity = reqwest::Identity::from_pem(pem)?;
+		self.identity = Some(identity);
+		Ok(self)
+	}
+
+	/// DANGER: Accepts invalid certificates (expired, self-signed, wrong host). For development only.
+	pub fn with_danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+		self.danger_accept_invalid_certs = Some(accept);
+		self
+	}
+}
+
+/// Connection setters
+impl WebConfig {
+	/// Uses HTTP/2 without the HTTP/1.1 upgrade negotiation (the server must support it).
+	pub fn with_http2_prior_knowledge(mut self, enabled: bool) -> Self {
+		self.http2_prior_knowledge = Some(enabled);
+		self
+	}
+
+	/// Sets how long an idle pooled connection is kept alive.
+	pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+		self.pool_idle_timeout = Some(timeout);
+		self
+	}
+
+	/// Sets the maximum number of idle pooled connections per host.
+	pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+		self.pool_max_idle_per_host = Some(max);
+		self
+	}
+
+	/// Sets the TCP keepalive interval.
+	pub fn with_tcp_keepalive(mut self, interval: Duration) -> Self {
+		self.tcp_keepalive = Some(interval);
+		self
+	}
+
+	/// Binds the connections to this local address.
+	pub fn with_local_address(mut self, address: IpAddr) -> Self {
+		self.local_address = Some(address);
+		self
+	}
+
+	/// Sends all the requests through this Unix domain socket (e.g., a local Ollama or vLLM).
+	///
+	/// Note: The request URL host is not resolved (the path is still used), and this is ignored on non-Unix targets.
+	pub fn with_unix_socket(mut self, path: impl Into<PathBuf>) -> Self {
+		self.unix_socket = Some(path.into());
+		self
+	}
+}
+
+impl WebConfig {
+	/// Builds the reqwest::Client of this config.
+	///
+	/// Panics if the reqwest client cannot be built (e.g., an invalid TLS configuration).
+	pub(crate) fn new_reqwest_client(&self) -> reqwest::Client {
+		self.try_new_reqwest_client().expect("Failed to build reqwest client")
+	}
+
+	/// Builds the reqwest::Client of this config, or returns the reqwest build error.
+	pub(crate) fn try_new_reqwest_client(&self) -> reqwest::Result<reqwest::Client> {
+		self.apply_to_builder(reqwest::Client::builder()).build()
+	}
 
 	/// Applies this config to a reqwest::ClientBuilder.
 	pub fn apply_to_builder(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
@@ -73,6 +196,204 @@ impl WebConfig {
 		if let Some(ref proxy) = self.proxy {
 			builder = builder.proxy(proxy.clone());
 		}
+		if let Some(ref user_agent) = self.user_agent {
+			builder = builder.user_agent(user_agent.clone());
+		}
+
+		// -- TLS
+		if !self.root_certificates.is_empty() {
+			builder = builder.tls_certs_merge(self.root_certificates.iter().cloned());
+		}
+		if let Some(ref identity) = self.identity {
+			builder = builder.identity(identity.clone());
+		}
+		if let Some(accept) = self.danger_accept_invalid_certs {
+			builder = builder.tls_danger_accept_invalid_certs(accept);
+		}
+
+		// -- Connection
+		if self.http2_prior_knowledge == Some(true) {
+			builder = builder.http2_prior_knowledge();
+		}
+		if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+			builder = builder.pool_idle_timeout(pool_idle_timeout);
+		}
+		if let Some(max) = self.pool_max_idle_per_host {
+			builder = builder.pool_max_idle_per_host(max);
+		}
+		if let Some(tcp_keepalive) = self.tcp_keepalive {
+			builder = builder.tcp_keepalive(tcp_keepalive);
+		}
+		if let Some(local_address) = self.local_address {
+			builder = builder.local_address(local_address);
+		}
+		#[cfg(unix)]
+		if let Some(ref unix_socket) = self.unix_socket {
+			builder = builder.unix_socket(unix_socket.clone());
+		}
+
 		builder
 	}
 }
+
+// region:    --- WebConfigTarget
+
+/// The calls a `WebConfig` override applies to (see `ClientConfig::with_web_config_override`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebConfigTarget {
+	/// The calls of the models of this adapter kind.
+	AdapterKind(AdapterKind),
+	/// The calls to this endpoint host (e.g., `localhost` or `generativelanguage.googleapis.com`).
+	Host(String),
+}
+
+impl WebConfigTarget {
+	pub fn host(host: impl Into<String>) -> Self {
+		Self::Host(host.into())
+	}
+}
+
+impl From<AdapterKind> for WebConfigTarget {
+	fn from(adapter_kind: AdapterKind) -> Self {
+		Self::AdapterKind(adapter_kind)
+	}
+}
+
+// endregion: --- WebConfigTarget
+
+// region:    --- Tests
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+	use crate::Client;
+	use crate::chat::ChatRequest;
+	use std::io::{Read, Write};
+	use std::os::unix::net::{UnixListener, UnixStream};
+	use std::path::Path;
+	use std::thread::JoinHandle;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_web_config_unix_socket_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let path = std::env::temp_dir().join(format!("genai-{}.sock", uuid::Uuid::new_v4()));
+		let server = spawn_ollama_server(&path)?;
+		let web_config = WebConfig::default()
+			.with_unix_socket(&path)
+			.with_user_agent("genai-test")
+			.with_pool_max_idle_per_host(0);
+		let client = Client::builder().with_web_config(web_config).build();
+
+		// -- Exec
+		let chat_res = client.exec_chat("ollama::llama3", ChatRequest::from_user("Hi"), None).await?;
+
+		// -- Check
+		assert_eq!(chat_res.first_text(), Some("Hello!"));
+		let request = server.join().map_err(|_| "Server thread panicked")??;
+		assert!(request.starts_with("POST /api/chat "));
+		assert!(request.to_lowercase().contains("user-agent: genai-test"));
+		std::fs::remove_file(&path)?;
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_web_config_override_ok() -> Result<()> {
+		// -- Setup & Fixtures
+		let path = std::env::temp_dir().join(format!("genai-{}.sock", uuid::Uuid::new_v4()));
+		let server = spawn_ollama_server(&path)?;
+		let client = Client::builder()
+			.with_web_config(WebConfig::default().with_all_proxy_url("http://proxy.invalid:3128")?)
+			.with_web_config_override(AdapterKind::Ollama, WebConfig::default().with_unix_socket(&path))
+			.with_web_config_override(WebConfigTarget::host("api.openai.com"), WebConfig::default())
+			.build();
+
+		// -- Exec
+		let built_before = client
+			.inner
+			.override_web_clients
+			.iter()
+			.any(|web_client| web_client.get().is_some());
+		let chat_res = client.exec_chat("ollama::llama3", ChatRequest::from_user("Hi"), None).await?;
+
+		// -- Check
+		assert_eq!(chat_res.first_text(), Some("Hello!"));
+		// The override web clients are built on first use (only the Ollama one here)
+		assert!(!built_before);
+		assert!(client.inner.override_web_clients[0].get().is_some());
+		assert!(client.inner.override_web_clients[1].get().is_none());
+		server.join().map_err(|_| "Server thread panicked")??;
+		let config = client.config();
+		let ollama_url = "http://localhost:11434/api/chat";
+		assert_eq!(
+			config.resolve_web_config_override(AdapterKind::Ollama, ollama_url),
+			Some(0)
+		);
+		let openai_url = "https://api.openai.com/v1/chat/completions";
+		assert_eq!(
+			config.resolve_web_config_override(AdapterKind::Ollama, openai_url),
+			Some(1)
+		);
+		assert_eq!(
+			config.resolve_web_config_override(AdapterKind::Gemini, ollama_url),
+			None
+		);
+		std::fs::remove_file(&path)?;
+
+		Ok(())
+	}
+
+	// region:    --- Support
+
+	/// Serves one Ollama chat request on the Unix socket, and returns the request.
+	fn spawn_ollama_server(path: &Path) -> std::io::Result<JoinHandle<std::io::Result<String>>> {
+		let listener = UnixListener::bind(path)?;
+		Ok(std::thread::spawn(move || {
+			let (mut stream, _) = listener.accept()?;
+			let request = read_http_request(&mut stream)?;
+			let body = r#"{"model":"llama3","message":{"role":"assistant","content":"Hello!"},"done":true}"#;
+			write!(
+				stream,
+				"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+				body.len()
+			)?;
+			Ok(request)
+		}))
+	}
+
+	/// Reads the request head and its `content-length` body.
+	fn read_http_request(stream: &mut UnixStream) -> std::io::Result<String> {
+		let mut data = Vec::new();
+		let mut buf = [0u8; 4096];
+		loop {
+			let n = stream.read(&mut buf)?;
+			if n == 0 {
+				break;
+			}
+			data.extend_from_slice(&buf[..n]);
+
+			let text = String::from_utf8_lossy(&data);
+			if let Some(head_end) = text.find("\r\n\r\n") {
+				let content_length = text[..head_end]
+					.lines()
+					.find_map(|line| {
+						line.to_lowercase()
+							.strip_prefix("content-length:")
+							.map(|v| v.trim().to_string())
+					})
+					.and_then(|v| v.parse::<usize>().ok())
+					.unwrap_or(0);
+				if data.len() >= head_end + 4 + content_length {
+					break;
+				}
+			}
+		}
+		Ok(String::from_utf8_lossy(&data).into_owned())
+	}
+
+	// endregion: --- Support
+}
+
+// endregion: --- Tests
@@ -1,9 +1,15 @@
 // region:    --- Modules
 
+mod model_capabilities;
 mod model_iden;
+mod model_info;
 mod model_name;
+mod pricing;
 
+pub use model_capabilities::*;
 pub use model_iden::*;
+pub use model_info::*;
 pub use model_name::*;
+pub use pricing::*;
 
 // endregion: --- Modules
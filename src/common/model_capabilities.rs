@@ -0,0 +1,640 @@
+//! Static model capabilities, used by the opt-in pre-flight check of the chat requests
+//! (see `ChatOptions::with_capability_check`).
+//!
+//! The built-in registry covers the main model families, and can be extended or overridden
+//! with `ClientConfig::with_model_capabilities(...)`.
+
+use crate::ModelIden;
+use crate::adapter::AdapterKind;
+use crate::chat::{
+	ChatOptionsSet, ChatRequest, ChatResponseFormat, ContentPart, ReasoningEffort, estimate_input_tokens,
+};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+// region:    --- ModelCapabilities
+
+/// The capabilities of a model (or model family).
+///
+/// Note: `None` means unknown, and is never checked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCapabilities {
+	/// Maximum input context (in tokens).
+	pub context_window: Option<u32>,
+	/// Maximum output tokens (checked against `ChatOptions::max_tokens`).
+	pub max_output_tokens: Option<u32>,
+
+	pub image_input: Option<bool>,
+	pub audio_input: Option<bool>,
+	pub pdf_input: Option<bool>,
+
+	pub tools: Option<bool>,
+	pub reasoning: Option<ReasoningStyle>,
+	/// Structured output with a JSON schema (`ChatResponseFormat::JsonSpec`).
+	pub json_schema: Option<bool>,
+}
+
+/// Chainable Setters
+impl ModelCapabilities {
+	pub fn with_context_window(mut self, value: u32) -> Self {
+		self.context_window = Some(value);
+		self
+	}
+
+	pub fn with_max_output_tokens(mut self, value: u32) -> Self {
+		self.max_output_tokens = Some(value);
+		self
+	}
+
+	pub fn with_image_input(mut self, value: bool) -> Self {
+		self.image_input = Some(value);
+		self
+	}
+
+	pub fn with_audio_input(mut self, value: bool) -> Self {
+		self.audio_input = Some(value);
+		self
+	}
+
+	pub fn with_pdf_input(mut self, value: bool) -> Self {
+		self.pdf_input = Some(value);
+		self
+	}
+
+	pub fn with_tools(mut self, value: bool) -> Self {
+		self.tools = Some(value);
+		self
+	}
+
+	pub fn with_reasoning(mut self, value: ReasoningStyle) -> Self {
+		self.reasoning = Some(value);
+		self
+	}
+
+	pub fn with_json_schema(mut self, value: bool) -> Self {
+		self.json_schema = Some(value);
+		self
+	}
+}
+
+/// How the model reasoning is controlled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReasoningStyle {
+	/// Not a reasoning model (a `ChatOptions::reasoning_effort` is an error).
+	None,
+	/// Reasoning effort levels (e.g., OpenAI `low`, `medium`, `high`).
+	Effort,
+	/// Reasoning token budget (e.g., Anthropic / Gemini 2.5 thinking budget).
+	Budget,
+	/// Always reasoning, not configurable (e.g., `deepseek-reasoner`).
+	Always,
+}
+
+// endregion: --- ModelCapabilities
+
+// region:    --- ModelPattern
+
+/// A `ModelIden` pattern, with an optional adapter kind and a model name with `*` wildcards.
+///
+/// From a string: `"gpt-4o*"` (any adapter), or `"openai::gpt-4o*"` (adapter kind lower name as namespace).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelPattern {
+	pub adapter_kind: Option<AdapterKind>,
+	pub name: String,
+}
+
+impl ModelPattern {
+	pub fn new(adapter_kind: Option<AdapterKind>, name: impl Into<String>) -> Self {
+		Self {
+			adapter_kind,
+			name: name.into(),
+		}
+	}
+
+	/// Returns true if the model matches (the model name namespace is ignored).
+	pub fn matches(&self, model: &ModelIden) -> bool {
+		if let Some(adapter_kind) = self.adapter_kind
+			&& adapter_kind != model.adapter_kind
+		{
+			return false;
+		}
+		let (_, model_name) = model.model_name.namespace_and_name();
+		glob_match(&self.name, model_name)
+	}
+}
+
+impl From<&str> for ModelPattern {
+	fn from(pattern: &str) -> Self {
+		if let Some((namespace, name)) = pattern.split_once("::")
+			&& let Some(adapter_kind) = AdapterKind::from_lower_str(namespace)
+		{
+			return ModelPattern::new(Some(adapter_kind), name);
+		}
+		ModelPattern::new(None, pattern)
+	}
+}
+
+impl From<String> for ModelPattern {
+	fn from(pattern: String) -> Self {
+		ModelPattern::from(pattern.as_str())
+	}
+}
+
+/// Formats as the `From<&str>` form (e.g., `openai::gpt-4o*`).
+impl std::fmt::Display for ModelPattern {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.adapter_kind {
+			Some(adapter_kind) => write!(f, "{}::{}", adapter_kind.as_lower_str(), self.name),
+			None => write!(f, "{}", self.name),
+		}
+	}
+}
+
+impl From<(AdapterKind, &str)> for ModelPattern {
+	fn from((adapter_kind, name): (AdapterKind, &str)) -> Self {
+		ModelPattern::new(Some(adapter_kind), name)
+	}
+}
+
+/// Simple glob match with `*` wildcards only.
+fn glob_match(pattern: &str, text: &str) -> bool {
+	let mut segments = pattern.split('*');
+
+	// The first segment must be a prefix (when the pattern does not start with `*`)
+	let first = segments.next().unwrap_or_default();
+	let Some(mut rest) = text.strip_prefix(first) else {
+		return false;
+	};
+
+	let segments: Vec<&str> = segments.collect();
+	let Some((last, middles)) = segments.split_last() else {
+		// No `*` in the pattern
+		return rest.is_empty();
+	};
+
+	for segment in middles {
+		match rest.find(segment) {
+			Some(idx) => rest = &rest[idx + segment.len()..],
+			None => return false,
+		}
+	}
+
+	rest.ends_with(last)
+}
+
+// endregion: --- ModelPattern
+
+// region:    --- ModelCapabilitiesRegistry
+
+/// The registry of `ModelCapabilities` by `ModelPattern` (first match wins).
+///
+/// `ModelCapabilitiesRegistry::default()` has the built-in entries, and the entries added
+/// with `with(...)` take precedence over them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelCapabilitiesRegistry {
+	entries: Vec<(ModelPattern, ModelCapabilities)>,
+}
+
+impl Default for ModelCapabilitiesRegistry {
+	fn default() -> Self {
+		BUILTIN_REGISTRY.clone()
+	}
+}
+
+/// Constructors
+impl ModelCapabilitiesRegistry {
+	/// A registry without any entry (not even the built-in ones).
+	pub fn empty() -> Self {
+		Self { entries: Vec::new() }
+	}
+}
+
+/// Chainable setters
+impl ModelCapabilitiesRegistry {
+	/// Adds an entry, with precedence over the existing ones.
+	pub fn with(mut self, pattern: impl Into<ModelPattern>, capabilities: ModelCapabilities) -> Self {
+		self.register(pattern, capabilities);
+		self
+	}
+
+	/// Adds an entry, with precedence over the existing ones.
+	pub fn register(&mut self, pattern: impl Into<ModelPattern>, capabilities: ModelCapabilities) {
+		self.entries.insert(0, (pattern.into(), capabilities));
+	}
+}
+
+/// Getters
+impl ModelCapabilitiesRegistry {
+	/// Returns the capabilities of the first matching pattern.
+	pub fn get(&self, model: &ModelIden) -> Option<&ModelCapabilities> {
+		self.entries
+			.iter()
+			.find(|(pattern, _)| pattern.matches(model))
+			.map(|(_, capabilities)| capabilities)
+	}
+
+	pub(crate) fn builtin() -> &'static ModelCapabilitiesRegistry {
+		&BUILTIN_REGISTRY
+	}
+}
+
+// endregion: --- ModelCapabilitiesRegistry
+
+// region:    --- Pre-flight Check
+
+impl ModelCapabilities {
+	/// Validates the tools and the options of the chat request, before any network call.
+	///
+	/// NOTE: Not changed by the `ContextPolicy` trim, so checked before its summarizer call.
+	pub(crate) fn check_chat_options(
+		&self,
+		model_iden: &ModelIden,
+		chat_req: &ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+	) -> Result<()> {
+		let not_supported = |capability: &'static str| Error::ModelCapabilityNotSupported {
+			model_iden: model_iden.clone(),
+			capability,
+		};
+
+		// -- Tools
+		if chat_req.tools.as_ref().is_some_and(|tools| !tools.is_empty()) && self.tools == Some(false) {
+			return Err(not_supported("tools"));
+		}
+
+		// -- Response format
+		if matches!(options_set.response_format(), Some(ChatResponseFormat::JsonSpec(_)))
+			&& self.json_schema == Some(false)
+		{
+			return Err(not_supported("json schema"));
+		}
+
+		// -- Reasoning
+		if self.reasoning == Some(ReasoningStyle::None)
+			&& options_set
+				.reasoning_effort()
+				.is_some_and(|effort| !matches!(effort, ReasoningEffort::None))
+		{
+			return Err(not_supported("reasoning effort"));
+		}
+
+		// -- Max output tokens
+		if let (Some(max_tokens), Some(max_output_tokens)) = (options_set.max_tokens(), self.max_output_tokens)
+			&& max_tokens > max_output_tokens
+		{
+			return Err(Error::MaxOutputTokensExceeded {
+				model_iden: model_iden.clone(),
+				max_tokens,
+				max_output_tokens,
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Validates the messages of the chat request (content types and context window), once trimmed.
+	pub(crate) fn check_chat_messages(&self, model_iden: &ModelIden, chat_req: &ChatRequest) -> Result<()> {
+		let not_supported = |capability: &'static str| Error::ModelCapabilityNotSupported {
+			model_iden: model_iden.clone(),
+			capability,
+		};
+
+		// -- Content types
+		for part in chat_req.messages.iter().flat_map(|msg| msg.content.parts()) {
+			let ContentPart::Binary(binary) = part else {
+				continue;
+			};
+			if binary.is_image() && self.image_input == Some(false) {
+				return Err(not_supported("image input"));
+			}
+			if binary.is_audio() && self.audio_input == Some(false) {
+				return Err(not_supported("audio input"));
+			}
+			if binary.is_pdf() && self.pdf_input == Some(false) {
+				return Err(not_supported("pdf input"));
+			}
+		}
+
+		// -- Context window
+		if let Some(context_window) = self.context_window {
+			let estimated_tokens = estimate_input_tokens(chat_req);
+			if estimated_tokens > context_window {
+				return Err(Error::ContextWindowExceeded {
+					model_iden: model_iden.clone(),
+					estimated_tokens,
+					context_window,
+				});
+			}
+		}
+
+		Ok(())
+	}
+}
+
+// endregion: --- Pre-flight Check
+
+// region:    --- Built-in Registry
+
+/// NOTE: The patterns are scoped to their adapter kind (the same model name served by another adapter may differ),
+///       and the more specific patterns must be before the more general ones.
+static BUILTIN_REGISTRY: LazyLock<ModelCapabilitiesRegistry> = LazyLock::new(|| {
+	let multimodal = ModelCapabilities::default()
+		.with_image_input(true)
+		.with_pdf_input(true)
+		.with_tools(true);
+	let text_only = ModelCapabilities::default()
+		.with_image_input(false)
+		.with_audio_input(false)
+		.with_pdf_input(false);
+
+	let entries: Vec<(ModelPattern, ModelCapabilities)> = vec![
+		// -- OpenAI
+		(
+			"openai::gpt-5*".into(),
+			multimodal
+				.clone()
+				.with_context_window(400_000)
+				.with_max_output_tokens(128_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Effort)
+				.with_json_schema(true),
+		),
+		(
+			"openai::gpt-4.1*".into(),
+			multimodal
+				.clone()
+				.with_context_window(1_047_576)
+				.with_max_output_tokens(32_768)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::None)
+				.with_json_schema(true),
+		),
+		(
+			"openai::gpt-4o*".into(),
+			multimodal
+				.clone()
+				.with_context_window(128_000)
+				.with_max_output_tokens(16_384)
+				.with_reasoning(ReasoningStyle::None)
+				.with_json_schema(true),
+		),
+		(
+			"openai::o4*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(100_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Effort)
+				.with_json_schema(true),
+		),
+		(
+			"openai::o3*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(100_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Effort)
+				.with_json_schema(true),
+		),
+		(
+			"openai::o1*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(100_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Effort)
+				.with_json_schema(true),
+		),
+		(
+			"openai::gpt-3.5-turbo*".into(),
+			text_only
+				.clone()
+				.with_context_window(16_385)
+				.with_max_output_tokens(4_096)
+				.with_tools(true)
+				.with_reasoning(ReasoningStyle::None)
+				.with_json_schema(false),
+		),
+		// -- Anthropic
+		(
+			"anthropic::claude-opus-4-5*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(64_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Budget),
+		),
+		(
+			"anthropic::claude-opus-4*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(32_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Budget),
+		),
+		(
+			"anthropic::claude-sonnet-4*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(64_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Budget),
+		),
+		(
+			"anthropic::claude-haiku-4*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(64_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Budget),
+		),
+		(
+			"anthropic::claude-3-7-sonnet*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(64_000)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::Budget),
+		),
+		(
+			"anthropic::claude-3-5-haiku*".into(),
+			multimodal
+				.clone()
+				.with_context_window(200_000)
+				.with_max_output_tokens(8_192)
+				.with_audio_input(false)
+				.with_reasoning(ReasoningStyle::None),
+		),
+		// -- Gemini
+		(
+			"gemini::gemini-3*".into(),
+			multimodal
+				.clone()
+				.with_context_window(1_048_576)
+				.with_max_output_tokens(65_536)
+				.with_audio_input(true)
+				.with_reasoning(ReasoningStyle::Effort)
+				.with_json_schema(true),
+		),
+		(
+			"gemini::gemini-2.5*".into(),
+			multimodal
+				.clone()
+				.with_context_window(1_048_576)
+				.with_max_output_tokens(65_536)
+				.with_audio_input(true)
+				.with_reasoning(ReasoningStyle::Budget)
+				.with_json_schema(true),
+		),
+		(
+			"gemini::gemini-2.0*".into(),
+			multimodal
+				.clone()
+				.with_context_window(1_048_576)
+				.with_max_output_tokens(8_192)
+				.with_audio_input(true)
+				.with_reasoning(ReasoningStyle::None)
+				.with_json_schema(true),
+		),
+		// -- DeepSeek
+		(
+			"deepseek::deepseek-reasoner*".into(),
+			text_only
+				.clone()
+				.with_context_window(128_000)
+				.with_max_output_tokens(64_000)
+				.with_reasoning(ReasoningStyle::Always),
+		),
+		(
+			"deepseek::deepseek-chat*".into(),
+			text_only
+				.clone()
+				.with_context_window(128_000)
+				.with_max_output_tokens(8_192)
+				.with_tools(true)
+				.with_reasoning(ReasoningStyle::None),
+		),
+	];
+
+	ModelCapabilitiesRegistry { entries }
+});
+
+// endregion: --- Built-in Registry
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chat::{Binary, ChatMessage, ChatOptions, ContentPart};
+
+	#[test]
+	fn test_model_capabilities_glob_match() {
+		assert!(glob_match("gpt-4o*", "gpt-4o-mini"));
+		assert!(glob_match("*-mini", "gpt-4o-mini"));
+		assert!(glob_match("claude-*-4*", "claude-sonnet-4-5"));
+		assert!(glob_match("gpt-4o", "gpt-4o"));
+		assert!(!glob_match("gpt-4o", "gpt-4o-mini"));
+		assert!(!glob_match("gpt-4.1*", "gpt-4o"));
+	}
+
+	#[test]
+	fn test_model_capabilities_registry_precedence() {
+		let model = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let registry = ModelCapabilitiesRegistry::default()
+			.with("openai::gpt-4o-mini", ModelCapabilities::default().with_tools(false));
+
+		let capabilities = registry.get(&model).unwrap();
+		assert_eq!(capabilities.tools, Some(false));
+
+		// other adapter kind does not match (the built-in patterns are scoped to their adapter kind)
+		let model = ModelIden::new(AdapterKind::Ollama, "gpt-4o-mini");
+		assert!(registry.get(&model).is_none());
+	}
+
+	#[test]
+	fn test_model_capabilities_check_chat_request() {
+		let model = ModelIden::new(AdapterKind::DeepSeek, "deepseek-chat");
+		let capabilities = ModelCapabilitiesRegistry::builtin().get(&model).unwrap();
+		let options = ChatOptions::default().with_max_tokens(100);
+		let options_set = ChatOptionsSet::default().with_chat_options(Some(&options));
+
+		// -- image on a text only model
+		let image = Binary::from_url("image/png", "https://example.com/duck.png", None);
+		let chat_req = ChatRequest::new(vec![ChatMessage::user(vec![
+			ContentPart::from_text("What is it?"),
+			image.into(),
+		])]);
+		let res = capabilities.check_chat_messages(&model, &chat_req);
+		assert!(matches!(
+			res,
+			Err(Error::ModelCapabilityNotSupported {
+				capability: "image input",
+				..
+			})
+		));
+
+		// -- too many tokens
+		let chat_req = ChatRequest::from_user("hello ".repeat(100_000));
+		let res = capabilities.check_chat_messages(&model, &chat_req);
+		assert!(matches!(res, Err(Error::ContextWindowExceeded { .. })));
+
+		// -- ok
+		let chat_req = ChatRequest::from_user("hello");
+		capabilities.check_chat_options(&model, &chat_req, &options_set).unwrap();
+		capabilities.check_chat_messages(&model, &chat_req).unwrap();
+	}
+
+	#[test]
+	fn test_model_capabilities_check_max_tokens_limits() {
+		let fx_cases = [
+			("claude-opus-4-5-20251101", 64_000, true),
+			("claude-opus-4-1-20250805", 32_000, true),
+			("claude-opus-4-1-20250805", 64_000, false),
+			("claude-sonnet-4-5", 64_000, true),
+			("gpt-4.1-mini", 32_768, true),
+			("gpt-4.1-mini", 32_769, false),
+		];
+
+		for (model_name, max_tokens, valid) in fx_cases {
+			let adapter_kind = AdapterKind::from_model(model_name).unwrap();
+			let model = ModelIden::new(adapter_kind, model_name);
+			let capabilities = ModelCapabilitiesRegistry::builtin().get(&model).unwrap();
+			let options = ChatOptions::default().with_max_tokens(max_tokens);
+			let options_set = ChatOptionsSet::default().with_chat_options(Some(&options));
+
+			let res = capabilities.check_chat_options(&model, &ChatRequest::from_user("hello"), &options_set);
+
+			assert_eq!(res.is_ok(), valid, "model: {model_name}, max_tokens: {max_tokens}");
+		}
+	}
+
+	// Note: The pre-flight check fails before any network call.
+	#[tokio::test]
+	async fn test_chat_capability_check_max_tokens_err() {
+		// -- Setup & Fixtures
+		let client = crate::Client::default();
+		let chat_req = ChatRequest::from_user("Why is the sky blue?");
+		let options = ChatOptions::default().with_max_tokens(1_000_000).with_capability_check(true);
+
+		// -- Exec
+		let res = client.exec_chat("gpt-4.1-mini", chat_req, Some(&options)).await;
+
+		// -- Check
+		assert!(
+			matches!(res, Err(Error::MaxOutputTokensExceeded { .. })),
+			"Should be MaxOutputTokensExceeded"
+		);
+	}
+}
+
+// endregion: --- Tests
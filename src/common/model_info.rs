@@ -0,0 +1,92 @@
+use crate::ModelIden;
+use crate::adapter::AdapterKind;
+use serde::{Deserialize, Serialize};
+
+/// Model information returned by `Client::list_models(...)`, parsed from the provider models endpoint.
+///
+/// Note: Providers expose different levels of details, so all but the `id` are optional
+///       (`None` or empty when the provider does not return it).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInfo {
+	/// The adapter kind the model was listed from.
+	pub adapter_kind: AdapterKind,
+
+	/// The model id, as used in the requests (e.g., `gpt-4o-mini`, `gemini-2.5-flash`).
+	pub id: String,
+
+	/// The human-readable name, when provided (e.g., `Claude Sonnet 4`).
+	pub display_name: Option<String>,
+
+	/// Creation time (unix timestamp in seconds).
+	pub created: Option<i64>,
+
+	/// Maximum input context (in tokens).
+	pub context_window: Option<u32>,
+
+	/// Maximum output tokens.
+	pub max_output_tokens: Option<u32>,
+
+	pub input_modalities: Vec<Modality>,
+	pub output_modalities: Vec<Modality>,
+
+	pub supports_tools: Option<bool>,
+	pub supports_reasoning: Option<bool>,
+	pub supports_embeddings: Option<bool>,
+}
+
+/// Constructor
+impl ModelInfo {
+	/// Create a `ModelInfo` with only the id (all other properties unknown).
+	pub fn new(adapter_kind: AdapterKind, id: impl Into<String>) -> Self {
+		Self {
+			adapter_kind,
+			id: id.into(),
+			display_name: None,
+			created: None,
+			context_window: None,
+			max_output_tokens: None,
+			input_modalities: Vec::new(),
+			output_modalities: Vec::new(),
+			supports_tools: None,
+			supports_reasoning: None,
+			supports_embeddings: None,
+		}
+	}
+}
+
+/// Getters
+impl ModelInfo {
+	/// Returns the `ModelIden` to use for the `Client::exec_*` calls.
+	pub fn model_iden(&self) -> ModelIden {
+		ModelIden::new(self.adapter_kind, self.id.clone())
+	}
+}
+
+// region:    --- Modality
+
+/// Input or output modality of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Modality {
+	Text,
+	Image,
+	Audio,
+	Video,
+	/// Documents (e.g., PDF)
+	File,
+}
+
+impl Modality {
+	/// Parses the provider modality keyword (e.g., `text`, `image`, `pdf`), case insensitive.
+	pub fn from_keyword(keyword: &str) -> Option<Self> {
+		match keyword.to_lowercase().as_str() {
+			"text" => Some(Modality::Text),
+			"image" | "images" | "vision" => Some(Modality::Image),
+			"audio" => Some(Modality::Audio),
+			"video" => Some(Modality::Video),
+			"file" | "pdf" | "document" => Some(Modality::File),
+			_ => None,
+		}
+	}
+}
+
+// endregion: --- Modality
@@ -0,0 +1,510 @@
+//! Cost estimation from the normalized `Usage` and a user provided pricing table.
+//!
+//! Prices are in USD per million tokens (the unit of the provider pricing pages).
+//! genai does not ship prices, as they change too often; load them with `Pricing::from_json(...)`
+//! or build them with `Pricing::with(...)`.
+
+use crate::chat::Usage;
+use crate::common::ModelPattern;
+use crate::{ModelIden, Result};
+use serde::{Deserialize, Serialize};
+
+const TOKENS_PER_PRICE_UNIT: f64 = 1_000_000.;
+
+// region:    --- PriceRates
+
+/// The per-million-token rates of a model (USD).
+///
+/// Only `input` and `output` are required. The others fall back on them when not set
+/// (e.g., `cached_input` to `input`, `reasoning` to `output`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceRates {
+	pub input: f64,
+	pub output: f64,
+
+	/// Cache read (hit) input tokens.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cached_input: Option<f64>,
+	/// Cache write input tokens, 5 minutes TTL (also used when the TTL is not reported).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cache_write_5m: Option<f64>,
+	/// Cache write input tokens, 1 hour TTL (falls back on `cache_write_5m`, then `input`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cache_write_1h: Option<f64>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub reasoning: Option<f64>,
+
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub audio_input: Option<f64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub audio_output: Option<f64>,
+}
+
+/// Constructor
+impl PriceRates {
+	pub fn new(input: f64, output: f64) -> Self {
+		Self {
+			input,
+			output,
+			..Default::default()
+		}
+	}
+}
+
+/// Chainable Setters
+impl PriceRates {
+	pub fn with_cached_input(mut self, value: f64) -> Self {
+		self.cached_input = Some(value);
+		self
+	}
+
+	pub fn with_cache_write_5m(mut self, value: f64) -> Self {
+		self.cache_write_5m = Some(value);
+		self
+	}
+
+	pub fn with_cache_write_1h(mut self, value: f64) -> Self {
+		self.cache_write_1h = Some(value);
+		self
+	}
+
+	pub fn with_reasoning(mut self, value: f64) -> Self {
+		self.reasoning = Some(value);
+		self
+	}
+
+	pub fn with_audio_input(mut self, value: f64) -> Self {
+		self.audio_input = Some(value);
+		self
+	}
+
+	pub fn with_audio_output(mut self, value: f64) -> Self {
+		self.audio_output = Some(value);
+		self
+	}
+}
+
+// endregion: --- PriceRates
+
+// region:    --- ModelPrice
+
+/// The price of a model: the base rates, and the optional long-context tiers.
+///
+/// A tier applies to the whole request when its prompt tokens are above `above_input_tokens`
+/// (e.g., Gemini 2.5 Pro or Claude Sonnet above 200K input tokens). The highest matching tier wins,
+/// and the cache rates it does not set are the base ones.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+	#[serde(flatten)]
+	pub rates: PriceRates,
+
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub tiers: Vec<PriceTier>,
+}
+
+/// A long-context tier of a `ModelPrice`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceTier {
+	pub above_input_tokens: u32,
+
+	#[serde(flatten)]
+	pub rates: PriceRates,
+}
+
+/// Constructor
+impl ModelPrice {
+	pub fn new(rates: PriceRates) -> Self {
+		Self {
+			rates,
+			tiers: Vec::new(),
+		}
+	}
+}
+
+impl From<PriceRates> for ModelPrice {
+	fn from(rates: PriceRates) -> Self {
+		ModelPrice::new(rates)
+	}
+}
+
+/// Chainable Setters
+impl ModelPrice {
+	/// Adds a long-context tier, applied when the prompt tokens are above `above_input_tokens`.
+	pub fn with_tier(mut self, above_input_tokens: u32, rates: PriceRates) -> Self {
+		self.tiers.push(PriceTier {
+			above_input_tokens,
+			rates,
+		});
+		self
+	}
+}
+
+/// Cost computation
+impl ModelPrice {
+	/// Returns the rates for a given number of prompt tokens (the highest matching tier, or the base rates).
+	///
+	/// The cache rates not set on the tier are the base ones (the tiers often only set `input` and `output`).
+	pub fn rates_for(&self, prompt_tokens: i32) -> PriceRates {
+		let Some(tier) = self
+			.tiers
+			.iter()
+			.filter(|tier| i64::from(prompt_tokens) > i64::from(tier.above_input_tokens))
+			.max_by_key(|tier| tier.above_input_tokens)
+		else {
+			return self.rates.clone();
+		};
+
+		let mut rates = tier.rates.clone();
+		rates.cached_input = rates.cached_input.or(self.rates.cached_input);
+		rates.cache_write_5m = rates.cache_write_5m.or(self.rates.cache_write_5m);
+		rates.cache_write_1h = rates.cache_write_1h.or(self.rates.cache_write_1h);
+		rates
+	}
+
+	/// Computes the itemized cost of a usage.
+	///
+	/// Note: The `Usage` is normalized by the adapters (prompt tokens include the cached and cache write tokens,
+	///       completion tokens include the reasoning tokens), so each token is only counted once.
+	pub fn cost(&self, usage: &Usage) -> Cost {
+		let prompt_tokens = usage.prompt_tokens.unwrap_or(0).max(0);
+		let completion_tokens = usage.completion_tokens.unwrap_or(0).max(0);
+		let rates = self.rates_for(prompt_tokens);
+
+		// -- Input
+		let prompt_details = usage.prompt_tokens_details.as_ref();
+		let cached_tokens = prompt_details.and_then(|d| d.cached_tokens).unwrap_or(0).max(0);
+		let cache_write_tokens = prompt_details.and_then(|d| d.cache_creation_tokens).unwrap_or(0).max(0);
+		let audio_input_tokens = prompt_details.and_then(|d| d.audio_tokens).unwrap_or(0).max(0);
+
+		// When the TTL breakdown is not reported, all the cache writes are priced as 5m.
+		let cache_write_1h_tokens = prompt_details
+			.and_then(|d| d.cache_creation_details.as_ref())
+			.and_then(|d| d.ephemeral_1h_tokens)
+			.unwrap_or(0)
+			.clamp(0, cache_write_tokens);
+		let cache_write_5m_tokens = cache_write_tokens - cache_write_1h_tokens;
+
+		let text_input_tokens = (prompt_tokens - cached_tokens - cache_write_tokens - audio_input_tokens).max(0);
+
+		let cache_write_5m_rate = rates.cache_write_5m.unwrap_or(rates.input);
+		let cache_write_1h_rate = rates.cache_write_1h.unwrap_or(cache_write_5m_rate);
+
+		// -- Output
+		let completion_details = usage.completion_tokens_details.as_ref();
+		let reasoning_tokens = completion_details.and_then(|d| d.reasoning_tokens).unwrap_or(0).max(0);
+		let audio_output_tokens = completion_details.and_then(|d| d.audio_tokens).unwrap_or(0).max(0);
+		let text_output_tokens = (completion_tokens - reasoning_tokens - audio_output_tokens).max(0);
+
+		Cost::new(
+			price(text_input_tokens, rates.input),
+			price(cached_tokens, rates.cached_input.unwrap_or(rates.input)),
+			price(cache_write_5m_tokens, cache_write_5m_rate) + price(cache_write_1h_tokens, cache_write_1h_rate),
+			price(audio_input_tokens, rates.audio_input.unwrap_or(rates.input)),
+			price(text_output_tokens, rates.output),
+			price(reasoning_tokens, rates.reasoning.unwrap_or(rates.output)),
+			price(audio_output_tokens, rates.audio_output.unwrap_or(rates.output)),
+		)
+	}
+}
+
+fn price(tokens: i32, rate_per_million: f64) -> f64 {
+	f64::from(tokens) * rate_per_million / TOKENS_PER_PRICE_UNIT
+}
+
+// endregion: --- ModelPrice
+
+// region:    --- Pricing
+
+/// The pricing table, with the `ModelPrice` by `ModelPattern` (first match wins).
+///
+/// The JSON form is an array of entries, each with a `model` pattern (see `ModelPattern`)
+/// and the `ModelPrice` properties, in precedence order:
+///
+/// ```json
+/// [
+///   { "model": "openai::gpt-4o-mini*", "input": 0.15, "cached_input": 0.075, "output": 0.6 },
+///   { "model": "claude-sonnet-4*", "input": 3.0, "cached_input": 0.3, "cache_write_5m": 3.75,
+///     "cache_write_1h": 6.0, "output": 15.0,
+///     "tiers": [{ "above_input_tokens": 200000, "input": 6.0, "output": 22.5 }] }
+/// ]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<PricingEntry>", into = "Vec<PricingEntry>")]
+pub struct Pricing {
+	entries: Vec<(ModelPattern, ModelPrice)>,
+}
+
+/// Constructors
+impl Pricing {
+	/// Parses the JSON form (see `Pricing`).
+	pub fn from_json(json: &str) -> Result<Self> {
+		Ok(serde_json::from_str(json)?)
+	}
+
+	pub fn from_json_value(value: serde_json::Value) -> Result<Self> {
+		Ok(serde_json::from_value(value)?)
+	}
+}
+
+/// Chainable Setters
+impl Pricing {
+	/// Adds an entry, with precedence over the existing ones.
+	pub fn with(mut self, pattern: impl Into<ModelPattern>, price: impl Into<ModelPrice>) -> Self {
+		self.register(pattern, price);
+		self
+	}
+
+	/// Adds an entry, with precedence over the existing ones.
+	pub fn register(&mut self, pattern: impl Into<ModelPattern>, price: impl Into<ModelPrice>) {
+		self.entries.insert(0, (pattern.into(), price.into()));
+	}
+}
+
+/// Getters
+impl Pricing {
+	/// Returns the price of the first matching pattern.
+	pub fn get(&self, model: &ModelIden) -> Option<&ModelPrice> {
+		self.entries
+			.iter()
+			.find(|(pattern, _)| pattern.matches(model))
+			.map(|(_, price)| price)
+	}
+
+	/// Returns the cost of a usage for a model, or `None` if the model has no price.
+	pub fn cost(&self, model: &ModelIden, usage: &Usage) -> Option<Cost> {
+		self.get(model).map(|price| price.cost(usage))
+	}
+
+	/// Like `cost(...)`, but falls back on the provider model iden (e.g., `gpt-4o-2024-08-06`)
+	/// when the resolved model has no price.
+	pub(crate) fn cost_with_fallback(
+		&self,
+		model: &ModelIden,
+		provider_model: &ModelIden,
+		usage: &Usage,
+	) -> Option<Cost> {
+		self.cost(model, usage).or_else(|| self.cost(provider_model, usage))
+	}
+}
+
+/// The JSON form of a `Pricing` entry.
+#[derive(Serialize, Deserialize)]
+struct PricingEntry {
+	model: String,
+	#[serde(flatten)]
+	price: ModelPrice,
+}
+
+impl From<Vec<PricingEntry>> for Pricing {
+	fn from(entries: Vec<PricingEntry>) -> Self {
+		let entries = entries
+			.into_iter()
+			.map(|entry| (ModelPattern::from(entry.model), entry.price))
+			.collect();
+		Self { entries }
+	}
+}
+
+impl From<Pricing> for Vec<PricingEntry> {
+	fn from(pricing: Pricing) -> Self {
+		pricing
+			.entries
+			.into_iter()
+			.map(|(pattern, price)| PricingEntry {
+				model: pattern.to_string(),
+				price,
+			})
+			.collect()
+	}
+}
+
+// endregion: --- Pricing
+
+// region:    --- Cost
+
+/// The itemized cost of a response (USD).
+///
+/// The items do not overlap (e.g., `input` excludes the cached, cache write, and audio input tokens),
+/// and `total` is their sum.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cost {
+	pub input: f64,
+	pub cached_input: f64,
+	pub cache_write: f64,
+	pub audio_input: f64,
+
+	pub output: f64,
+	pub reasoning: f64,
+	pub audio_output: f64,
+
+	pub total: f64,
+}
+
+impl Cost {
+	#[allow(clippy::too_many_arguments)]
+	fn new(
+		input: f64,
+		cached_input: f64,
+		cache_write: f64,
+		audio_input: f64,
+		output: f64,
+		reasoning: f64,
+		audio_output: f64,
+	) -> Self {
+		Self {
+			input,
+			cached_input,
+			cache_write,
+			audio_input,
+			output,
+			reasoning,
+			audio_output,
+			total: input + cached_input + cache_write + audio_input + output + reasoning + audio_output,
+		}
+	}
+}
+
+/// Getters
+impl Cost {
+	/// Total of the input items (input, cached input, cache write, audio input).
+	pub fn total_input(&self) -> f64 {
+		self.input + self.cached_input + self.cache_write + self.audio_input
+	}
+
+	/// Total of the output items (output, reasoning, audio output).
+	pub fn total_output(&self) -> f64 {
+		self.output + self.reasoning + self.audio_output
+	}
+}
+
+impl std::ops::Add for Cost {
+	type Output = Cost;
+
+	fn add(self, other: Cost) -> Cost {
+		Cost::new(
+			self.input + other.input,
+			self.cached_input + other.cached_input,
+			self.cache_write + other.cache_write,
+			self.audio_input + other.audio_input,
+			self.output + other.output,
+			self.reasoning + other.reasoning,
+			self.audio_output + other.audio_output,
+		)
+	}
+}
+
+impl std::ops::AddAssign for Cost {
+	fn add_assign(&mut self, other: Cost) {
+		*self = *self + other;
+	}
+}
+
+// endregion: --- Cost
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::adapter::AdapterKind;
+	use crate::chat::{CacheCreationDetails, CompletionTokensDetails, PromptTokensDetails};
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	fn assert_approx(actual: f64, expected: f64) {
+		assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+	}
+
+	#[test]
+	fn test_pricing_cost_itemized() -> Result<()> {
+		// -- Setup & Fixtures
+		let pricing = Pricing::from_json(
+			r#"[
+				{ "model": "anthropic::claude-sonnet-4*", "input": 3.0, "cached_input": 0.3,
+				  "cache_write_5m": 3.75, "cache_write_1h": 6.0, "output": 15.0,
+				  "tiers": [{ "above_input_tokens": 200000, "input": 6.0, "output": 22.5 }] }
+			]"#,
+		)?;
+		let model = ModelIden::new(AdapterKind::Anthropic, "claude-sonnet-4-5");
+		let usage = Usage {
+			prompt_tokens: Some(1_000_000),
+			prompt_tokens_details: Some(PromptTokensDetails {
+				cache_creation_tokens: Some(200_000),
+				cache_creation_details: Some(CacheCreationDetails {
+					ephemeral_5m_tokens: Some(100_000),
+					ephemeral_1h_tokens: Some(100_000),
+				}),
+				cached_tokens: Some(100_000),
+				audio_tokens: None,
+			}),
+			completion_tokens: Some(1_000_000),
+			completion_tokens_details: Some(CompletionTokensDetails {
+				accepted_prediction_tokens: None,
+				rejected_prediction_tokens: None,
+				reasoning_tokens: Some(500_000),
+				audio_tokens: None,
+			}),
+			total_tokens: Some(2_000_000),
+		};
+
+		// -- Exec
+		let cost = pricing.cost(&model, &usage).ok_or("Should have a price")?;
+
+		// -- Check (1M prompt tokens, so the tier applies, and the cache rates not set on the tier are the base ones)
+		assert_approx(cost.input, 0.7 * 6.0);
+		assert_approx(cost.cached_input, 0.1 * 0.3);
+		assert_approx(cost.cache_write, 0.1 * 3.75 + 0.1 * 6.0);
+		assert_approx(cost.output, 0.5 * 22.5);
+		assert_approx(cost.reasoning, 0.5 * 22.5);
+		assert_approx(cost.total, 4.2 + 0.03 + 0.975 + 22.5);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_pricing_cost_fallbacks_and_precedence() -> Result<()> {
+		// -- Setup & Fixtures
+		let pricing = Pricing::default()
+			.with("gpt-4o*", PriceRates::new(2.5, 10.0))
+			.with("gpt-4o-mini*", PriceRates::new(0.15, 0.6).with_cached_input(0.075));
+		let usage = Usage {
+			prompt_tokens: Some(2_000),
+			prompt_tokens_details: Some(PromptTokensDetails {
+				cache_creation_tokens: None,
+				cache_creation_details: None,
+				cached_tokens: Some(1_000),
+				audio_tokens: None,
+			}),
+			completion_tokens: Some(1_000),
+			..Default::default()
+		};
+
+		// -- Exec
+		let mini_cost = pricing
+			.cost(&ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini"), &usage)
+			.ok_or("Should have a price")?;
+		let cost = pricing
+			.cost(&ModelIden::new(AdapterKind::OpenAI, "gpt-4o"), &usage)
+			.ok_or("Should have a price")?;
+
+		// -- Check
+		assert_approx(mini_cost.input, 0.001 * 0.15);
+		assert_approx(mini_cost.cached_input, 0.001 * 0.075);
+		assert_approx(mini_cost.output, 0.001 * 0.6);
+		assert_approx(cost.cached_input, 0.001 * 2.5);
+		assert!(pricing.cost(&ModelIden::new(AdapterKind::OpenAI, "o3"), &usage).is_none());
+
+		// -- Check JSON round trip (precedence order preserved)
+		let json = serde_json::to_string(&pricing)?;
+		let pricing_2 = Pricing::from_json(&json)?;
+		let price = pricing_2
+			.get(&ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini"))
+			.ok_or("Should have a price")?;
+		assert_approx(price.rates.input, 0.15);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -2,7 +2,8 @@
 //! - It can be provided at the `client::embed(..)` level as an argument,
 //! - or set in the client config `client_config.with_embed_options(..)` to be used as the default for all requests
 
-use crate::Headers;
+use crate::adapter::CompatProfile;
+use crate::{Headers, UsageTracker};
 use serde::{Deserialize, Serialize};
 
 // region:    --- EmbedOptions
@@ -41,6 +42,13 @@ pub struct EmbedOptions {
 	/// Common values: "NONE", "START", "END"
 	/// Default: "END"
 	pub truncate: Option<String>,
+
+	/// The label (e.g., tenant or feature name) the usage is recorded under in the `UsageTracker`.
+	pub usage_label: Option<String>,
+
+	/// The `UsageTracker` for this request (takes precedence over the client one).
+	#[serde(skip)]
+	pub usage_tracker: Option<UsageTracker>,
 }
 
 /// Constructors
@@ -100,6 +108,18 @@ impl EmbedOptions {
 		self.truncate = Some(truncate.into());
 		self
 	}
+
+	/// Set the label the usage is recorded under in the `UsageTracker`.
+	pub fn with_usage_label(mut self, label: impl Into<String>) -> Self {
+		self.usage_label = Some(label.into());
+		self
+	}
+
+	/// Set the `UsageTracker` for the request(s) using these options.
+	pub fn with_usage_tracker(mut self, tracker: UsageTracker) -> Self {
+		self.usage_tracker = Some(tracker);
+		self
+	}
 }
 
 /// Getters
@@ -143,6 +163,16 @@ impl EmbedOptions {
 	pub fn truncate(&self) -> Option<&str> {
 		self.truncate.as_deref()
 	}
+
+	/// Get the usage label.
+	pub fn usage_label(&self) -> Option<&str> {
+		self.usage_label.as_deref()
+	}
+
+	/// Get the usage tracker.
+	pub fn usage_tracker(&self) -> Option<&UsageTracker> {
+		self.usage_tracker.as_ref()
+	}
 }
 
 // endregion: --- EmbedOptions
@@ -154,6 +184,8 @@ impl EmbedOptions {
 pub struct EmbedOptionsSet<'client, 'request> {
 	client_options: Option<&'client EmbedOptions>,
 	request_options: Option<&'request EmbedOptions>,
+	/// The client `CompatProfile` of the model namespace (for `AdapterKind::OpenAICompat`).
+	compat_profile: Option<&'client CompatProfile>,
 }
 
 impl<'client, 'request> EmbedOptionsSet<'client, 'request> {
@@ -174,6 +206,17 @@ impl<'client, 'request> EmbedOptionsSet<'client, 'request> {
 		self
 	}
 
+	/// Set the client `CompatProfile` of the model (for `AdapterKind::OpenAICompat`).
+	pub(crate) fn with_compat_profile(mut self, compat_profile: Option<&'client CompatProfile>) -> Self {
+		self.compat_profile = compat_profile;
+		self
+	}
+
+	/// Get the client `CompatProfile` of the model, if any.
+	pub(crate) fn compat_profile(&self) -> Option<&CompatProfile> {
+		self.compat_profile
+	}
+
 	/// Get the effective headers (request overrides client).
 	pub fn headers(&self) -> Option<&Headers> {
 		self.request_options
@@ -231,6 +274,20 @@ impl<'client, 'request> EmbedOptionsSet<'client, 'request> {
 			.and_then(|o| o.truncate())
 			.or_else(|| self.client_options.and_then(|o| o.truncate()))
 	}
+
+	/// Get the effective usage label.
+	pub fn usage_label(&self) -> Option<&str> {
+		self.request_options
+			.and_then(|o| o.usage_label())
+			.or_else(|| self.client_options.and_then(|o| o.usage_label()))
+	}
+
+	/// Get the effective usage tracker.
+	pub fn usage_tracker(&self) -> Option<&UsageTracker> {
+		self.request_options
+			.and_then(|o| o.usage_tracker())
+			.or_else(|| self.client_options.and_then(|o| o.usage_tracker()))
+	}
 }
 
 // endregion: --- EmbedOptionsSet
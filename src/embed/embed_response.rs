@@ -2,6 +2,7 @@
 
 use crate::ModelIden;
 use crate::chat::Usage;
+use crate::common::{Cost, Pricing};
 use serde::{Deserialize, Serialize};
 
 // region:    --- EmbedResponse
@@ -89,6 +90,16 @@ impl EmbedResponse {
 	}
 }
 
+/// Cost
+impl EmbedResponse {
+	/// Returns the cost of this response from its usage, or `None` if the model has no price.
+	///
+	/// The model is looked up by `model_iden`, then by `provider_model_iden`.
+	pub fn cost(&self, pricing: &Pricing) -> Option<Cost> {
+		pricing.cost_with_fallback(&self.model_iden, &self.provider_model_iden, &self.usage)
+	}
+}
+
 // endregion: --- EmbedResponse
 
 // region:    --- Embedding
@@ -1,5 +1,5 @@
 use crate::adapter::AdapterKind;
-use crate::chat::ChatRole;
+use crate::chat::{ChatRole, TimeoutPhase};
 use crate::{ModelIden, resolver, webc};
 use derive_more::{Display, From};
 use reqwest::StatusCode;
@@ -43,6 +43,44 @@ pub enum Error {
 	#[display("Failed to parse service tier. Actual: '{actual}'")]
 	ServiceTierParsing { actual: String },
 
+	// -- Capability Check (pre-flight, see `ChatOptions::with_capability_check`)
+	#[display("Model '{model_iden}' does not support '{capability}' (per the model capabilities registry)")]
+	ModelCapabilityNotSupported {
+		model_iden: ModelIden,
+		capability: &'static str,
+	},
+
+	#[display(
+		"Chat request for model '{model_iden}' is estimated at {estimated_tokens} input tokens, exceeding its context window of {context_window}"
+	)]
+	ContextWindowExceeded {
+		model_iden: ModelIden,
+		estimated_tokens: u32,
+		context_window: u32,
+	},
+
+	#[display("max_tokens {max_tokens} exceeds the max output tokens of {max_output_tokens} for model '{model_iden}'")]
+	MaxOutputTokensExceeded {
+		model_iden: ModelIden,
+		max_tokens: u32,
+		max_output_tokens: u32,
+	},
+
+	// -- Usage Tracker
+	#[display("Usage budget exceeded (label: {label:?}): {reason}")]
+	UsageBudgetExceeded { label: Option<String>, reason: String },
+
+	// -- Chat Session
+	#[display("Chat session version {version} is not supported (max version: {max_version})")]
+	ChatSessionVersionNotSupported { version: u64, max_version: u32 },
+
+	// -- Mock
+	#[display("Model '{model_iden}' is a mock model, but no MockScript is set on the client")]
+	MockScriptNotSet { model_iden: ModelIden },
+
+	#[display("No scripted reply left in the MockScript for model '{model_iden}'")]
+	MockNoReply { model_iden: ModelIden },
+
 	// -- Chat Output
 	#[display("No chat response from model '{model_iden}'")]
 	NoChatResponse { model_iden: ModelIden },
@@ -101,6 +139,10 @@ Cause:\n{cause}
 		body: serde_json::Value,
 	},
 
+	// -- Timeout (see `ChatOptions::with_timeout`)
+	#[display("Request for model '{model_iden}' timed out ({phase})")]
+	Timeout { model_iden: ModelIden, phase: TimeoutPhase },
+
 	// -- Chat Stream
 	#[display("Failed to parse stream data for model '{model_iden}'.\nCause: {serde_error}")]
 	StreamParse {
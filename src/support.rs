@@ -35,3 +35,73 @@ pub fn combine_text_with_empty_line(combined: &mut String, text: &str) {
 }
 
 // endregion: --- Text Support
+
+// region:    --- Time Support
+
+/// Parses a RFC 3339 date time (e.g., `2025-02-19T00:00:00Z`, `2025-05-12T10:21:33.12-07:00`) to a unix timestamp (seconds).
+/// Returns `None` if the format is not supported (the fractional seconds are ignored).
+pub fn rfc3339_to_unix_secs(value: &str) -> Option<i64> {
+	let value = value.trim();
+	let (date, time) = value.split_once(['T', 't', ' '])?;
+
+	// -- Date
+	let mut date_parts = date.splitn(3, '-');
+	let year: i64 = date_parts.next()?.parse().ok()?;
+	let month: i64 = date_parts.next()?.parse().ok()?;
+	let day: i64 = date_parts.next()?.parse().ok()?;
+
+	// -- Offset
+	let (time, offset_secs) = if let Some(time) = time.strip_suffix(['Z', 'z']) {
+		(time, 0)
+	} else {
+		let idx = time.rfind(['+', '-'])?;
+		let (time, offset) = time.split_at(idx);
+		let sign = if offset.starts_with('-') { -1 } else { 1 };
+		let (offset_h, offset_m) = offset[1..].split_once(':')?;
+		let offset_secs = offset_h.parse::<i64>().ok()? * 3600 + offset_m.parse::<i64>().ok()? * 60;
+		(time, sign * offset_secs)
+	};
+
+	// -- Time
+	let time = time.split('.').next()?;
+	let mut time_parts = time.splitn(3, ':');
+	let hour: i64 = time_parts.next()?.parse().ok()?;
+	let minute: i64 = time_parts.next()?.parse().ok()?;
+	let second: i64 = time_parts.next()?.parse().ok()?;
+
+	if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+		return None;
+	}
+
+	// Days from civil (proleptic Gregorian calendar)
+	let y = if month <= 2 { year - 1 } else { year };
+	let era = if y >= 0 { y } else { y - 399 } / 400;
+	let yoe = y - era * 400;
+	let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+	let days = era * 146097 + doe - 719468;
+
+	Some(days * 86400 + hour * 3600 + minute * 60 + second - offset_secs)
+}
+
+// endregion: --- Time Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_support_rfc3339_to_unix_secs() {
+		assert_eq!(rfc3339_to_unix_secs("1970-01-01T00:00:00Z"), Some(0));
+		assert_eq!(rfc3339_to_unix_secs("2025-02-19T00:00:00Z"), Some(1739923200));
+		assert_eq!(
+			rfc3339_to_unix_secs("2025-02-18T17:00:00.123456-07:00"),
+			Some(1739923200)
+		);
+		assert_eq!(rfc3339_to_unix_secs("not a date"), None);
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,485 @@
+//! Record/replay of the web calls in cassette files (feature `cassette`), for deterministic tests without network.
+
+use crate::Headers;
+use crate::error::BoxError;
+use crate::webc::{
+	BytesStream, Error, HttpRequest, HttpTransport, Result, WebResponse, WebStreamSource, http_status_error,
+};
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+
+/// The current version of the cassette file format.
+const CASSETTE_VERSION: u32 = 1;
+
+// region:    --- Cassette
+
+/// Records the web calls (request and response pairs, including the streams) to a cassette file,
+/// or replays them from it without network.
+///
+/// Set on the client with `ClientBuilder::with_cassette(...)`.
+///
+/// Notes:
+/// - The recorded request headers are redacted (see `Headers::redacted()`).
+/// - The replayed interactions are matched by method, URL, and JSON payload (key order does not matter),
+///   in the recorded order when the same request was recorded more than once.
+/// - The cassette is cheap to clone (all clones share the same interactions).
+#[derive(Debug, Clone)]
+pub struct Cassette {
+	mode: CassetteMode,
+	path: Arc<PathBuf>,
+	inner: Arc<Mutex<CassetteInner>>,
+}
+
+/// The mode of a `Cassette`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+	/// The calls are sent, and recorded (the file is rewritten after each call).
+	Record,
+	/// The calls are served from the file, and never sent.
+	Replay,
+}
+
+#[derive(Debug, Default)]
+struct CassetteInner {
+	interactions: Vec<CassetteInteraction>,
+	/// The replayed flags, by interaction index.
+	replayed: Vec<bool>,
+}
+
+/// Constructors
+impl Cassette {
+	/// A recording cassette; the file (and its parent directories) is created at the first recorded call.
+	pub fn record(path: impl Into<PathBuf>) -> Self {
+		Self {
+			mode: CassetteMode::Record,
+			path: Arc::new(path.into()),
+			inner: Default::default(),
+		}
+	}
+
+	/// A replaying cassette, loaded from a file recorded with `Cassette::record(...)`.
+	pub fn replay(path: impl Into<PathBuf>) -> crate::Result<Self> {
+		let path = path.into();
+		let content = std::fs::read_to_string(&path)
+			.map_err(|e| crate::Error::Internal(format!("Failed to read cassette file '{}': {}", path.display(), e)))?;
+		let file: CassetteFile = serde_json::from_str(&content)?;
+		if file.version > CASSETTE_VERSION {
+			return Err(crate::Error::Internal(format!(
+				"Cassette file '{}' version {} is not supported (max version: {CASSETTE_VERSION})",
+				path.display(),
+				file.version
+			)));
+		}
+
+		let replayed = vec![false; file.interactions.len()];
+		Ok(Self {
+			mode: CassetteMode::Replay,
+			path: Arc::new(path),
+			inner: Arc::new(Mutex::new(CassetteInner {
+				interactions: file.interactions,
+				replayed,
+			})),
+		})
+	}
+}
+
+/// Getters
+impl Cassette {
+	pub fn mode(&self) -> CassetteMode {
+		self.mode
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// The number of interactions (recorded, or loaded for replay).
+	pub fn len(&self) -> usize {
+		self.lock().interactions.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+/// Web calls (for the `WebClient`)
+impl Cassette {
+	/// Replays the response of the request, or sends it with `send_fut` and records the response.
+	pub(crate) async fn exec<F>(&self, request: CassetteRequest, send_fut: F) -> Result<WebResponse>
+	where
+		F: Future<Output = Result<WebResponse>>,
+	{
+		match self.mode {
+			CassetteMode::Replay => self.find_response(&request)?.into_web_response(),
+			CassetteMode::Record => {
+				let res = send_fut.await;
+				match &res {
+					Ok(web_res) => {
+						self.record_interaction(request, CassetteResponse::json(web_res.status, web_res.body.clone()))
+					}
+					Err(Error::ResponseFailedStatus { status, body, .. }) => {
+						self.record_interaction(request, CassetteResponse::text(*status, body.clone()))
+					}
+					// Not a provider response (e.g., connection error), so nothing to replay.
+					Err(_) => (),
+				}
+				res
+			}
+		}
+	}
+
+	/// The stream source replaying the response of the request, or recording it once the stream is consumed.
+	pub(crate) fn stream_source(
+		&self,
+		request: CassetteRequest,
+		transport: Arc<dyn HttpTransport>,
+		http_request: HttpRequest,
+	) -> Result<WebStreamSource> {
+		match self.mode {
+			CassetteMode::Replay => Ok(self.find_response(&request)?.into_stream_source()),
+			CassetteMode::Record => Ok(WebStreamSource::Bytes(Box::pin(RecordingStream::new(
+				self.clone(),
+				request,
+				transport,
+				http_request,
+			)))),
+		}
+	}
+}
+
+// Private
+impl Cassette {
+	fn find_response(&self, request: &CassetteRequest) -> Result<CassetteResponse> {
+		let mut inner = self.lock();
+
+		let matching: Vec<usize> = (0..inner.interactions.len())
+			.filter(|&idx| inner.interactions[idx].request.matches(request))
+			.collect();
+
+		// The first not yet replayed, or the last one (e.g., a request repeated more than recorded).
+		let idx = matching
+			.iter()
+			.copied()
+			.find(|&idx| !inner.replayed[idx])
+			.or_else(|| matching.last().copied())
+			.ok_or_else(|| Error::CassetteNoMatch {
+				method: request.method.clone(),
+				url: request.url.clone(),
+			})?;
+
+		inner.replayed[idx] = true;
+		Ok(inner.interactions[idx].response.clone())
+	}
+
+	fn record_interaction(&self, request: CassetteRequest, response: CassetteResponse) {
+		let mut inner = self.lock();
+		inner.interactions.push(CassetteInteraction { request, response });
+		inner.replayed.push(false);
+
+		let file = CassetteFileRef {
+			version: CASSETTE_VERSION,
+			interactions: &inner.interactions,
+		};
+		if let Err(err) = write_json_file(&self.path, &file) {
+			tracing::warn!(
+				"GENAI - WARNING - Failed to write cassette file '{}': {err}",
+				self.path.display()
+			);
+		}
+	}
+
+	fn lock(&self) -> MutexGuard<'_, CassetteInner> {
+		self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+}
+
+fn write_json_file(path: &Path, file: &CassetteFileRef<'_>) -> core::result::Result<(), BoxError> {
+	if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+		std::fs::create_dir_all(dir)?;
+	}
+	std::fs::write(path, serde_json::to_string_pretty(file)?)?;
+	Ok(())
+}
+
+// endregion: --- Cassette
+
+// region:    --- Cassette File
+
+#[derive(Deserialize)]
+struct CassetteFile {
+	version: u32,
+	interactions: Vec<CassetteInteraction>,
+}
+
+#[derive(Serialize)]
+struct CassetteFileRef<'a> {
+	version: u32,
+	interactions: &'a [CassetteInteraction],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteInteraction {
+	request: CassetteRequest,
+	response: CassetteResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CassetteRequest {
+	method: String,
+	url: String,
+	headers: Headers,
+	#[serde(default)]
+	payload: Value,
+}
+
+impl CassetteRequest {
+	/// Note: The headers are redacted, as they are not matched, and are written to the cassette file.
+	pub(crate) fn new(method: Method, url: &str, headers: &Headers, payload: Option<&Value>) -> Self {
+		Self {
+			method: method.to_string(),
+			url: url.to_string(),
+			headers: headers.redacted(),
+			payload: payload.cloned().unwrap_or_default(),
+		}
+	}
+
+	/// The `serde_json::Value` equality ignores the object key order.
+	fn matches(&self, other: &CassetteRequest) -> bool {
+		self.method == other.method && self.url == other.url && self.payload == other.payload
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteResponse {
+	status: u16,
+	body: CassetteBody,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CassetteBody {
+	/// The JSON body of a successful response.
+	Json(Value),
+	/// The raw body of a stream (e.g., the SSE events).
+	Stream(String),
+	/// The body of a failed response.
+	Text(String),
+}
+
+impl CassetteResponse {
+	fn json(status: StatusCode, body: Value) -> Self {
+		Self {
+			status: status.as_u16(),
+			body: CassetteBody::Json(body),
+		}
+	}
+
+	fn stream(status: StatusCode, body: String) -> Self {
+		Self {
+			status: status.as_u16(),
+			body: CassetteBody::Stream(body),
+		}
+	}
+
+	fn text(status: StatusCode, body: String) -> Self {
+		Self {
+			status: status.as_u16(),
+			body: CassetteBody::Text(body),
+		}
+	}
+
+	fn status_code(&self) -> StatusCode {
+		StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+	}
+
+	fn into_body_text(self) -> String {
+		match self.body {
+			CassetteBody::Json(value) => value.to_string(),
+			CassetteBody::Stream(text) | CassetteBody::Text(text) => text,
+		}
+	}
+
+	fn into_web_response(self) -> Result<WebResponse> {
+		let status = self.status_code();
+		match self.body {
+			CassetteBody::Json(body) if status.is_success() => Ok(WebResponse { status, body }),
+			CassetteBody::Stream(body) if status.is_success() => Err(Error::ResponseFailedNotJson {
+				content_type: "text/event-stream".to_string(),
+				body,
+			}),
+			_ => Err(Error::ResponseFailedStatus {
+				status,
+				body: self.into_body_text(),
+				headers: Default::default(),
+			}),
+		}
+	}
+
+	fn into_stream_source(self) -> WebStreamSource {
+		let status = self.status_code();
+		if status.is_success() {
+			return WebStreamSource::from_text(self.into_body_text());
+		}
+
+		// Same error as a failed `WebStream` request.
+		let err = http_status_error(status, self.into_body_text());
+		WebStreamSource::Bytes(Box::pin(futures::stream::once(
+			async move { Err::<Bytes, BoxError>(err) },
+		)))
+	}
+}
+
+// endregion: --- Cassette File
+
+// region:    --- RecordingStream
+
+/// The bytes stream of a recorded request, recording the full body at the end.
+struct RecordingStream {
+	inner: BytesStream,
+	buffer: Vec<u8>,
+	/// Taken when recorded (or on error, as the failed responses are recorded when received).
+	recorder: Option<(Cassette, CassetteRequest)>,
+}
+
+impl RecordingStream {
+	fn new(
+		cassette: Cassette,
+		request: CassetteRequest,
+		transport: Arc<dyn HttpTransport>,
+		http_request: HttpRequest,
+	) -> Self {
+		let recorder = Some((cassette.clone(), request.clone()));
+
+		let response_fut = async move {
+			let response = transport.send_stream(http_request).await.map_err(|e| Box::new(e) as BoxError)?;
+			let status = response.status;
+			if !status.is_success() {
+				let body = response.text().await;
+				cassette.record_interaction(request, CassetteResponse::text(status, body.clone()));
+				return Err(http_status_error(status, body));
+			}
+			Ok(response.body)
+		};
+
+		Self {
+			inner: Box::pin(futures::stream::once(response_fut).try_flatten()),
+			buffer: Vec::new(),
+			recorder,
+		}
+	}
+}
+
+impl Stream for RecordingStream {
+	type Item = core::result::Result<Bytes, BoxError>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+
+		let poll = this.inner.as_mut().poll_next(cx);
+		match &poll {
+			Poll::Ready(Some(Ok(bytes))) => this.buffer.extend_from_slice(bytes),
+			Poll::Ready(Some(Err(_))) => this.recorder = None,
+			Poll::Ready(None) => {
+				if let Some((cassette, request)) = this.recorder.take() {
+					let body = String::from_utf8_lossy(&this.buffer).into_owned();
+					cassette.record_interaction(request, CassetteResponse::stream(StatusCode::OK, body));
+				}
+			}
+			Poll::Pending => (),
+		}
+		poll
+	}
+}
+
+// endregion: --- RecordingStream
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Client;
+	use crate::chat::{ChatMessage, ChatRequest, ChatStreamEvent};
+	use crate::resolver::AuthData;
+	use futures::StreamExt;
+	use serde_json::json;
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	#[tokio::test]
+	async fn test_cassette_replay_chat_and_stream() -> Result<()> {
+		// -- Setup & Fixtures
+		let model = "gpt-4o-mini";
+		let chat_req = ChatRequest::new(vec![ChatMessage::user("Say hello")]);
+		let client = Client::builder()
+			.with_auth_resolver_fn(|_| Ok(Some(AuthData::from_single("sk-test"))))
+			.build();
+		let chat_data = client.build_chat_request(model, chat_req.clone(), None).await?;
+		let mut stream_payload = chat_data.payload.clone();
+		stream_payload["stream"] = json!(true);
+
+		let path = std::env::temp_dir().join(format!("genai-cassette-{}.json", uuid::Uuid::new_v4()));
+		let file = json!({
+			"version": 1,
+			"interactions": [
+				{
+					"request": {"method": "POST", "url": chat_data.url, "headers": {}, "payload": chat_data.payload},
+					"response": {"status": 200, "body": {"json": {
+						"model": model,
+						"choices": [{"index": 0, "message": {"role": "assistant", "content": "Hello!"}, "finish_reason": "stop"}],
+						"usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}
+					}}}
+				},
+				{
+					"request": {"method": "POST", "url": chat_data.url, "headers": {}, "payload": stream_payload},
+					"response": {"status": 200, "body": {"stream":
+						"data: {\"model\":\"gpt-4o-mini\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi!\"}}]}\n\ndata: [DONE]\n\n"
+					}}
+				}
+			]
+		});
+		std::fs::write(&path, file.to_string())?;
+
+		let client = Client::builder()
+			.with_auth_resolver_fn(|_| Ok(Some(AuthData::from_single("sk-test"))))
+			.with_cassette(Cassette::replay(&path)?)
+			.build();
+
+		// -- Exec
+		let chat_res = client.exec_chat(model, chat_req.clone(), None).await?;
+		let mut stream = client.exec_chat_stream(model, chat_req.clone(), None).await?.stream;
+		let mut stream_text = String::new();
+		while let Some(event) = stream.next().await {
+			if let ChatStreamEvent::Chunk(chunk) = event? {
+				stream_text.push_str(&chunk.content);
+			}
+		}
+		let no_match = client
+			.exec_chat(model, chat_req.append_message(ChatMessage::user("Other")), None)
+			.await;
+		std::fs::remove_file(&path)?;
+
+		// -- Check
+		assert_eq!(chat_res.first_text(), Some("Hello!"));
+		assert_eq!(stream_text, "Hi!");
+		assert!(matches!(
+			no_match,
+			Err(crate::Error::WebModelCall {
+				webc_error: Error::CassetteNoMatch { .. },
+				..
+			})
+		));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,3 +1,4 @@
+use crate::error::BoxError;
 use derive_more::{Display, From};
 use reqwest::{StatusCode, header::HeaderMap};
 use value_ext::JsonValueExtError;
@@ -21,11 +22,20 @@ pub enum Error {
 		headers: Box<HeaderMap>,
 	},
 
+	// -- Cassette
+	#[cfg(feature = "cassette")]
+	#[display("No cassette interaction for '{method} {url}'")]
+	CassetteNoMatch { method: String, url: String },
+
 	// -- Utils
 	#[display("JSON value extension error: {_0}")]
 	#[from]
 	JsonValueExt(JsonValueExtError),
 
+	// -- Transport
+	#[display("Transport error: {_0}")]
+	Transport(BoxError),
+
 	// -- Externals
 	#[display("Reqwest error: {_0}")]
 	#[from]
@@ -1,7 +1,6 @@
 use crate::error::BoxError;
-use crate::webc::WebStream;
+use crate::webc::{WebStream, WebStreamSource};
 use futures::Stream;
-use reqwest::RequestBuilder;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -24,10 +23,10 @@ pub struct Message {
 }
 
 impl EventSourceStream {
-	pub fn new(reqwest_builder: RequestBuilder) -> Self {
+	pub fn new(source: impl Into<WebStreamSource>) -> Self {
 		// Standard EventSource uses \n\n as event separator
 		Self {
-			inner: WebStream::new_with_delimiter(reqwest_builder, "\n\n"),
+			inner: WebStream::new_with_delimiter(source, "\n\n"),
 			opened: false,
 		}
 	}
@@ -0,0 +1,255 @@
+//! The HTTP transport the `WebClient` and the web streams are built on (reqwest by default).
+
+use crate::Headers;
+use crate::error::BoxError;
+use crate::webc::{BytesStream, Error};
+use bytes::Bytes;
+use futures::TryStreamExt;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::pin::Pin;
+
+// region:    --- HttpTransport
+
+/// The future returned by the `HttpTransport` methods.
+pub type TransportFuture<'a, T> = Pin<Box<dyn Future<Output = core::result::Result<T, Error>> + Send + 'a>>;
+
+/// The transport sending the HTTP requests of the client (chat, stream, embed, and model listing calls).
+///
+/// Set on the client with `ClientBuilder::with_transport(...)` (e.g., a service mesh client, a custom
+/// connection pool, or an in-memory test transport). The default is `ReqwestTransport`.
+///
+/// Notes:
+/// - The transport returns the responses as received (the non-success status responses are not errors).
+/// - Custom transport errors can be returned as `webc::Error::Transport(...)`.
+pub trait HttpTransport: Debug + Send + Sync {
+	/// Sends the request, and returns the response with its full body.
+	fn send(&self, request: HttpRequest) -> TransportFuture<'_, HttpResponse>;
+
+	/// Sends the request, and returns the response with its body as a bytes stream.
+	fn send_stream(&self, request: HttpRequest) -> TransportFuture<'_, HttpStreamResponse>;
+}
+
+/// An HTTP request of the client.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+	pub method: Method,
+	pub url: String,
+	pub headers: Headers,
+	/// The JSON body, if any (sent with the `application/json` content type).
+	pub body: Option<Value>,
+}
+
+impl HttpRequest {
+	pub fn new(method: Method, url: impl Into<String>, headers: Headers, body: Option<Value>) -> Self {
+		Self {
+			method,
+			url: url.into(),
+			headers,
+			body,
+		}
+	}
+}
+
+/// An HTTP response with its full body.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+	pub status: StatusCode,
+	pub headers: HeaderMap,
+	pub body: Bytes,
+}
+
+/// An HTTP response with its body as a bytes stream.
+pub struct HttpStreamResponse {
+	pub status: StatusCode,
+	pub headers: HeaderMap,
+	pub body: BytesStream,
+}
+
+impl Debug for HttpStreamResponse {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("HttpStreamResponse")
+			.field("status", &self.status)
+			.field("headers", &self.headers)
+			.finish_non_exhaustive()
+	}
+}
+
+impl HttpStreamResponse {
+	/// Reads the full body as text (e.g., the body of a failed response).
+	pub async fn text(self) -> String {
+		let body: core::result::Result<Vec<Bytes>, BoxError> = self.body.try_collect().await;
+		match body {
+			Ok(chunks) => String::from_utf8_lossy(&chunks.concat()).into_owned(),
+			Err(err) => format!("Failed to read error body: {err}"),
+		}
+	}
+}
+
+// endregion: --- HttpTransport
+
+// region:    --- ReqwestTransport
+
+/// The default `HttpTransport`, sending the requests with a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+	reqwest_client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+	pub fn new(reqwest_client: reqwest::Client) -> Self {
+		Self { reqwest_client }
+	}
+
+	fn new_req_builder(&self, request: HttpRequest) -> reqwest::RequestBuilder {
+		let HttpRequest {
+			method,
+			url,
+			headers,
+			body,
+		} = request;
+
+		let mut reqwest_builder = self.reqwest_client.request(method, url);
+		for (k, v) in headers.iter() {
+			reqwest_builder = reqwest_builder.header(k, v);
+		}
+		if let Some(body) = &body {
+			reqwest_builder = reqwest_builder.json(body);
+		}
+
+		reqwest_builder
+	}
+}
+
+impl HttpTransport for ReqwestTransport {
+	fn send(&self, request: HttpRequest) -> TransportFuture<'_, HttpResponse> {
+		let reqwest_builder = self.new_req_builder(request);
+		Box::pin(async move {
+			let reqwest_res = reqwest_builder.send().await?;
+			let status = reqwest_res.status();
+			let headers = reqwest_res.headers().clone();
+			let body = reqwest_res.bytes().await?;
+			Ok(HttpResponse { status, headers, body })
+		})
+	}
+
+	fn send_stream(&self, request: HttpRequest) -> TransportFuture<'_, HttpStreamResponse> {
+		let reqwest_builder = self.new_req_builder(request);
+		Box::pin(async move {
+			let reqwest_res = reqwest_builder.send().await?;
+			let status = reqwest_res.status();
+			let headers = reqwest_res.headers().clone();
+			let body = reqwest_res.bytes_stream().map_err(|e| Box::new(e) as BoxError);
+			Ok(HttpStreamResponse {
+				status,
+				headers,
+				body: Box::pin(body),
+			})
+		})
+	}
+}
+
+// endregion: --- ReqwestTransport
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Client;
+	use crate::chat::{ChatMessage, ChatRequest, ChatStreamEvent};
+	use crate::resolver::AuthData;
+	use futures::StreamExt;
+	use reqwest::header::{CONTENT_TYPE, HeaderValue};
+	use serde_json::json;
+	use std::sync::{Arc, Mutex};
+
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+	/// An in-memory transport answering OpenAI chat completions.
+	#[derive(Debug, Default, Clone)]
+	struct TestTransport {
+		requests: Arc<Mutex<Vec<HttpRequest>>>,
+	}
+
+	impl HttpTransport for TestTransport {
+		fn send(&self, request: HttpRequest) -> TransportFuture<'_, HttpResponse> {
+			self.requests.lock().unwrap().push(request);
+			let body = json!({
+				"model": "gpt-4o-mini",
+				"choices": [{"index": 0, "message": {"role": "assistant", "content": "Hello!"}, "finish_reason": "stop"}]
+			});
+			let mut headers = HeaderMap::new();
+			headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+			Box::pin(async move {
+				Ok(HttpResponse {
+					status: StatusCode::OK,
+					headers,
+					body: Bytes::from(body.to_string()),
+				})
+			})
+		}
+
+		fn send_stream(&self, request: HttpRequest) -> TransportFuture<'_, HttpStreamResponse> {
+			self.requests.lock().unwrap().push(request);
+			let chunks = [
+				"data: {\"model\":\"gpt-4o-mini\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"}}]}\n\n",
+				"data: {\"model\":\"gpt-4o-mini\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"!\"}}]}\n\ndata: [DONE]\n\n",
+			];
+			let body = futures::stream::iter(chunks.map(|chunk| Ok(Bytes::from_static(chunk.as_bytes()))));
+			Box::pin(async move {
+				Ok(HttpStreamResponse {
+					status: StatusCode::OK,
+					headers: HeaderMap::new(),
+					body: Box::pin(body),
+				})
+			})
+		}
+	}
+
+	#[tokio::test]
+	async fn test_http_transport_custom_chat_and_stream() -> Result<()> {
+		// -- Setup & Fixtures
+		let transport = TestTransport::default();
+		let client = Client::builder()
+			.with_auth_resolver_fn(|_| Ok(Some(AuthData::from_single("sk-test"))))
+			.with_transport(transport.clone())
+			.build();
+		let chat_req = ChatRequest::new(vec![ChatMessage::user("Say hello")]);
+
+		// -- Exec & Check - chat
+		let chat_res = client.exec_chat("gpt-4o-mini", chat_req.clone(), None).await?;
+		assert_eq!(chat_res.first_text(), Some("Hello!"));
+
+		// -- Exec & Check - stream
+		let mut stream = client.exec_chat_stream("gpt-4o-mini", chat_req, None).await?.stream;
+		let mut content = String::new();
+		while let Some(event) = stream.next().await {
+			if let ChatStreamEvent::Chunk(chunk) = event? {
+				content.push_str(&chunk.content);
+			}
+		}
+		assert_eq!(content, "Hi!");
+
+		// -- Check - requests
+		let requests = transport.requests.lock().unwrap().clone();
+		assert_eq!(requests.len(), 2);
+		assert_eq!(requests[0].method, Method::POST);
+		assert!(requests[0].url.ends_with("chat/completions"));
+		let auth = requests[1]
+			.headers
+			.iter()
+			.find(|(k, _)| k.eq_ignore_ascii_case("authorization"));
+		assert_eq!(auth.map(|(_, v)| v.as_str()), Some("Bearer sk-test"));
+		assert_eq!(
+			requests[1].body.as_ref().and_then(|b| b["stream"].as_bool()),
+			Some(true)
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
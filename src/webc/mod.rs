@@ -1,9 +1,13 @@
-//! The GenAI web client construct that uses reqwest. Only `webc::Error` is exposed as the public interface.
+//! The GenAI web client construct, built on the `HttpTransport` trait (reqwest by default).
+//! Only `webc::Error` and the transport types are exposed as the public interface.
 
 // region:    --- Modules
 
+#[cfg(feature = "cassette")]
+mod cassette;
 mod error;
 mod event_source_stream;
+mod http_transport;
 mod web_client;
 mod web_stream;
 
@@ -13,6 +17,10 @@ pub(crate) use web_client::*;
 pub(crate) use web_stream::*;
 
 // Only public for external use
+#[cfg(feature = "cassette")]
+pub use cassette::{Cassette, CassetteMode};
 pub use error::Error;
+pub use http_transport::*;
+pub use web_stream::BytesStream;
 
 // endregion: --- Modules
@@ -1,21 +1,23 @@
 use crate::Headers;
-use crate::webc::{Error, Result};
-use reqwest::header::HeaderMap;
-use reqwest::{Method, RequestBuilder, StatusCode};
+#[cfg(feature = "cassette")]
+use crate::webc::{Cassette, cassette::CassetteRequest};
+use crate::webc::{Error, HttpRequest, HttpResponse, HttpTransport, ReqwestTransport, Result, WebStreamSource};
+use reqwest::{Method, StatusCode};
 use serde_json::Value;
+use std::sync::Arc;
 
-/// A simple reqwest client wrapper for this library.
+/// A simple web client wrapper for this library, sending the requests with its `HttpTransport`.
 #[derive(Debug)]
 pub struct WebClient {
-	reqwest_client: reqwest::Client,
+	transport: Arc<dyn HttpTransport>,
+	#[cfg(feature = "cassette")]
+	cassette: Option<Cassette>,
 }
 
 // Implements Default
 impl Default for WebClient {
 	fn default() -> Self {
-		WebClient {
-			reqwest_client: reqwest::Client::new(),
-		}
+		WebClient::from_transport(Arc::new(ReqwestTransport::default()))
 	}
 }
 
@@ -23,7 +25,31 @@ impl Default for WebClient {
 
 impl WebClient {
 	pub fn from_reqwest_client(reqwest_client: reqwest::Client) -> Self {
-		WebClient { reqwest_client }
+		WebClient::from_transport(Arc::new(ReqwestTransport::new(reqwest_client)))
+	}
+
+	pub fn from_transport(transport: Arc<dyn HttpTransport>) -> Self {
+		WebClient {
+			transport,
+			#[cfg(feature = "cassette")]
+			cassette: None,
+		}
+	}
+
+	/// A web client with another transport, and the same cassette (if any).
+	pub fn with_transport(&self, transport: Arc<dyn HttpTransport>) -> Self {
+		WebClient {
+			transport,
+			#[cfg(feature = "cassette")]
+			cassette: self.cassette.clone(),
+		}
+	}
+
+	/// Records or replays the web calls with the cassette.
+	#[cfg(feature = "cassette")]
+	pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+		self.cassette = Some(cassette);
+		self
 	}
 }
 
@@ -33,38 +59,48 @@ impl WebClient {
 
 impl WebClient {
 	pub async fn do_get(&self, url: &str, headers: &Headers) -> Result<WebResponse> {
-		let mut reqwest_builder = self.reqwest_client.request(Method::GET, url);
-
-		for (k, v) in headers.iter() {
-			reqwest_builder = reqwest_builder.header(k, v);
+		#[cfg(feature = "cassette")]
+		if let Some(cassette) = &self.cassette {
+			let request = CassetteRequest::new(Method::GET, url, headers, None);
+			return cassette.exec(request, self.send(Method::GET, url, headers, None)).await;
 		}
-		let reqwest_res = reqwest_builder.send().await?;
-
-		let response = WebResponse::from_reqwest_response(reqwest_res).await?;
 
-		Ok(response)
+		self.send(Method::GET, url, headers, None).await
 	}
 
 	pub async fn do_post(&self, url: &str, headers: &Headers, content: &Value) -> Result<WebResponse> {
-		let reqwest_builder = self.new_req_builder(url, headers, content)?;
+		#[cfg(feature = "cassette")]
+		if let Some(cassette) = &self.cassette {
+			let request = CassetteRequest::new(Method::POST, url, headers, Some(content));
+			return cassette
+				.exec(request, self.send(Method::POST, url, headers, Some(content)))
+				.await;
+		}
 
-		let reqwest_res = reqwest_builder.send().await?;
+		self.send(Method::POST, url, headers, Some(content)).await
+	}
 
-		let response = WebResponse::from_reqwest_response(reqwest_res).await?;
+	/// The source of a streamed POST (the request, or the cassette replay/recording).
+	pub fn new_stream_source(&self, url: &str, headers: &Headers, content: &Value) -> Result<WebStreamSource> {
+		let http_request = HttpRequest::new(Method::POST, url, headers.clone(), Some(content.clone()));
 
-		Ok(response)
+		#[cfg(feature = "cassette")]
+		if let Some(cassette) = &self.cassette {
+			let request = CassetteRequest::new(Method::POST, url, headers, Some(content));
+			return cassette.stream_source(request, self.transport.clone(), http_request);
+		}
+
+		Ok(WebStreamSource::from_request(self.transport.clone(), http_request))
 	}
 
-	pub fn new_req_builder(&self, url: &str, headers: &Headers, content: &Value) -> Result<RequestBuilder> {
-		let method = Method::POST;
+	async fn send(&self, method: Method, url: &str, headers: &Headers, content: Option<&Value>) -> Result<WebResponse> {
+		let http_request = HttpRequest::new(method, url, headers.clone(), content.cloned());
 
-		let mut reqwest_builder = self.reqwest_client.request(method, url);
-		for (k, v) in headers.iter() {
-			reqwest_builder = reqwest_builder.header(k, v);
-		}
-		reqwest_builder = reqwest_builder.json(content);
+		let http_res = self.transport.send(http_request).await?;
 
-		Ok(reqwest_builder)
+		let response = WebResponse::from_http_response(http_res)?;
+
+		Ok(response)
 	}
 }
 // endregion: --- Web Method Implementation
@@ -86,12 +122,11 @@ impl WebResponse {
 	/// Note 2: Currently, the WebResponse holds a Value (parsed from the entire body), and then the caller
 	///         can cherry-pick/deserialize further. In the future, we might consider returning `body: String`
 	///         to enable more optimized parsing, allowing for selective parsing constrained by the structure.
-	pub(crate) async fn from_reqwest_response(mut res: reqwest::Response) -> Result<WebResponse> {
-		let status = res.status();
+	pub(crate) fn from_http_response(http_res: HttpResponse) -> Result<WebResponse> {
+		let HttpResponse { status, headers, body } = http_res;
+		let body = String::from_utf8_lossy(&body).into_owned();
 
 		if !status.is_success() {
-			let headers = res.headers().clone();
-			let body = res.text().await?;
 			tracing::trace!("AI Response failed. Body:\n{body}");
 			return Err(Error::ResponseFailedStatus {
 				status,
@@ -100,13 +135,8 @@ impl WebResponse {
 			});
 		}
 
-		// Move the headers into a new HeaderMap
-		let headers = res.headers_mut().drain().filter_map(|(n, v)| n.map(|n| (n, v)));
-		let header_map = HeaderMap::from_iter(headers);
-
 		// Capture the body
-		let ct = header_map.get("content-type").and_then(|v| v.to_str().ok()).unwrap_or_default();
-		let body = res.text().await?;
+		let ct = headers.get("content-type").and_then(|v| v.to_str().ok()).unwrap_or_default();
 
 		let body = if ct.starts_with("application/json") {
 			tracing::trace!("AI Response body:\n{body}");
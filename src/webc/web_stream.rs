@@ -1,12 +1,14 @@
 use bytes::Bytes;
+use futures::Stream;
 use futures::stream::TryStreamExt;
-use futures::{Future, Stream};
-use reqwest::{RequestBuilder, Response};
+use reqwest::StatusCode;
 use std::collections::VecDeque;
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use crate::error::{BoxError, Error as GenaiError};
+use crate::webc::{HttpRequest, HttpTransport};
 
 /// WebStream is a simple web stream implementation that splits the stream messages by a given delimiter.
 /// - It is intended to be a pragmatic solution for services that do not adhere to the `text/event-stream` format and content type.
@@ -14,18 +16,68 @@ use crate::error::{BoxError, Error as GenaiError};
 /// - This stream item is just a `String` and has different stream modes that define the message delimiter strategy (without any event typing).
 /// - Each "Event" is just string-based and has only one event type, which is a string.
 /// - It is the responsibility of the user of this stream to wrap it into a semantically correct stream of events depending on the domain.
-#[allow(clippy::type_complexity)]
 pub struct WebStream {
 	stream_mode: StreamMode,
-	reqwest_builder: Option<RequestBuilder>,
-	response_future: Option<Pin<Box<dyn Future<Output = Result<Response, BoxError>> + Send>>>,
-	bytes_stream: Option<Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send>>>,
+	source: Option<WebStreamSource>,
+	bytes_stream: Option<BytesStream>,
 	// If a poll was a partial message, then we keep the previous part
 	partial_message: Option<String>,
 	// If a poll retrieved multiple messages, we keep them to be sent in the next poll
 	remaining_messages: Option<VecDeque<String>>,
 }
 
+/// A stream of body bytes (e.g., the body of an `HttpStreamResponse`).
+pub type BytesStream = Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send>>;
+
+/// The source of the `WebStream` bytes.
+pub enum WebStreamSource {
+	/// The response body of the request, sent with the transport on the first poll.
+	Request(Arc<dyn HttpTransport>, Box<HttpRequest>),
+	/// Bytes already available (e.g., a saved transcript).
+	Bytes(BytesStream),
+}
+
+impl WebStreamSource {
+	pub fn from_request(transport: Arc<dyn HttpTransport>, request: HttpRequest) -> Self {
+		Self::Request(transport, Box::new(request))
+	}
+
+	/// A source replaying a saved body (e.g., an SSE transcript), as a single chunk.
+	pub fn from_text(text: impl Into<String>) -> Self {
+		let bytes = Bytes::from(text.into());
+		Self::Bytes(Box::pin(futures::stream::once(async move { Ok(bytes) })))
+	}
+
+	/// The bytes stream of the source (the request is sent on the first poll).
+	///
+	/// Note: A non-success response fails the stream with `Error::HttpError` (with the response body).
+	pub(crate) fn into_bytes_stream(self) -> BytesStream {
+		match self {
+			Self::Request(transport, request) => {
+				let fut = async move {
+					let response = transport.send_stream(*request).await.map_err(|e| Box::new(e) as BoxError)?;
+					let status = response.status;
+					if !status.is_success() {
+						return Err(http_status_error(status, response.text().await));
+					}
+					Ok(response.body)
+				};
+				Box::pin(futures::stream::once(fut).try_flatten())
+			}
+			Self::Bytes(bytes_stream) => bytes_stream,
+		}
+	}
+}
+
+/// The error of a failed streamed request.
+pub(crate) fn http_status_error(status: StatusCode, body: String) -> BoxError {
+	Box::new(GenaiError::HttpError {
+		status,
+		canonical_reason: status.canonical_reason().unwrap_or("Unknown").to_string(),
+		body,
+	})
+}
+
 pub enum StreamMode {
 	// This is used for Cohere with a single `\n`
 	Delimiter(&'static str),
@@ -34,22 +86,20 @@ pub enum StreamMode {
 }
 
 impl WebStream {
-	pub fn new_with_delimiter(reqwest_builder: RequestBuilder, message_delimiter: &'static str) -> Self {
+	pub fn new_with_delimiter(source: impl Into<WebStreamSource>, message_delimiter: &'static str) -> Self {
 		Self {
 			stream_mode: StreamMode::Delimiter(message_delimiter),
-			reqwest_builder: Some(reqwest_builder),
-			response_future: None,
+			source: Some(source.into()),
 			bytes_stream: None,
 			partial_message: None,
 			remaining_messages: None,
 		}
 	}
 
-	pub fn new_with_pretty_json_array(reqwest_builder: RequestBuilder) -> Self {
+	pub fn new_with_pretty_json_array(source: impl Into<WebStreamSource>) -> Self {
 		Self {
 			stream_mode: StreamMode::PrettyJsonArray,
-			reqwest_builder: Some(reqwest_builder),
-			response_future: None,
+			source: Some(source.into()),
 			bytes_stream: None,
 			partial_message: None,
 			remaining_messages: None,
@@ -72,41 +122,6 @@ impl Stream for WebStream {
 
 		// -- Then execute the web poll and processing loop
 		loop {
-			if let Some(ref mut fut) = this.response_future {
-				match Pin::new(fut).poll(cx) {
-					Poll::Ready(Ok(response)) => {
-						// Check HTTP status before proceeding with the stream
-						let status = response.status();
-						if !status.is_success() {
-							this.response_future = None;
-							// For error responses, we need to read the body to get the error message
-							// Store a future that reads the body and returns an error
-							let error_future = async move {
-								let body = response
-									.text()
-									.await
-									.unwrap_or_else(|e| format!("Failed to read error body: {}", e));
-								Err::<Response, BoxError>(Box::new(GenaiError::HttpError {
-									status,
-									canonical_reason: status.canonical_reason().unwrap_or("Unknown").to_string(),
-									body,
-								}))
-							};
-							this.response_future = Some(Box::pin(error_future));
-							continue;
-						}
-						let bytes_stream = response.bytes_stream().map_err(|e| Box::new(e) as BoxError);
-						this.bytes_stream = Some(Box::pin(bytes_stream));
-						this.response_future = None;
-					}
-					Poll::Ready(Err(e)) => {
-						this.response_future = None;
-						return Poll::Ready(Some(Err(e)));
-					}
-					Poll::Pending => return Poll::Pending,
-				}
-			}
-
 			if let Some(ref mut stream) = this.bytes_stream {
 				match stream.as_mut().poll_next(cx) {
 					Poll::Ready(Some(Ok(bytes))) => {
@@ -165,9 +180,8 @@ impl Stream for WebStream {
 				}
 			}
 
-			if let Some(reqwest_builder) = this.reqwest_builder.take() {
-				let fut = async move { reqwest_builder.send().await.map_err(|e| Box::new(e) as BoxError) };
-				this.response_future = Some(Box::pin(fut));
+			if let Some(source) = this.source.take() {
+				this.bytes_stream = Some(source.into_bytes_stream());
 				continue;
 			}
 
@@ -15,7 +15,7 @@ use genai::chat::{
 };
 use genai::embed::EmbedOptions;
 use genai::resolver::{AuthData, AuthResolver, AuthResolverFn, IntoAuthResolverFn};
-use genai::{Client, ClientConfig, ModelIden};
+use genai::{Client, ClientConfig, ListModelsSpec, ModelIden};
 use serde_json::{Value, json};
 use std::sync::Arc;
 use value_ext::JsonValueExt;
@@ -1029,8 +1029,47 @@ pub async fn common_test_list_models(adapter_kind: AdapterKind, contains: &str)
 	Ok(())
 }
 
+pub async fn common_test_list_models_info(spec: impl Into<ListModelsSpec>, contains: &str) -> TestResult<()> {
+	let client = Client::default();
+
+	// -- Exec
+	let infos = client.list_models(spec).await?;
+
+	// -- Check
+	let ids: Vec<&str> = infos.iter().map(|info| info.id.as_str()).collect();
+	assert_contains(&ids, contains);
+
+	Ok(())
+}
+
 // endregion: --- List
 
+// region:    --- Count Tokens
+
+/// Counts the tokens of the simple chat request, and checks the tool definitions are counted.
+pub async fn common_test_count_tokens_ok(model: &str, expect_estimated: bool) -> TestResult<()> {
+	// -- Setup & Fixtures
+	let client = Client::default();
+	let chat_req = seed_chat_req_simple();
+	let chat_req_tool = seed_chat_req_tool_simple();
+
+	// -- Exec
+	let count = client.count_tokens(model, &chat_req, None).await?;
+	let count_tool = client.count_tokens(model, &chat_req_tool, None).await?;
+
+	// -- Check
+	assert_eq!(count.estimated, expect_estimated);
+	assert!(count.input_tokens > 0, "input_tokens should be > 0");
+	assert!(
+		count_tool.input_tokens > count.input_tokens,
+		"tool definitions should be counted"
+	);
+
+	Ok(())
+}
+
+// endregion: --- Count Tokens
+
 // region:    --- Embeddings
 
 pub async fn common_test_embed_single_simple_ok(model: &str) -> TestResult<()> {
@@ -93,6 +93,11 @@ pub async fn extract_stream_end(mut chat_stream: ChatStream) -> TestResult<Strea
 			ChatStreamEvent::ReasoningChunk(s_chunk) => reasoning_content.push(s_chunk.content),
 			ChatStreamEvent::ThoughtSignatureChunk(_) => (), // ignore thought signature chunks for now
 			ChatStreamEvent::ToolCallChunk(_) => (),         // ignore tool call chunks for now
+			ChatStreamEvent::ToolCallStart(_)
+			| ChatStreamEvent::ToolCallArgsDelta(_)
+			| ChatStreamEvent::ToolCallEnd(_) => (),
+			ChatStreamEvent::BlockStart(_) | ChatStreamEvent::BlockStop(_) => (),
+			ChatStreamEvent::Usage(_) => (),
 			ChatStreamEvent::End(s_end) => {
 				stream_end = Some(s_end);
 				break;
@@ -181,4 +181,18 @@ async fn test_list_models() -> TestResult<()> {
 	common_tests::common_test_list_models(AdapterKind::Anthropic, "claude-opus-4-6").await
 }
 
+#[tokio::test]
+async fn test_list_models_info() -> TestResult<()> {
+	common_tests::common_test_list_models_info(AdapterKind::Anthropic, "claude-opus-4-6").await
+}
+
 // endregion: --- List
+
+// region:    --- Count Tokens
+
+#[tokio::test]
+async fn test_count_tokens_ok() -> TestResult<()> {
+	common_tests::common_test_count_tokens_ok(MODEL, false).await
+}
+
+// endregion: --- Count Tokens
@@ -7,6 +7,7 @@ use serial_test::serial;
 
 const MODEL: &str = "command-r7b-12-2024";
 const MODEL_NS: &str = "cohere::command-r7b-12-2024";
+const MODEL_VISION: &str = "command-a-vision-07-2025";
 
 // region:    --- Chat
 
@@ -65,6 +66,32 @@ async fn test_chat_temperature_ok() -> TestResult<()> {
 
 // endregion: --- Chat Stream Tests
 
+// region:    --- Image Tests
+
+#[tokio::test]
+#[serial(cohere)]
+async fn test_chat_image_url_ok() -> TestResult<()> {
+	common_tests::common_test_chat_image_url_ok(MODEL_VISION).await
+}
+
+// endregion: --- Image Tests
+
+// region:    --- Tool Tests
+
+#[tokio::test]
+#[serial(cohere)]
+async fn test_tool_simple_ok() -> TestResult<()> {
+	common_tests::common_test_tool_simple_ok(MODEL).await
+}
+
+#[tokio::test]
+#[serial(cohere)]
+async fn test_tool_full_flow_ok() -> TestResult<()> {
+	common_tests::common_test_tool_full_flow_ok(MODEL).await
+}
+
+// endregion: --- Tool Tests
+
 // region:    --- Resolver Tests
 
 #[tokio::test]
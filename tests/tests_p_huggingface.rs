@@ -0,0 +1,95 @@
+mod support;
+
+use crate::support::{Check, TestResult, common_tests};
+use genai::adapter::AdapterKind;
+use genai::resolver::AuthData;
+use serial_test::serial;
+
+// NOTE: Served via the Hugging Face Inference Providers router (provider auto-selected)
+const MODEL: &str = "hf::meta-llama/Llama-3.1-8B-Instruct";
+
+// region:    --- Chat
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_chat_simple_ok() -> TestResult<()> {
+	common_tests::common_test_chat_simple_ok(MODEL, None).await
+}
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_chat_multi_system_ok() -> TestResult<()> {
+	common_tests::common_test_chat_multi_system_ok(MODEL).await
+}
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_chat_json_structured_ok() -> TestResult<()> {
+	common_tests::common_test_chat_json_structured_ok(MODEL, Some(Check::USAGE)).await
+}
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_chat_temperature_ok() -> TestResult<()> {
+	common_tests::common_test_chat_temperature_ok(MODEL).await
+}
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_chat_stop_sequences_ok() -> TestResult<()> {
+	common_tests::common_test_chat_stop_sequences_ok(MODEL).await
+}
+
+// endregion: --- Chat
+
+// region:    --- Chat Stream Tests
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_chat_stream_simple_ok() -> TestResult<()> {
+	common_tests::common_test_chat_stream_simple_ok(MODEL, None).await
+}
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_chat_stream_capture_content_ok() -> TestResult<()> {
+	common_tests::common_test_chat_stream_capture_content_ok(MODEL).await
+}
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_chat_stream_capture_all_ok() -> TestResult<()> {
+	common_tests::common_test_chat_stream_capture_all_ok(MODEL, None).await
+}
+
+// endregion: --- Chat Stream Tests
+
+// region:    --- Tool Tests
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_tool_simple_ok() -> TestResult<()> {
+	common_tests::common_test_tool_simple_ok(MODEL).await
+}
+
+// endregion: --- Tool Tests
+
+// region:    --- Resolver Tests
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_resolver_auth_ok() -> TestResult<()> {
+	common_tests::common_test_resolver_auth_ok(MODEL, AuthData::from_env("HF_TOKEN")).await
+}
+
+// endregion: --- Resolver Tests
+
+// region:    --- List
+
+#[tokio::test]
+#[serial(huggingface)]
+async fn test_list_models() -> TestResult<()> {
+	common_tests::common_test_list_models(AdapterKind::HuggingFace, "meta-llama/Llama-3.1-8B-Instruct").await
+}
+
+// endregion: --- List
@@ -0,0 +1,29 @@
+mod support;
+
+use crate::support::{TestResult, common_tests};
+
+// NOTE: The feature-extraction pipeline does not return usage
+const MODEL: &str = "hf::sentence-transformers/all-MiniLM-L6-v2";
+
+// region:    --- Single Embedding Tests
+
+#[tokio::test]
+async fn test_huggingface_embed_single_simple_ok() -> TestResult<()> {
+	common_tests::common_test_embed_single_simple_ok_with_usage_check(MODEL, false).await
+}
+
+// endregion: --- Single Embedding Tests
+
+// region:    --- Batch Embedding Tests
+
+#[tokio::test]
+async fn test_huggingface_embed_batch_simple_ok() -> TestResult<()> {
+	common_tests::common_test_embed_batch_simple_ok_with_usage_check(MODEL, false).await
+}
+
+#[tokio::test]
+async fn test_huggingface_embed_batch_empty_should_fail() -> TestResult<()> {
+	common_tests::common_test_embed_empty_batch_should_fail(MODEL).await
+}
+
+// endregion: --- Batch Embedding Tests
@@ -1,8 +1,11 @@
 mod support;
 
-use crate::support::{TestResult, common_tests};
+use crate::support::{TestResult, common_tests, seed_chat_req_simple};
+use genai::Client;
 use genai::adapter::AdapterKind;
+use genai::chat::{ChatOptions, OllamaOptions};
 use genai::resolver::AuthData;
+use tokio_stream::StreamExt;
 
 // https://ollama.com/library
 // "gemma3:4b" "phi3:latest" "gpt-oss:20b"
@@ -44,6 +47,28 @@ async fn test_chat_stop_sequences_ok() -> TestResult<()> {
 	common_tests::common_test_chat_stop_sequences_ok(MODEL).await
 }
 
+#[tokio::test]
+async fn test_chat_ollama_options_ok() -> TestResult<()> {
+	// -- Setup & Fixtures
+	let client = Client::default();
+	let chat_req = seed_chat_req_simple();
+	let ollama_options = OllamaOptions::default()
+		.with_num_ctx(4096)
+		.with_repeat_penalty(1.1)
+		.with_mirostat(0)
+		.with_keep_alive("1m");
+	let options = ChatOptions::default().with_ollama_options(ollama_options);
+
+	// -- Exec
+	let chat_res = client.exec_chat(MODEL, chat_req, Some(&options)).await?;
+
+	// -- Check
+	let content = chat_res.first_text().ok_or("Should have content")?;
+	assert!(!content.trim().is_empty(), "Content should not be empty");
+
+	Ok(())
+}
+
 // endregion: --- Chat
 
 // region:    --- Chat Stream Tests
@@ -125,4 +150,87 @@ async fn test_list_models() -> TestResult<()> {
 	common_tests::common_test_list_models(AdapterKind::Ollama, "gemma3:4b").await
 }
 
+#[tokio::test]
+async fn test_list_models_info() -> TestResult<()> {
+	common_tests::common_test_list_models_info(MODEL, "gemma3:4b").await
+}
+
 // endregion: --- List
+
+// region:    --- Count Tokens
+
+#[tokio::test]
+async fn test_count_tokens_ok() -> TestResult<()> {
+	common_tests::common_test_count_tokens_ok(MODEL, false).await
+}
+
+// endregion: --- Count Tokens
+
+// region:    --- Management
+
+/// NOTE this test assume the "gemma3:4b" is present (the pull is then only a manifest check).
+#[tokio::test]
+async fn test_management_pull_ok() -> TestResult<()> {
+	// -- Setup & Fixtures
+	let client = Client::default();
+
+	// -- Exec
+	let mut pull_stream = client.ollama_pull(MODEL).await?;
+	let mut last_progress = None;
+	while let Some(progress) = pull_stream.next().await {
+		last_progress = Some(progress?);
+	}
+
+	// -- Check
+	let last_progress = last_progress.ok_or("Should have at least one progress event")?;
+	assert!(last_progress.is_success(), "Last status should be success");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_management_show_ok() -> TestResult<()> {
+	// -- Setup & Fixtures
+	let client = Client::default();
+
+	// -- Exec
+	let info = client.ollama_show(MODEL_NS).await?;
+
+	// -- Check
+	assert_eq!(info.model_name, "gemma3:4b");
+	assert!(info.context_length.is_some(), "Should have context_length");
+	assert!(info.quantization_level.is_some(), "Should have quantization_level");
+	assert!(info.has_capability("completion"), "Should have completion capability");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_management_unload_ok() -> TestResult<()> {
+	// -- Setup & Fixtures
+	let client = Client::default();
+
+	// -- Exec & Check
+	client.ollama_unload(MODEL).await?;
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_management_not_ollama_err() -> TestResult<()> {
+	// -- Setup & Fixtures
+	let client = Client::default();
+
+	// -- Exec
+	let res = client.ollama_show("gpt-4o-mini").await;
+
+	// -- Check
+	assert!(
+		matches!(res, Err(genai::Error::AdapterNotSupported { .. })),
+		"Should be AdapterNotSupported"
+	);
+
+	Ok(())
+}
+
+// endregion: --- Management
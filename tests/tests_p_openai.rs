@@ -1,9 +1,10 @@
 mod support;
 
-use crate::support::{Check, TestResult, common_tests};
+use crate::support::{Check, TestResult, common_tests, seed_chat_req_simple};
 use genai::adapter::AdapterKind;
-use genai::chat::ReasoningEffort;
+use genai::chat::{ChatOptions, ReasoningEffort};
 use genai::resolver::AuthData;
+use genai::{Client, UsageBudget, UsageTracker};
 
 // note: "gpt-4o-mini" has issue when image & pdf
 // as for 2025-08-08 gpt-5-mini does not support temperature & stop sequence
@@ -28,6 +29,83 @@ async fn test_chat_reasoning_minimal_ok() -> TestResult<()> {
 // 	common_tests::common_test_chat_simple_ok("gpt-5-pro", None).await
 // }
 
+#[tokio::test]
+async fn test_chat_usage_tracker_ok() -> TestResult<()> {
+	// -- Setup & Fixtures
+	let tracker = UsageTracker::new();
+	let client = Client::builder().with_usage_tracker(tracker.clone()).build();
+	let chat_req = seed_chat_req_simple();
+	let options = ChatOptions::default().with_usage_label("tenant-a");
+
+	// -- Exec
+	let chat_res = client.exec_chat(MODEL2, chat_req, Some(&options)).await?;
+
+	// -- Check
+	let snapshot = tracker.snapshot();
+	assert_eq!(snapshot.total.requests, 1);
+	let tenant = snapshot.by_label.get("tenant-a").ok_or("Should have tenant-a totals")?;
+	assert_eq!(
+		tenant.prompt_tokens,
+		chat_res.usage.prompt_tokens.unwrap_or_default() as u64
+	);
+	assert!(snapshot.by_model.contains_key("openai::gpt-4.1-mini"));
+
+	Ok(())
+}
+
+// Note: The budget check fails before any network call.
+#[tokio::test]
+async fn test_chat_usage_tracker_budget_err() -> TestResult<()> {
+	// -- Setup & Fixtures
+	let tracker = UsageTracker::new().with_label_budget("tenant-a", UsageBudget::default().with_max_requests(0));
+	let client = Client::default();
+	let chat_req = seed_chat_req_simple();
+	let options = ChatOptions::default().with_usage_tracker(tracker).with_usage_label("tenant-a");
+
+	// -- Exec
+	let res = client.exec_chat(MODEL2, chat_req, Some(&options)).await;
+
+	// -- Check
+	assert!(
+		matches!(res, Err(genai::Error::UsageBudgetExceeded { .. })),
+		"Should be UsageBudgetExceeded"
+	);
+
+	Ok(())
+}
+
+// Note: Dry run, no network call.
+#[tokio::test]
+async fn test_build_chat_request_redacted_ok() -> TestResult<()> {
+	// -- Setup & Fixtures
+	let client = Client::builder()
+		.with_auth_resolver_fn(|_| Ok(Some(AuthData::from_single("sk-test-secret"))))
+		.build();
+	let chat_req = seed_chat_req_simple();
+	let options = ChatOptions::default()
+		.with_temperature(0.5)
+		.with_extra_headers(("x-trace-id", "trace-1"));
+
+	// -- Exec
+	let req_data = client.build_chat_request(MODEL2, chat_req, Some(&options)).await?;
+
+	// -- Check
+	assert!(req_data.url.ends_with("chat/completions"), "url: {}", req_data.url);
+	let headers: std::collections::HashMap<_, _> = req_data.headers.iter().collect();
+	assert_eq!(
+		headers.get(&"Authorization".to_string()).map(|v| v.as_str()),
+		Some("Bearer [REDACTED]")
+	);
+	assert_eq!(
+		headers.get(&"x-trace-id".to_string()).map(|v| v.as_str()),
+		Some("trace-1")
+	);
+	assert_eq!(req_data.payload.get("model").and_then(|v| v.as_str()), Some(MODEL2));
+	assert_eq!(req_data.payload.get("temperature").and_then(|v| v.as_f64()), Some(0.5));
+
+	Ok(())
+}
+
 // endregion: --- Provider Specific
 
 // region:    --- Chat
@@ -178,4 +256,18 @@ async fn test_list_models() -> TestResult<()> {
 	common_tests::common_test_list_models(AdapterKind::OpenAI, "gpt-5.2-codex").await
 }
 
+#[tokio::test]
+async fn test_list_models_info() -> TestResult<()> {
+	common_tests::common_test_list_models_info(AdapterKind::OpenAI, "gpt-5.2-codex").await
+}
+
 // endregion: --- List
+
+// region:    --- Count Tokens
+
+#[tokio::test]
+async fn test_count_tokens_ok() -> TestResult<()> {
+	common_tests::common_test_count_tokens_ok(MODEL2, true).await
+}
+
+// endregion: --- Count Tokens
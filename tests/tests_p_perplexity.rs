@@ -0,0 +1,105 @@
+mod support;
+
+use crate::support::{TestResult, common_tests};
+use genai::adapter::AdapterKind;
+use genai::resolver::AuthData;
+use serial_test::serial;
+
+// "sonar", "sonar-pro", "sonar-reasoning", "sonar-reasoning-pro"
+const MODEL: &str = "sonar";
+const MODEL_NS: &str = "perplexity::sonar";
+
+// region:    --- Chat
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_chat_simple_ok() -> TestResult<()> {
+	common_tests::common_test_chat_simple_ok(MODEL, None).await
+}
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_chat_namespaced_ok() -> TestResult<()> {
+	common_tests::common_test_chat_simple_ok(MODEL_NS, None).await
+}
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_chat_multi_system_ok() -> TestResult<()> {
+	common_tests::common_test_chat_multi_system_ok(MODEL).await
+}
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_chat_temperature_ok() -> TestResult<()> {
+	common_tests::common_test_chat_temperature_ok(MODEL).await
+}
+
+// endregion: --- Chat
+
+// region:    --- Chat Stream Tests
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_chat_stream_simple_ok() -> TestResult<()> {
+	common_tests::common_test_chat_stream_simple_ok(MODEL, None).await
+}
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_chat_stream_capture_all_ok() -> TestResult<()> {
+	common_tests::common_test_chat_stream_capture_all_ok(MODEL, None).await
+}
+
+// endregion: --- Chat Stream Tests
+
+// region:    --- Web Search Tests
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_chat_web_search_citations_ok() -> TestResult<()> {
+	use genai::chat::{ChatRequest, Tool, WebSearchConfig, WebSearchRecency};
+
+	// -- Fixtures & Setup
+	let client = genai::Client::default();
+	let web_search_tool = Tool::new_web_search().with_config(
+		WebSearchConfig::default()
+			.with_allowed_domains(["rust-lang.org"])
+			.with_recency(WebSearchRecency::Year),
+	);
+	let chat_req =
+		ChatRequest::from_user("What is the latest version of Rust? (be concise)").append_tool(web_search_tool);
+
+	// -- Exec
+	let res = client.exec_chat(MODEL, chat_req, None).await?;
+
+	// -- Check
+	let citations = res.citations.as_ref().ok_or("Should have citations")?;
+	assert!(!citations.is_empty(), "citations should not be empty");
+	let first_url = citations[0].sources[0].url.as_deref().ok_or("Should have a source url")?;
+	assert!(first_url.contains("rust-lang.org"), "should be from rust-lang.org");
+
+	Ok(())
+}
+
+// endregion: --- Web Search Tests
+
+// region:    --- Resolver Tests
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_resolver_auth_ok() -> TestResult<()> {
+	common_tests::common_test_resolver_auth_ok(MODEL, AuthData::from_env("PERPLEXITY_API_KEY")).await
+}
+
+// endregion: --- Resolver Tests
+
+// region:    --- List
+
+#[tokio::test]
+#[serial(perplexity)]
+async fn test_list_models() -> TestResult<()> {
+	common_tests::common_test_list_models(AdapterKind::Perplexity, "sonar-pro").await
+}
+
+// endregion: --- List